@@ -0,0 +1,54 @@
+//! Hot-path benchmarks for line rendering.
+//!
+//! Output goes to stderr, so run with it discarded:
+//!
+//!     cargo bench --bench logging 2>/dev/null
+
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::hint::black_box;
+use stderr::{ColorMode, FlushPolicy, LogLevel, Stderr};
+
+fn logger(label: Option<&str>, ci_profile: bool) -> Stderr {
+    let mut builder = Stderr::builder()
+        .quiet(false)
+        .color(ColorMode::Always)
+        .flush_policy(FlushPolicy::Bytes(64 * 1024))
+        .ci(None)
+        .ci_profile(ci_profile);
+    if let Some(label) = label {
+        builder = builder.label(label);
+    }
+    builder.build()
+}
+
+fn bench_prefix(c: &mut Criterion) {
+    let mut group = c.benchmark_group("prefix");
+
+    let mut log = logger(None, false);
+    group.bench_function("info", |b| b.iter(|| log.info(black_box("cache warmed up"))));
+
+    let mut log = logger(Some("worker-3"), false);
+    group.bench_function("info_labelled", |b| b.iter(|| log.info(black_box("cache warmed up"))));
+
+    let mut log = logger(Some("worker-3"), true);
+    group.bench_function("info_ci_profile", |b| b.iter(|| log.info(black_box("cache warmed up"))));
+
+    let mut log = logger(None, false);
+    group.bench_function("info_display", |b| {
+        b.iter(|| log.info(format_args!("{} of {} files", black_box(12), black_box(40))))
+    });
+
+    let mut log = logger(None, false);
+    group.bench_function("debug_value", |b| b.iter(|| log.inspect().info(black_box(&(1, "two", 3.0)))));
+
+    group.finish();
+}
+
+fn bench_disabled(c: &mut Criterion) {
+    let mut log = logger(None, false);
+    log.disable(LogLevel::Trace);
+    c.bench_function("disabled_trace", |b| b.iter(|| log.trace(black_box("never printed"))));
+}
+
+criterion_group!(benches, bench_prefix, bench_disabled);
+criterion_main!(benches);
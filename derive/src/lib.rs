@@ -0,0 +1,89 @@
+//! Derive macros for `rdx-stderr`, enabled through its `derive` feature.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::ext::IdentExt;
+use syn::{parse_macro_input, Data, DeriveInput, Index, LitStr};
+
+/// Implements `TableRow` for a struct: one column per field, in declaration
+/// order, each cell the field's `Display` output. Headers are the field
+/// names unless renamed with `#[column(rename = "...")]`; `#[column(skip)]`
+/// leaves a field out.
+///
+/// ```
+/// use stderr::{Stderr, TableRow};
+///
+/// #[derive(TableRow)]
+/// struct Service {
+///     name: String,
+///     #[column(rename = "Uptime (h)")]
+///     uptime: u32,
+///     #[column(skip)]
+///     _pid: u32,
+/// }
+///
+/// let api = Service { name: "api".into(), uptime: 42, _pid: 7 };
+/// assert_eq!(Service::headers(), ["name", "Uptime (h)"]);
+/// assert_eq!(api.columns(), ["api", "42"]);
+///
+/// let mut log = Stderr::new();
+/// log.table_of(&[api]).unwrap();
+/// ```
+#[proc_macro_derive(TableRow, attributes(column))]
+pub fn derive_table_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input).unwrap_or_else(syn::Error::into_compile_error).into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<TokenStream2> {
+    let Data::Struct(data) = &input.data else {
+        return Err(syn::Error::new_spanned(&input.ident, "TableRow can only be derived for structs"));
+    };
+
+    let mut headers = Vec::new();
+    let mut cells = Vec::new();
+    for (i, field) in data.fields.iter().enumerate() {
+        let mut rename = None;
+        let mut skip = false;
+        for attr in field.attrs.iter().filter(|attr| attr.path().is_ident("column")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    rename = Some(meta.value()?.parse::<LitStr>()?.value());
+                    Ok(())
+                } else if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `rename = \"...\"` or `skip`"))
+                }
+            })?;
+        }
+        if skip { continue; }
+
+        let member = match &field.ident {
+            Some(ident) => quote!(#ident),
+            None => {
+                let index = Index::from(i);
+                quote!(#index)
+            }
+        };
+        let name = field.ident.as_ref().map_or_else(|| i.to_string(), |ident| ident.unraw().to_string());
+        headers.push(rename.unwrap_or(name));
+        cells.push(quote!(::std::string::ToString::to_string(&self.#member)));
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::stderr::TableRow for #ident #ty_generics #where_clause {
+            fn columns(&self) -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(#cells),*]
+            }
+
+            fn headers() -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(::std::string::String::from(#headers)),*]
+            }
+        }
+    })
+}
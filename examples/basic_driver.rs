@@ -1,9 +1,11 @@
 // examples/basic_test_driver.rs
 
-use stderr::Stderr;
+use stderr::{Stderr, StderrConfig};
 
 fn main() {
-    let mut log = Stderr::new();
+    // -q / -v / -vv / --debug / --trace / --no-color work on top of the env vars
+    let (config, _rest) = StderrConfig::from_args(std::env::args());
+    let mut log = Stderr::with_config(config);
     
     println!("=== Basic Stderr Library Test ===\n");
     
@@ -14,7 +16,7 @@ fn main() {
     log.okay("Success message test");
     log.note("Note message test");
     
-    // Test debug/trace (only visible with env vars)
+    // Test debug/trace (only visible with env vars or -v flags)
     log.debug("Debug message (visible with DEBUG_MODE=0)");
     log.trace("Trace message (visible with TRACE_MODE=0)");
     log.devlog("Devlog message (visible with DEV_MODE=0)");
@@ -44,6 +46,7 @@ fn main() {
     
     // Test debug printing
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct TestStruct {
         id: u32,
         name: String,
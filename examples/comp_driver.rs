@@ -1,7 +1,7 @@
 // examples/comprehensive_test_driver.rs
 // Tests all stderr features in a single comprehensive run
 
-use stderr::{Stderr, BorderStyle, Glyph, Color as ESC, LogLevel, GlyphSet};
+use stderr::{Stderr, BorderStyle, LogLevel, GlyphSet};
 use std::io::Result;
 
 fn main() -> Result<()> {
@@ -55,6 +55,7 @@ fn test_basic_logging() -> Result<()> {
     
     // Test debug printing
     #[derive(Debug)]
+    #[allow(dead_code)]
     struct TestData {
         name: String,
         value: i32,
@@ -214,6 +215,7 @@ fn test_customization() -> Result<()> {
         trace: "🔍",
         debug: "🐛",
         magic: "✨",
+        ..GlyphSet::default()
     };
     
     let mut log = Stderr::new().with_glyphs(custom_glyphs);
@@ -257,7 +259,7 @@ fn test_edge_cases() -> Result<()> {
     // Test rapid context changes
     for i in 1..=3 {
         log.set_context(&format!("@test{}.VAR.data", i));
-        log.info(&format!("Context {}", i));
+        log.info(format_args!("Context {}", i));
     }
     
     // Test large table
@@ -62,7 +62,7 @@ fn main() {
     
     for context in &contexts {
         log.set_context(context);
-        log.info(&format!("Switched to {}", context));
+        log.info(format_args!("Switched to {}", context));
     }
     
     println!("\n=== Context Test Complete ===");
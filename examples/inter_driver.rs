@@ -125,9 +125,9 @@ For more information, see the documentation."#;
         match log.confirm_builder(prompt)
             .prompt_color(ESC::BLUE)
             .ask()? {
-            Some(true) => log.okay(&format!("✓ {}", step_name)),
+            Some(true) => log.okay(format_args!("✓ {}", step_name)),
             Some(false) => {
-                log.warn(&format!("✗ {} skipped", step_name));
+                log.warn(format_args!("✗ {} skipped", step_name));
                 break;
             },
             None => {
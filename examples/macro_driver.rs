@@ -1,9 +1,10 @@
 
 
 use stderr::{
-    logger,
+    logger, OptionFlag,
     Color as ESC,
     Glyph as ART,
+    qnote, qmagic, qsilly, qdevlog, qinfo_debug, qwarn_debug,
 };
 
 #[allow(dead_code)]
@@ -15,12 +16,24 @@ struct FakeStruct {
 }
 
 fn main() {
+    // Configure the static logger before first use
+    stderr::init(stderr::StderrConfig::from_env());
+    logger.configure(|cfg| cfg.set_flag(OptionFlag::Silly, true));
+
     // Use static logger
     logger.info("Starting macro driver");
     logger.okay("Started macro driver");
     logger.warn("Careful — edge case ahead");
     logger.error("Something went wrong but not really");
 
+    // Macro suite
+    qnote!("Note via macro");
+    qmagic!("Magic via macro ({} sparkles)", 3);
+    qsilly!("Silly via macro");
+    qdevlog!("Devlog via macro (needs DEV_MODE=0)");
+    qinfo_debug!(vec![1, 2, 3]);
+    qwarn_debug!(("pair", 42));
+
     // Use raw logger directly
     let mut log = logger.raw();
     log.trace("Debug only shows if DEBUG_MODE=0");
@@ -58,5 +71,19 @@ fn main() {
     // Custom prefix with color
     let _ = log.print_with_prefix(ESC::YELLOW2, "⚙ INIT", "Bootstrapping complete");
 
+    drop(log);
+
+    // Per-thread scopes layered over the static logger
+    let workers: Vec<_> = (1..=2)
+        .map(|n| std::thread::spawn(move || {
+            logger.set_scope(format!("worker-{}", n));
+            logger.info("Processing batch");
+        }))
+        .collect();
+    for worker in workers {
+        worker.join().unwrap();
+    }
+    logger.info("Main thread keeps its own label");
+
     println!("\n✅ All logger macros exercised.");
 }
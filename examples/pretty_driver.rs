@@ -68,11 +68,11 @@ fn main() -> Result<()> {
         Ok(Some(true)) => log.okay("User confirmed the action."),
         Ok(Some(false)) => log.warn("User denied the action."),
         Ok(None) => log.note("User quit the prompt."),
-        Err(e) => log.error(&format!("Could not get confirmation: {}", e)),
+        Err(e) => log.error(format_args!("Could not get confirmation: {}", e)),
     }
     println!();
 
-    log.info(&format!("Showcase complete for stderr version {}.", VERSION));
+    log.info(format_args!("Showcase complete for stderr version {}.", VERSION));
 
     Ok(())
 }
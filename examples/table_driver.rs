@@ -88,10 +88,24 @@ fn main() -> Result<()> {
     
     log.box_double("Double border box\nFor critical alerts")?;
     println!();
+
+    log.box_rounded("Rounded border box\nFor softer notices")?;
+    println!();
     
     // Test 8: Banner
     log.banner("Test Banner", '=')?;
     log.info("Content under the banner");
+
+    println!();
+
+    // Test 9: Rendering to strings (composable output)
+    log.info("Test 9: Rendered Table Inside a Box");
+    let table = log.render_table(&[
+        &["Key", "Value"],
+        &["region", "us-east-1"],
+        &["tier", "gold"],
+    ], false);
+    log.box_double(&table)?;
     
     println!("\n=== Table Test Complete ===");
     
@@ -15,6 +15,17 @@
 //! - **interactive**: User prompts, confirmations, and interactive elements
 //! - **formatting**: Tables, boxes, banners, and advanced text formatting
 //! - **auto-fn-names**: Automatic function name detection for tracing
+//! - **async**: tokio-based prompt variants (`confirm_async`, `ask_async`)
+//! - **serde**: `Serialize`/`Deserialize` for `StderrConfig` and `StderrConfig::from_file` (TOML/JSON)
+//! - **syslog / journald**: forward messages to the system log (unix only)
+//! - **otel**: export messages as OpenTelemetry logs and trace scopes as spans
+//! - **tracing-layer**: `StderrLayer`, a `tracing-subscriber` layer that prints spans as trace trees
+//! - **log**: `log_bridge::init()` routes the `log` facade through the global logger,
+//!   plus conversions between `LogLevel` and `log::Level`
+//! - **anstyle / owo-colors**: convert colors and `ColorSpec` styles to and from those crates
+//! - **clap**: `StderrArgs` with the standard `-q`/`-v`/`--debug`/`--color` flags
+//! - **max-level-info / max-level-debug**: compile verbose levels down to no-ops
+//!   (`release-max-level-*` variants only apply to release builds)
 //!
 //! ## Quick Start
 //!
@@ -51,37 +62,64 @@ pub mod meta;
 #[path = "rdx/macros.rs"]
 pub mod macros;
 
+#[cfg(feature = "log")]
+#[path = "rdx/log_bridge.rs"]
+pub mod log_bridge;
+
 // --- Re-export the public API ---
 
 // Core exports (always available)
 pub use stderr::{
-    Stderr, StderrConfig, LogLevel, OptionFlag, GlyphSet,
-    logger, StaticLogger
+    Stderr, StderrBuilder, StderrConfig, ColorMode, OutputFormat, ErrorPolicy, ConfirmPolicy, LogLevel, LevelMask, Route, OptionFlag, GlyphSet, GlyphSetBuilder, supports_unicode, StyleSet, Theme, PrefixStyle, FlushPolicy, WidthPolicy, SoftWrap, NARROW_WIDTH, CiVendor, Sink, Record, LevelWriter, StderrHandle, MultiProgress, ProgressBar, ContextScope, IndentScope, ContextBannerStyle, LogFlag, LOG_FLAGS,
+    logger, StaticLogger, StaticContextScope, FlushGuard, LoggerGuard, init
 };
 
 // ESC and styling
 pub use esc::colors::Color;
-pub use esc::glyphs::{Glyph, debug_glyphs_string};
-pub use esc::style::Style;
+pub use esc::glyphs::{Glyph, GlyphCategory, GlyphEntry, debug_glyphs_string, render_glyph_catalog};
+pub use esc::style::{Style, StyledString};
+pub use esc::markup::{parse_markup, render_markup};
 pub use esc::boxes::{BorderStyle, BoxChars};
 
 // Utilities
 pub use utils::helpers::{readline, repeat_char, term_width, env};
+pub use utils::text::{strip_ansi, display_width, truncate, Ellipsis};
+pub use utils::ansi::{StyledSpan, parse_ansi, render_spans, truncate_ansi, visible_width, wrap_ansi};
+#[cfg(feature = "async")]
+pub use utils::helpers::readline_async;
 pub use utils::flag::flag_table;
-pub use utils::grid::print_color_grid;
+pub use utils::grid::{print_color_grid, render_color_grid};
 
 // Meta information
-pub use meta::{STDERR_VERSION as VERSION, help_string};
+pub use meta::{STDERR_VERSION as VERSION, help_string, BuildInfo};
 
 // Feature-gated exports
 #[cfg(feature = "trace")]
-pub use stderr::TraceScope;
+pub use stderr::{TraceScope, StaticTraceScope};
+
+#[cfg(feature = "tracing-layer")]
+pub use stderr::StderrLayer;
+
+#[cfg(feature = "clap")]
+pub use stderr::StderrArgs;
+
+#[cfg(all(unix, feature = "syslog"))]
+pub use stderr::SyslogSink;
+
+#[cfg(all(unix, feature = "journald"))]
+pub use stderr::JournaldSink;
+
+#[cfg(feature = "otel")]
+pub use stderr::OtelSink;
 
 #[cfg(feature = "interactive")]
-pub use stderr::{ConfirmBuilder, InteractiveExt};
+pub use stderr::{ConfirmBuilder, InputBuilder, InteractiveExt};
 
 #[cfg(feature = "formatting")]
-pub use stderr::{TableRow, FormattingExt};
+pub use stderr::{TableRow, FormattingExt, HelpBuilder, Panel, SectionScope, Align, Overflow, SortOrder, TableBuilder, TreeNode, DiffBuilder, CodeBuilder, BigBannerBuilder};
+
+#[cfg(feature = "derive")]
+pub use stderr_derive::TableRow;
 
 // --- Type Aliases for Convenience ---
 
@@ -111,7 +149,7 @@ qtrace_scope!(\"my_function\"); // Logs entry and exit automatically
 ```
 
 Visual output:
-```
+```text
 λ┄┄┄[my_function]
     ┆
     └┄┄> starting work
@@ -126,9 +164,10 @@ pub mod trace_docs {}
 
 The `interactive` feature adds user prompts and confirmations:
 
-```rust
-use stderr::Stderr;
+```rust,no_run
+use stderr::{Stderr, BorderStyle};
 
+# fn main() -> std::io::Result<()> {
 let mut log = Stderr::new();
 
 // Simple confirmation
@@ -143,6 +182,8 @@ if log.confirm_builder(\"Delete all files?\")
     .ask()?.unwrap_or(false) {
     log.warn(\"Files deleted\");
 }
+# Ok(())
+# }
 ```
 "]
 pub mod interactive_docs {}
@@ -156,6 +197,7 @@ The `formatting` feature adds tables, boxes, and banners:
 ```rust
 use stderr::{Stderr, BorderStyle};
 
+# fn main() -> std::io::Result<()> {
 let mut log = Stderr::new();
 
 // Banners
@@ -170,6 +212,8 @@ log.simple_table(&[
     &[\"config.env\", \"file\", \"1.2KB\"],
     &[\"secrets\", \"dir\", \"--\"],
 ])?;
+# Ok(())
+# }
 ```
 "]
 pub mod formatting_docs {}
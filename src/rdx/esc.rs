@@ -16,3 +16,10 @@ pub mod style;
 
 #[path = "esc/boxes.rs"]
 pub mod boxes;
+
+#[path = "esc/markup.rs"]
+pub mod markup;
+
+#[cfg(any(feature = "anstyle", feature = "owo-colors"))]
+#[path = "esc/interop.rs"]
+pub mod interop;
@@ -1,16 +1,35 @@
 //!  esc/boxes.rs
 
 //! re : https://en.wikipedia.org/wiki/Box-drawing_characters
-  #[derive(Default)]
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
   pub enum BorderStyle {
       #[default]
       Light,
       Heavy,
       Double,
-
+      /// Light lines with rounded corners
+      Rounded,
+      /// `+`, `-` and `|`, for plain-text output
+      Ascii,
+      /// A caller-supplied character set. Each piece must be exactly one
+      /// column wide: borders are laid out by repeating and counting them,
+      /// so wide pieces (most emoji, CJK) or empty ones break the alignment.
+      ///
+      /// ```
+      /// use stderr::{BorderStyle, BoxChars, Stderr};
+      ///
+      /// let brand = BorderStyle::Custom(BoxChars {
+      ///     horizontal: "~",
+      ///     ..BoxChars::from_style(&BorderStyle::Rounded)
+      /// });
+      /// Stderr::new().boxed("ACME deploy tool", brand).unwrap();
+      /// ```
+      Custom(BoxChars),
   }
 
 
+  /// The pieces of a border; each is one column wide (see [`BorderStyle::Custom`])
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
   pub struct BoxChars {
 
       pub top_left: &'static str,
@@ -30,6 +49,17 @@
 
 
 impl BoxChars {
+    /// Whether every piece is plain ASCII, so the set is safe on any terminal
+    pub fn is_ascii(&self) -> bool {
+        [
+            self.top_left, self.top_right, self.bottom_left, self.bottom_right,
+            self.horizontal, self.vertical,
+            self.top_t, self.bottom_t, self.left_t, self.right_t, self.cross,
+        ]
+        .iter()
+        .all(|piece| piece.is_ascii())
+    }
+
     /// Creates a character set from a given `BorderStyle`.
     pub fn from_style(style: &BorderStyle) -> Self {
         match style {
@@ -72,6 +102,33 @@ impl BoxChars {
                 right_t: "\u{2563}",       // ╣
                 cross: "\u{256C}",         // ╬
             },
+            BorderStyle::Rounded => Self {
+                top_left: "\u{256D}",      // ╭
+                top_right: "\u{256E}",     // ╮
+                bottom_left: "\u{2570}",   // ╰
+                bottom_right: "\u{256F}",  // ╯
+                horizontal: "\u{2500}",    // ─
+                vertical: "\u{2502}",      // │
+                top_t: "\u{252C}",         // ┬
+                bottom_t: "\u{2534}",      // ┴
+                left_t: "\u{251C}",        // ├
+                right_t: "\u{2524}",       // ┤
+                cross: "\u{253C}",         // ┼
+            },
+            BorderStyle::Custom(chars) => *chars,
+            BorderStyle::Ascii => Self {
+                top_left: "+",
+                top_right: "+",
+                bottom_left: "+",
+                bottom_right: "+",
+                horizontal: "-",
+                vertical: "|",
+                top_t: "+",
+                bottom_t: "+",
+                left_t: "+",
+                right_t: "+",
+                cross: "+",
+            },
         }
     }
 }
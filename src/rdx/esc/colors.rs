@@ -6,7 +6,8 @@
 //! FFunctions and types for manipulating text color in the terminal.
 
 
-use termcolor::Color as TermColor;
+use std::io::Write;
+use termcolor::{Ansi, Color as TermColor, ColorSpec, WriteColor};
 
 
 
@@ -43,13 +44,235 @@ impl Color {
   pub const PINK: TermColor = TermColor::Ansi256(211);
 }
 
+impl Color {
+  /// The escape sequence that sets `color` as the foreground, e.g. for a PS1
+  /// or text handed to another writer.
+  ///
+  /// ```
+  /// use stderr::Color;
+  ///
+  /// let prompt = format!("{}ok{}", Color::to_ansi_fg(Color::GREEN), stderr::Style::ANSI_RESET);
+  /// assert_eq!(prompt, "\x1b[0m\x1b[38;5;10mok\x1b[0m");
+  /// ```
+  pub fn to_ansi_fg(color: TermColor) -> String {
+      spec_to_ansi(ColorSpec::new().set_fg(Some(color)))
+  }
+
+  /// The escape sequence that sets `color` as the background
+  pub fn to_ansi_bg(color: TermColor) -> String {
+      spec_to_ansi(ColorSpec::new().set_bg(Some(color)))
+  }
+}
+
+/// Every named palette constant, in declaration order
+const PALETTE: [(&str, TermColor); 26] = [
+  ("VOID", Color::VOID),
+  ("FOREST", Color::FOREST),
+  ("OCEAN", Color::OCEAN),
+  ("RED2", Color::RED2),
+  ("RED", Color::RED),
+  ("BLUE", Color::BLUE),
+  ("BLUE2", Color::BLUE2),
+  ("YELLOW", Color::YELLOW),
+  ("YELLOW2", Color::YELLOW2),
+  ("ORANGE", Color::ORANGE),
+  ("ORANGE2", Color::ORANGE2),
+  ("GREEN", Color::GREEN),
+  ("GREEN2", Color::GREEN2),
+  ("CYAN", Color::CYAN),
+  ("PURPLE", Color::PURPLE),
+  ("PURPLE2", Color::PURPLE2),
+  ("BLACK0", Color::BLACK0),
+  ("BLACK", Color::BLACK),
+  ("WHITE", Color::WHITE),
+  ("WHITE2", Color::WHITE2),
+  ("GREY", Color::GREY),
+  ("GREY2", Color::GREY2),
+  ("GREY3", Color::GREY3),
+  ("MAGENTA", Color::MAGENTA),
+  ("MAGENTA2", Color::MAGENTA2),
+  ("PINK", Color::PINK),
+];
+
+// xterm's values for the 16 system colors
+const SYSTEM_RGB: [(u8, u8, u8); 16] = [
+  (0, 0, 0), (205, 0, 0), (0, 205, 0), (205, 205, 0),
+  (0, 0, 238), (205, 0, 205), (0, 205, 205), (229, 229, 229),
+  (127, 127, 127), (255, 0, 0), (0, 255, 0), (255, 255, 0),
+  (92, 92, 255), (255, 0, 255), (0, 255, 255), (255, 255, 255),
+];
+
+impl Color {
+  /// Looks a palette constant up by name, ignoring case
+  ///
+  /// ```
+  /// use stderr::Color;
+  ///
+  /// assert_eq!(Color::by_name("orange2"), Some(Color::ORANGE2));
+  /// assert_eq!(Color::by_name("chartreuse"), None);
+  /// ```
+  pub fn by_name(name: &str) -> Option<TermColor> {
+      Self::palette().find(|(n, _)| n.eq_ignore_ascii_case(name)).map(|(_, color)| color)
+  }
 
+  /// Every named palette color as `(name, color)`
+  pub fn palette() -> impl Iterator<Item = (&'static str, TermColor)> {
+      PALETTE.iter().copied()
+  }
+
+  /// The name of a palette color, if it is one
+  pub fn name_of(color: TermColor) -> Option<&'static str> {
+      Self::palette().find(|&(_, c)| c == color).map(|(name, _)| name)
+  }
+
+  /// Approximate RGB of a terminal color, using xterm's defaults for the
+  /// system and 256-color palettes
+  pub fn to_rgb(color: TermColor) -> Option<(u8, u8, u8)> {
+      let index = match color {
+          TermColor::Black => 0,
+          TermColor::Red => 1,
+          TermColor::Green => 2,
+          TermColor::Yellow => 3,
+          TermColor::Blue => 4,
+          TermColor::Magenta => 5,
+          TermColor::Cyan => 6,
+          TermColor::White => 7,
+          TermColor::Ansi256(n) => n,
+          TermColor::Rgb(r, g, b) => return Some((r, g, b)),
+          _ => return None,
+      };
+      Some(ansi256_rgb(index))
+  }
+
+  /// The palette color closest to an RGB value, e.g. to map a theme's hex
+  /// color onto the named palette
+  ///
+  /// ```
+  /// use stderr::Color;
+  ///
+  /// assert_eq!(Color::nearest(250, 170, 10), ("ORANGE", Color::ORANGE));
+  /// ```
+  pub fn nearest(r: u8, g: u8, b: u8) -> (&'static str, TermColor) {
+      Self::palette()
+          .min_by_key(|&(_, color)| Self::to_rgb(color).map_or(u32::MAX, |rgb| distance(rgb, (r, g, b))))
+          .unwrap_or(("WHITE", Color::WHITE))
+  }
+
+  /// The 256-color index closest to an RGB value, for terminals without
+  /// true color
+  pub fn nearest_ansi256(r: u8, g: u8, b: u8) -> u8 {
+      (16..=255u8).min_by_key(|&n| distance(ansi256_rgb(n), (r, g, b))).unwrap_or(16)
+  }
+}
+
+impl Color {
+  /// A true color (24-bit) value
+  pub const fn rgb(r: u8, g: u8, b: u8) -> TermColor {
+      TermColor::Rgb(r, g, b)
+  }
+
+  /// Parses `#rrggbb` or `#rgb`, with or without the `#`
+  ///
+  /// ```
+  /// use stderr::Color;
+  /// use termcolor::Color as TermColor;
+  ///
+  /// assert_eq!(Color::from_hex("#ff8800"), Some(TermColor::Rgb(255, 136, 0)));
+  /// assert_eq!(Color::from_hex("f80"), Some(TermColor::Rgb(255, 136, 0)));
+  /// assert_eq!(Color::from_hex("#ff88"), None);
+  /// ```
+  pub fn from_hex(hex: &str) -> Option<TermColor> {
+      let hex = hex.trim().trim_start_matches('#');
+      if !hex.is_ascii() { return None; }
+      let channel = |i: usize, len: usize| u8::from_str_radix(&hex[i * len..(i + 1) * len], 16).ok();
+      match hex.len() {
+          6 => Some(TermColor::Rgb(channel(0, 2)?, channel(1, 2)?, channel(2, 2)?)),
+          // `f80` is `ff8800`
+          3 => Some(TermColor::Rgb(channel(0, 1)? * 17, channel(1, 1)? * 17, channel(2, 1)? * 17)),
+          _ => None,
+      }
+  }
+
+  /// A palette name or a hex value, e.g. from a config file
+  ///
+  /// ```
+  /// use stderr::Color;
+  ///
+  /// assert_eq!(Color::parse("orange"), Some(Color::ORANGE));
+  /// assert_eq!(Color::parse("#ffa500"), Some(Color::rgb(255, 165, 0)));
+  /// ```
+  pub fn parse(spec: &str) -> Option<TermColor> {
+      Self::by_name(spec.trim()).or_else(|| Self::from_hex(spec))
+  }
+
+  /// Whether the terminal advertises true color through `COLORTERM`
+  /// (`truecolor` or `24bit`)
+  pub fn supports_truecolor() -> bool {
+      std::env::var("COLORTERM").is_ok_and(|v| v == "truecolor" || v == "24bit")
+  }
+
+  /// `color` as the terminal can show it: true color unchanged when
+  /// `truecolor` is set, else the nearest 256-color entry
+  pub fn downgrade(color: TermColor, truecolor: bool) -> TermColor {
+      match color {
+          TermColor::Rgb(r, g, b) if !truecolor => nearest_ansi(r, g, b),
+          other => other,
+      }
+  }
+}
+
+/// Quantizes an RGB value to the nearest 256-color entry, the same mapping
+/// the logger applies on terminals without true color.
+///
+/// Only the color cube and grayscale ramp (16-255) are candidates: the 16
+/// system colors are themeable, so their RGB can't be relied on. Handy for
+/// gradients that should look right on both classes of terminal:
+///
+/// ```
+/// use stderr::esc::colors::nearest_ansi;
+/// use termcolor::Color as TermColor;
+///
+/// let truecolor = std::env::var("COLORTERM").is_ok_and(|v| v == "truecolor");
+/// let steps: Vec<TermColor> = (0..=4u8)
+///     .map(|i| (i * 63, 0, 255 - i * 63))
+///     .map(|(r, g, b)| if truecolor { TermColor::Rgb(r, g, b) } else { nearest_ansi(r, g, b) })
+///     .collect();
+/// assert_eq!(nearest_ansi(255, 0, 0), TermColor::Ansi256(196));
+/// ```
+pub fn nearest_ansi(r: u8, g: u8, b: u8) -> TermColor {
+  TermColor::Ansi256(Color::nearest_ansi256(r, g, b))
+}
+
+fn ansi256_rgb(n: u8) -> (u8, u8, u8) {
+  const LEVELS: [u8; 6] = [0, 95, 135, 175, 215, 255];
+  match n {
+      0..=15 => SYSTEM_RGB[n as usize],
+      16..=231 => {
+          let i = n - 16;
+          (LEVELS[(i / 36) as usize], LEVELS[(i / 6 % 6) as usize], LEVELS[(i % 6) as usize])
+      }
+      _ => {
+          let v = 8 + (n - 232) * 10;
+          (v, v, v)
+      }
+  }
+}
+
+// Squared distance, weighted toward green like the eye
+fn distance(a: (u8, u8, u8), b: (u8, u8, u8)) -> u32 {
+  let d = |x: u8, y: u8| (i32::from(x) - i32::from(y)).pow(2) as u32;
+  2 * d(a.0, b.0) + 4 * d(a.1, b.1) + 3 * d(a.2, b.2)
+}
+
+/// Renders a `ColorSpec` through termcolor's own ANSI writer, so the result
+/// matches what the logger prints byte for byte.
+pub(crate) fn spec_to_ansi(spec: &ColorSpec) -> String {
+  let mut ansi = Ansi::new(Vec::new());
+  let _ = ansi.set_color(spec);
+  let _ = ansi.flush();
+  String::from_utf8(ansi.into_inner()).unwrap_or_default()
+}
 
-// pub struct TrueColor {
-//     pub r: u8,
-//     pub g: u8,
-//     pub b: u8,
-// }
 
 //   let mut writer = StandardStream::stdout(ColorChoice::Auto);
 
@@ -1,13 +1,17 @@
 //! esc/glyphs_strum.rs
 //! note enum != strum
 //! A curated collection of Unicode (non-emoji) glyphs for use in terminal output.
+use std::collections::{HashMap, HashSet};
+use std::str::FromStr;
+use std::sync::{Mutex, PoisonError, RwLock};
+use once_cell::sync::Lazy;
 use strum::IntoEnumIterator;
-use strum_macros::{AsRefStr, Display, EnumIter};
+use strum_macros::{AsRefStr, Display, EnumIter, IntoStaticStr};
 
 
 
 /// A comprehensive, iterable enum of Unicode glyphs for UI design.
-#[derive(Debug, Clone, Copy, EnumIter, Display, AsRefStr, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, EnumIter, Display, AsRefStr, IntoStaticStr, PartialEq, Eq)]
 pub enum Glyph {
     // --- General & Common ---
     #[strum(to_string = "\u{2756}")] Usage,
@@ -127,36 +131,222 @@ pub enum Glyph {
 }
 
 
-/// Generates a formatted string table of all available `Glyph` variants.
-///
-/// This function is intended for debugging or demonstration purposes. It
-/// is fully automatic and will include any new glyphs added to the enum.
-pub fn debug_glyphs_string() -> String {
-    let all_glyphs: Vec<Glyph> = Glyph::iter().collect();
-    let items_per_row = 5; // Adjust number of columns here
+/// The groups glyphs are listed under in the catalog
+#[derive(Debug, Clone, Copy, EnumIter, Display, PartialEq, Eq, Hash)]
+pub enum GlyphCategory {
+    General,
+    Status,
+    Bullets,
+    Arrows,
+    Actions,
+    Time,
+    Misc,
+    Greek,
+    #[strum(to_string = "Box Drawing")]
+    BoxDrawing,
+}
 
-    // Estimate capacity to reduce reallocations
-    let mut output = String::with_capacity(all_glyphs.len() * 25);
+/// One glyph with the details theme authors look for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GlyphEntry {
+    pub glyph: Glyph,
+    /// Variant name, e.g. `"Lambda"`
+    pub name: String,
+    pub ch: char,
+    /// Unicode scalar value (`U+03BB` is `0x3BB`)
+    pub codepoint: u32,
+    pub category: GlyphCategory,
+}
 
-    // --- Build Banner ---
-    // We'll create a simple banner since we don't have access to the Stderr methods
-    let title = " Available Glyphs ";
-    let width = 80;
-    let padding = (width - title.len()) / 2;
-    let bar = "=".repeat(padding);
-    output.push_str(&format!("{}{}{}\n", bar, title, bar));
+impl Glyph {
+    /// The variant name, e.g. `"Lambda"`
+    pub fn name(self) -> String {
+        format!("{:?}", self)
+    }
 
-    // --- Build Table ---
-    for row in all_glyphs.chunks(items_per_row) {
-        for glyph in row {
-            let name: &str = glyph.as_ref();
-            let code = format!("{:X}", glyph.to_string().chars().next().unwrap() as u32);
+    /// The glyph's character
+    pub fn char(self) -> char {
+        self.as_ref().chars().next().unwrap_or(' ')
+    }
 
-            // Use `format_args!` and `push_str` for efficient string building
-            output.push_str(&format!("{} {:<15} U+{: <8}", glyph, name, code));
+    pub fn category(self) -> GlyphCategory {
+        use Glyph::*;
+        match self {
+            Usage | Cmdr | Boto | Gear | Info | Ellipsis => GlyphCategory::General,
+            Pass | Fail | Mark | Dots | FlagOff | FlagOn | Bolt | Anchor | Unlock => GlyphCategory::Status,
+            Bullet | Dot | Target | RadioOn | RadioOff | SquareSmall | Pointer => GlyphCategory::Bullets,
+            Up | Down | Right | Left | HeavyArrowRight | DownArr | UpArr | ArrowSw | ArrowCurveSe
+            | CurveArrowLeft | Uarr | ReturnSymbol | NewlineSymbol => GlyphCategory::Arrows,
+            Undo | Recover | RedoClosed => GlyphCategory::Actions,
+            Clock | Timer | Hourglass | Calendar => GlyphCategory::Time,
+            Alpha | Beta | Gamma | DeltaSm | Epsilon | Zeta | Eta | Theta | Iota | Kappa | Lambda
+            | Mu | Nu | Xi | Omicron | Pi | Rho | Sigma | Tau | Upsilon | Phi | Chi | Psi
+            | Omega => GlyphCategory::Greek,
+            HLine | VLine | TRight | CornerUr => GlyphCategory::BoxDrawing,
+            _ => GlyphCategory::Misc,
         }
-        output.push('\n');
     }
 
+    /// Every glyph as a structured entry, in declaration order
+    ///
+    /// ```
+    /// use stderr::{Glyph, GlyphCategory};
+    ///
+    /// let greek: Vec<_> = Glyph::catalog()
+    ///     .into_iter()
+    ///     .filter(|e| e.category == GlyphCategory::Greek)
+    ///     .collect();
+    /// assert!(greek.iter().any(|e| e.name == "Lambda" && e.codepoint == 0x3BB));
+    /// ```
+    pub fn catalog() -> Vec<GlyphEntry> {
+        Glyph::iter()
+            .map(|glyph| GlyphEntry {
+                glyph,
+                name: glyph.name(),
+                ch: glyph.char(),
+                codepoint: glyph.char() as u32,
+                category: glyph.category(),
+            })
+            .collect()
+    }
+
+    /// Glyphs in one category
+    pub fn in_category(category: GlyphCategory) -> impl Iterator<Item = Glyph> {
+        Glyph::iter().filter(move |glyph| glyph.category() == category)
+    }
+
+    /// Looks a built-in glyph up by variant name, ignoring case
+    pub fn by_name(name: &str) -> Option<Glyph> {
+        Glyph::iter().find(|glyph| glyph.name().eq_ignore_ascii_case(name))
+    }
+
+    /// The glyph as a `&'static str`, e.g. for a `GlyphSet`
+    pub fn as_static(self) -> &'static str {
+        self.into()
+    }
+
+    /// Registers a named glyph string at runtime (it may be several
+    /// characters or an emoji), replacing any earlier one with that name.
+    /// Custom names take precedence over built-in ones in [`resolve`](Self::resolve).
+    ///
+    /// Registered strings live for the rest of the program; each distinct
+    /// string is stored once, however often it is registered.
+    pub fn register(name: &str, glyph: &str) {
+        let glyph = intern(glyph);
+        CUSTOM_GLYPHS.write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(name.to_ascii_lowercase(), glyph);
+    }
+
+    /// Removes a custom glyph, returning whether it existed
+    pub fn unregister(name: &str) -> bool {
+        CUSTOM_GLYPHS.write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(&name.to_ascii_lowercase())
+            .is_some()
+    }
+
+    /// Resolves a name from a config or theme file: a custom glyph first,
+    /// then a built-in one (case-insensitive either way).
+    ///
+    /// ```
+    /// use stderr::{Glyph, LogLevel, Stderr};
+    ///
+    /// Glyph::register("deploy", "\u{2B06}");
+    /// assert_eq!(Glyph::resolve("Deploy"), Some("\u{2B06}"));
+    /// assert_eq!(Glyph::resolve("lambda"), Some("\u{03BB}"));
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_glyph(LogLevel::Info, Glyph::resolve("deploy").unwrap());
+    /// ```
+    pub fn resolve(name: &str) -> Option<&'static str> {
+        let custom = CUSTOM_GLYPHS.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(&name.to_ascii_lowercase())
+            .copied();
+        custom.or_else(|| Glyph::by_name(name).map(Glyph::as_static))
+    }
+
+    /// Registered custom glyphs as `(name, glyph)`, sorted by name
+    pub fn custom() -> Vec<(String, &'static str)> {
+        let mut glyphs: Vec<_> = CUSTOM_GLYPHS.read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .iter()
+            .map(|(name, &glyph)| (name.clone(), glyph))
+            .collect();
+        glyphs.sort();
+        glyphs
+    }
+}
+
+// Named glyph strings registered at runtime (keys are lowercase)
+static CUSTOM_GLYPHS: Lazy<RwLock<HashMap<String, &'static str>>> = Lazy::new(Default::default);
+
+// Every glyph string ever registered, so re-registering leaks nothing new
+static INTERNED: Lazy<Mutex<HashSet<&'static str>>> = Lazy::new(Default::default);
+
+pub(crate) fn intern(glyph: &str) -> &'static str {
+    let mut interned = INTERNED.lock().unwrap_or_else(PoisonError::into_inner);
+    match interned.get(glyph) {
+        Some(existing) => existing,
+        None => {
+            let leaked: &'static str = Box::leak(glyph.to_string().into_boxed_str());
+            interned.insert(leaked);
+            leaked
+        }
+    }
+}
+
+/// Parses a built-in glyph by variant name, ignoring case
+///
+/// ```
+/// use stderr::Glyph;
+///
+/// assert_eq!("Lambda".parse::<Glyph>(), Ok(Glyph::Lambda));
+/// assert!("nope".parse::<Glyph>().is_err());
+/// ```
+impl FromStr for Glyph {
+    type Err = strum::ParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Glyph::by_name(name).ok_or(strum::ParseError::VariantNotFound)
+    }
+}
+
+// `λ Lambda          U+03BB   ` - one catalog cell
+const CELL_WIDTH: usize = 28;
+
+/// Renders the catalog grouped by category, in as many columns as fit in
+/// `width`; pass a category to list only that one.
+pub fn render_glyph_catalog(category: Option<GlyphCategory>, width: usize) -> String {
+    let per_row = (width / CELL_WIDTH).max(1);
+    let mut output = String::new();
+    let entries = Glyph::catalog();
+    for group in GlyphCategory::iter().filter(|&c| category.map_or(true, |only| only == c)) {
+        let in_group: Vec<&GlyphEntry> = entries.iter().filter(|e| e.category == group).collect();
+        if in_group.is_empty() { continue; }
+        output.push_str(&format!("{}\n", group));
+        for row in in_group.chunks(per_row) {
+            let line: String = row.iter()
+                .map(|e| format!("{} {:<15} {:<10}", e.ch, e.name, format!("U+{:04X}", e.codepoint)))
+                .collect();
+            output.push_str(line.trim_end());
+            output.push('\n');
+        }
+        output.push('\n');
+    }
     output
 }
+
+/// Generates a formatted string table of all available `Glyph` variants,
+/// grouped by category and fitted to the terminal width.
+///
+/// This function is intended for debugging or demonstration purposes. It
+/// is fully automatic and will include any new glyphs added to the enum.
+pub fn debug_glyphs_string() -> String {
+    let width = crate::utils::helpers::term_width();
+    let title = " Available Glyphs ";
+    let padding = width.saturating_sub(title.len()) / 2;
+    let bar = "=".repeat(padding);
+    format!("{}{}{}\n{}", bar, title, bar, render_glyph_catalog(None, width))
+}
@@ -0,0 +1,153 @@
+// esc/interop.rs
+//! # Ecosystem Style Conversions
+//!
+//! Bridges between this crate's colors / `ColorSpec` styles and `anstyle`
+//! (what clap uses for its help styling) or `owo-colors`, so one palette can
+//! be shared between `--help` output, themes and log prefixes.
+//!
+//! The conversions live on the [`Color`] and [`Style`] namespaces, since both
+//! sides are foreign types (`termcolor` and `anstyle`) and can't get `From` impls.
+
+use termcolor::{Color as TermColor, ColorSpec};
+
+#[cfg(feature = "anstyle")]
+use super::colors::Color;
+use super::style::Style;
+
+#[cfg(feature = "anstyle")]
+impl Color {
+  /// Converts a terminal color to its `anstyle` equivalent
+  pub fn to_anstyle(color: TermColor) -> Option<anstyle::Color> {
+      use anstyle::AnsiColor as A;
+      let ansi = match color {
+          TermColor::Black => A::Black,
+          TermColor::Red => A::Red,
+          TermColor::Green => A::Green,
+          TermColor::Yellow => A::Yellow,
+          TermColor::Blue => A::Blue,
+          TermColor::Magenta => A::Magenta,
+          TermColor::Cyan => A::Cyan,
+          TermColor::White => A::White,
+          TermColor::Ansi256(n) => return Some(anstyle::Ansi256Color(n).into()),
+          TermColor::Rgb(r, g, b) => return Some(anstyle::RgbColor(r, g, b).into()),
+          _ => return None,
+      };
+      Some(ansi.into())
+  }
+
+  /// Converts an `anstyle` color; bright ANSI colors become their 256-color index
+  pub fn from_anstyle(color: anstyle::Color) -> TermColor {
+      use anstyle::AnsiColor as A;
+      match color {
+          anstyle::Color::Ansi(ansi) => match ansi {
+              A::Black => TermColor::Black,
+              A::Red => TermColor::Red,
+              A::Green => TermColor::Green,
+              A::Yellow => TermColor::Yellow,
+              A::Blue => TermColor::Blue,
+              A::Magenta => TermColor::Magenta,
+              A::Cyan => TermColor::Cyan,
+              A::White => TermColor::White,
+              bright => TermColor::Ansi256(anstyle::Ansi256Color::from_ansi(bright).index()),
+          },
+          anstyle::Color::Ansi256(n) => TermColor::Ansi256(n.index()),
+          anstyle::Color::Rgb(rgb) => TermColor::Rgb(rgb.r(), rgb.g(), rgb.b()),
+      }
+  }
+}
+
+#[cfg(feature = "anstyle")]
+impl Style {
+  /// Converts a `ColorSpec` to an `anstyle::Style`.
+  ///
+  /// An intense basic foreground becomes the bright ANSI color.
+  ///
+  /// ```
+  /// use stderr::{Color, Style};
+  ///
+  /// let style = Style::new().fg(Color::ORANGE).bold();
+  /// let converted = Style::to_anstyle(style.spec());
+  /// assert_eq!(&Style::from_anstyle(converted), style.spec());
+  /// ```
+  pub fn to_anstyle(spec: &ColorSpec) -> anstyle::Style {
+      let mut fg = spec.fg().and_then(|&c| Color::to_anstyle(c));
+      if spec.intense() {
+          if let Some(anstyle::Color::Ansi(ansi)) = fg {
+              fg = Some(ansi.bright(true).into());
+          }
+      }
+      let mut style = anstyle::Style::new()
+          .fg_color(fg)
+          .bg_color(spec.bg().and_then(|&c| Color::to_anstyle(c)));
+      if spec.bold() { style = style.bold(); }
+      if spec.dimmed() { style = style.dimmed(); }
+      if spec.italic() { style = style.italic(); }
+      if spec.underline() { style = style.underline(); }
+      if spec.strikethrough() { style = style.strikethrough(); }
+      style
+  }
+
+  /// Converts an `anstyle::Style` (e.g. from a clap `Styles` set) to a `ColorSpec`.
+  ///
+  /// Effects without a `ColorSpec` counterpart (blink, hidden, ...) are dropped.
+  pub fn from_anstyle(style: anstyle::Style) -> ColorSpec {
+      use anstyle::Effects;
+      let effects = style.get_effects();
+      let mut spec = ColorSpec::new();
+      spec.set_fg(style.get_fg_color().map(Color::from_anstyle))
+          .set_bg(style.get_bg_color().map(Color::from_anstyle))
+          .set_bold(effects.contains(Effects::BOLD))
+          .set_dimmed(effects.contains(Effects::DIMMED))
+          .set_italic(effects.contains(Effects::ITALIC))
+          .set_underline(effects.contains(Effects::UNDERLINE))
+          .set_strikethrough(effects.contains(Effects::STRIKETHROUGH));
+      spec
+  }
+}
+
+#[cfg(feature = "owo-colors")]
+impl Style {
+  /// Converts a `ColorSpec` to an `owo_colors::Style`
+  ///
+  /// ```
+  /// use owo_colors::OwoColorize;
+  /// use stderr::{Color, Style};
+  ///
+  /// let style = Style::new().fg(Color::GREEN).bold();
+  /// println!("{}", "ok".style(Style::to_owo(style.spec())));
+  /// ```
+  pub fn to_owo(spec: &ColorSpec) -> owo_colors::Style {
+      let mut style = owo_colors::Style::new();
+      if let Some(fg) = spec.fg().and_then(|&c| owo_color(c, spec.intense())) {
+          style = style.color(fg);
+      }
+      if let Some(bg) = spec.bg().and_then(|&c| owo_color(c, false)) {
+          style = style.on_color(bg);
+      }
+      if spec.bold() { style = style.bold(); }
+      if spec.dimmed() { style = style.dimmed(); }
+      if spec.italic() { style = style.italic(); }
+      if spec.underline() { style = style.underline(); }
+      if spec.strikethrough() { style = style.strikethrough(); }
+      style
+  }
+}
+
+#[cfg(feature = "owo-colors")]
+fn owo_color(color: TermColor, intense: bool) -> Option<owo_colors::DynColors> {
+  use owo_colors::{AnsiColors as A, DynColors};
+  let (normal, bright) = match color {
+      TermColor::Black => (A::Black, A::BrightBlack),
+      TermColor::Red => (A::Red, A::BrightRed),
+      TermColor::Green => (A::Green, A::BrightGreen),
+      TermColor::Yellow => (A::Yellow, A::BrightYellow),
+      TermColor::Blue => (A::Blue, A::BrightBlue),
+      TermColor::Magenta => (A::Magenta, A::BrightMagenta),
+      TermColor::Cyan => (A::Cyan, A::BrightCyan),
+      TermColor::White => (A::White, A::BrightWhite),
+      TermColor::Ansi256(n) => return Some(DynColors::Xterm(n.into())),
+      TermColor::Rgb(r, g, b) => return Some(DynColors::Rgb(r, g, b)),
+      _ => return None,
+  };
+  Some(DynColors::Ansi(if intense { bright } else { normal }))
+}
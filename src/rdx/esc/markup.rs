@@ -0,0 +1,165 @@
+//! Inline markup: `[bold red]fatal[/] in [cyan]src/main.rs[/]`
+//!
+//! A tag is a list of words in square brackets: attributes (`bold`/`b`,
+//! `italic`/`i`, `underline`/`u`, `dim`, `strike`), a foreground color
+//! (palette name like `orange2`, or hex like `#ff8800` with the `#`) and `on <color>`
+//! for the background. `[/]` (or `[/anything]`) closes the innermost tag;
+//! tags nest, each adding to the one around it. `[[` is a literal `[`, and
+//! brackets that don't form a valid tag are kept as text.
+
+use termcolor::ColorSpec;
+
+use super::colors::Color;
+use super::style::{Style, StyledString};
+
+/// Splits markup into styled segments; unstyled text has an empty style.
+///
+/// ```
+/// use stderr::{Color, Style, parse_markup};
+///
+/// let spans = parse_markup("[bold red]fatal[/] in [cyan]main.rs[/]");
+/// assert_eq!(spans[0].text, "fatal");
+/// assert_eq!(spans[0].style, Style::new().fg(Color::RED).bold());
+/// assert_eq!(spans[1].text, " in ");
+/// assert_eq!(spans[1].style, Style::new());
+/// ```
+pub fn parse_markup(markup: &str) -> Vec<StyledString> {
+    let mut spans: Vec<StyledString> = Vec::new();
+    let mut stack: Vec<ColorSpec> = Vec::new();
+    let mut text = String::new();
+    let mut rest = markup;
+
+    while let Some(open) = rest.find('[') {
+        text.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        if let Some(tail) = after.strip_prefix('[') {
+            text.push('[');
+            rest = tail;
+            continue;
+        }
+        let tag = after.find(']').map(|close| (&after[..close], &after[close + 1..]));
+        let Some((tag, tail)) = tag else {
+            text.push('[');
+            rest = after;
+            continue;
+        };
+        // `Some(None)` closes the innermost tag, `Some(Some(spec))` opens one
+        let next = if tag.starts_with('/') {
+            (!stack.is_empty()).then_some(None)
+        } else {
+            let base = stack.last().cloned().unwrap_or_default();
+            parse_tag(tag, base).map(Some)
+        };
+        match next {
+            Some(next) => {
+                flush(&mut spans, &mut text, stack.last());
+                match next {
+                    Some(spec) => stack.push(spec),
+                    None => { stack.pop(); }
+                }
+                rest = tail;
+            }
+            // Not a tag: keep the bracket as text
+            None => {
+                text.push('[');
+                rest = after;
+            }
+        }
+    }
+    text.push_str(rest);
+    flush(&mut spans, &mut text, stack.last());
+    spans
+}
+
+/// Renders markup to a string: escape codes when `ansi` is true, else the
+/// text with its tags removed.
+///
+/// ```
+/// use stderr::render_markup;
+///
+/// assert_eq!(render_markup("[bold]done[/] in 3s", false), "done in 3s");
+/// // Hex colors need their `#`, so bracketed words stay text
+/// assert_eq!(render_markup("run [add] then [#fed]fed[/]", false), "run [add] then fed");
+/// ```
+pub fn render_markup(markup: &str, ansi: bool) -> String {
+    let spans = parse_markup(markup);
+    if ansi {
+        spans.iter().map(ToString::to_string).collect()
+    } else {
+        plain_text(&spans)
+    }
+}
+
+/// The text of `spans` without any styling
+pub(crate) fn plain_text(spans: &[StyledString]) -> String {
+    spans.iter().map(|span| span.text.as_str()).collect()
+}
+
+/// Renders spans inside a line already drawn in `base`: each styled span
+/// adds to `base` and switches back to it afterwards. RGB colors become
+/// their nearest 256-color entry unless `truecolor` is set.
+pub(crate) fn render_within(spans: &[StyledString], base: &ColorSpec, truecolor: bool) -> String {
+    let restore = Style::to_ansi(base);
+    let mut out = String::new();
+    for span in spans {
+        if span.style.spec().is_none() {
+            out.push_str(&span.text);
+            continue;
+        }
+        let mut spec = overlay(base, span.style.spec());
+        spec.set_fg(spec.fg().map(|&c| Color::downgrade(c, truecolor)));
+        spec.set_bg(spec.bg().map(|&c| Color::downgrade(c, truecolor)));
+        out.push_str(&Style::to_ansi(&spec));
+        out.push_str(&span.text);
+        out.push_str(&restore);
+    }
+    out
+}
+
+fn flush(spans: &mut Vec<StyledString>, text: &mut String, spec: Option<&ColorSpec>) {
+    if text.is_empty() { return; }
+    let style = Style::from(spec.cloned().unwrap_or_default());
+    spans.push(StyledString { style, text: std::mem::take(text) });
+}
+
+// The spec for a tag's words on top of `base`, or `None` if any word is unknown
+fn parse_tag(tag: &str, base: ColorSpec) -> Option<ColorSpec> {
+    let mut spec = base;
+    let mut words = tag.split_whitespace().peekable();
+    words.peek()?;
+    while let Some(word) = words.next() {
+        match word.to_ascii_lowercase().as_str() {
+            "bold" | "b" => { spec.set_bold(true); }
+            "italic" | "i" => { spec.set_italic(true); }
+            "underline" | "u" => { spec.set_underline(true); }
+            "dim" => { spec.set_dimmed(true); }
+            "strike" => { spec.set_strikethrough(true); }
+            "on" => { spec.set_bg(Some(tag_color(words.next()?)?)); }
+            _ => { spec.set_fg(Some(tag_color(word)?)); }
+        }
+    }
+    Some(spec)
+}
+
+// A palette name or `#`-prefixed hex; bare hex would turn words like
+// `[add]` or `[fed]` into tags
+fn tag_color(word: &str) -> Option<termcolor::Color> {
+    match word.strip_prefix('#') {
+        Some(_) => Color::from_hex(word),
+        None => Color::by_name(word),
+    }
+}
+
+// `top`'s colors and attributes on top of `base`
+fn overlay(base: &ColorSpec, top: &ColorSpec) -> ColorSpec {
+    let mut spec = base.clone();
+    if let Some(&fg) = top.fg() { spec.set_fg(Some(fg)); }
+    if let Some(&bg) = top.bg() { spec.set_bg(Some(bg)); }
+    if top.bold() { spec.set_bold(true); }
+    if top.italic() { spec.set_italic(true); }
+    if top.underline() { spec.set_underline(true); }
+    if top.dimmed() { spec.set_dimmed(true); }
+    if top.strikethrough() { spec.set_strikethrough(true); }
+    if top.intense() { spec.set_intense(true); }
+    spec
+}
@@ -1,30 +1,254 @@
 // esc/style.rs
-use termcolor::{ColorSpec};
+use std::fmt;
+use std::io;
+use termcolor::{Color as TermColor, ColorSpec};
 
-/// A namespace for custom text styles.
-pub struct Style;
+use crate::stderr::Stderr;
+
+use crate::utils::ansi::visible_width;
+
+/// A text style (colors plus attributes), built up by chaining.
+///
+/// ```
+/// use stderr::{Color, Style};
+///
+/// let alert = Style::new().fg(Color::RED2).bold().underline();
+/// println!("{}", alert.apply("disk full"));
+/// ```
+///
+/// Anything that takes `impl Into<Style>` also accepts a plain color.
+///
+/// With the `serde` feature a style is a table of `fg`/`bg` colors and the
+/// attributes that are on, e.g. `{ fg = "ORANGE", bold = true }`. Colors
+/// are palette names (upper case, as in [`Color::palette`](crate::Color::palette)),
+/// `#rrggbb`, 256-color numbers or termcolor's lower-case basic names.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "StyleRepr", try_from = "StyleRepr"))]
+pub struct Style {
+    spec: ColorSpec,
+}
 
 impl Style {
-  /// Returns a ColorSpec for bold text.
-  pub fn bold() -> ColorSpec {
-      let mut spec = ColorSpec::new();
-      spec.set_bold(true);
-      spec
+  /// Resets all colors and attributes
+  pub const ANSI_RESET: &'static str = "\x1b[0m";
+
+  /// A style with no colors or attributes
+  pub fn new() -> Self {
+      Self::default()
+  }
+
+  pub fn fg(mut self, color: TermColor) -> Self {
+      self.spec.set_fg(Some(color));
+      self
+  }
+
+  pub fn bg(mut self, color: TermColor) -> Self {
+      self.spec.set_bg(Some(color));
+      self
+  }
+
+  pub fn bold(mut self) -> Self {
+      self.spec.set_bold(true);
+      self
+  }
+
+  pub fn italic(mut self) -> Self {
+      self.spec.set_italic(true);
+      self
+  }
+
+  pub fn underline(mut self) -> Self {
+      self.spec.set_underline(true);
+      self
+  }
+
+  pub fn dimmed(mut self) -> Self {
+      self.spec.set_dimmed(true);
+      self
+  }
+
+  pub fn strike(mut self) -> Self {
+      self.spec.set_strikethrough(true);
+      self
+  }
+
+  /// Bright variant of a basic foreground color
+  pub fn intense(mut self) -> Self {
+      self.spec.set_intense(true);
+      self
+  }
+
+  /// The underlying `termcolor` spec
+  pub fn spec(&self) -> &ColorSpec {
+      &self.spec
+  }
+
+  /// This style's escape sequence (see [`to_ansi`](Self::to_ansi))
+  pub fn ansi(&self) -> String {
+      Self::to_ansi(&self.spec)
+  }
+
+  /// Pairs `text` with this style; the result prints with its escape codes
+  pub fn apply(&self, text: impl Into<String>) -> StyledString {
+      StyledString { style: self.clone(), text: text.into() }
+  }
+
+  /// Writes `text` in this style through a logger; see [`Stderr::write_styled`]
+  pub fn apply_to(&self, log: &mut Stderr, text: impl fmt::Display) -> io::Result<()> {
+      log.write_styled(self.clone(), text)
+  }
+
+  /// The escape sequence for `spec`, for embedding styled fragments in plain
+  /// strings. It starts with a reset, like the logger's own output.
+  ///
+  /// ```
+  /// use stderr::Style;
+  ///
+  /// let bold = format!("{}loud{}", Style::new().bold().ansi(), Style::ANSI_RESET);
+  /// assert_eq!(bold, "\x1b[0m\x1b[1mloud\x1b[0m");
+  /// ```
+  pub fn to_ansi(spec: &ColorSpec) -> String {
+      super::colors::spec_to_ansi(spec)
+  }
+}
+
+impl From<TermColor> for Style {
+  fn from(color: TermColor) -> Self {
+      Style::new().fg(color)
   }
-  
-  /// Returns a ColorSpec for italic text.
-  pub fn italic() -> ColorSpec {
+}
+
+impl From<ColorSpec> for Style {
+  fn from(spec: ColorSpec) -> Self {
+      Self { spec }
+  }
+}
+
+impl From<Style> for ColorSpec {
+  fn from(style: Style) -> Self {
+      style.spec
+  }
+}
+
+/// Text paired with a [`Style`], from [`Style::apply`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StyledString {
+    pub style: Style,
+    pub text: String,
+}
+
+impl StyledString {
+  /// Width on screen, without the escape codes
+  pub fn width(&self) -> usize {
+      visible_width(&self.text)
+  }
+}
+
+impl fmt::Display for StyledString {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+      if self.style.spec.is_none() {
+          return f.write_str(&self.text);
+      }
+      write!(f, "{}{}{}", self.style.ansi(), self.text, Style::ANSI_RESET)
+  }
+}
+
+// A style as it appears in config and theme files
+#[cfg(feature = "serde")]
+#[derive(Default, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+struct StyleRepr {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fg: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    bg: Option<String>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    bold: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    dimmed: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    italic: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    underline: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    strike: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    intense: bool,
+}
+
+#[cfg(feature = "serde")]
+impl From<Style> for StyleRepr {
+  fn from(style: Style) -> Self {
+      let spec = style.spec;
+      Self {
+          fg: spec.fg().map(|&c| color_to_string(c)),
+          bg: spec.bg().map(|&c| color_to_string(c)),
+          bold: spec.bold(),
+          dimmed: spec.dimmed(),
+          italic: spec.italic(),
+          underline: spec.underline(),
+          strike: spec.strikethrough(),
+          intense: spec.intense(),
+      }
+  }
+}
+
+#[cfg(feature = "serde")]
+impl TryFrom<StyleRepr> for Style {
+  type Error = String;
+
+  fn try_from(repr: StyleRepr) -> Result<Self, String> {
       let mut spec = ColorSpec::new();
-      spec.set_italic(true);
-      spec
+      spec.set_fg(repr.fg.as_deref().map(color_from_str).transpose()?)
+          .set_bg(repr.bg.as_deref().map(color_from_str).transpose()?)
+          .set_bold(repr.bold)
+          .set_dimmed(repr.dimmed)
+          .set_italic(repr.italic)
+          .set_underline(repr.underline)
+          .set_strikethrough(repr.strike)
+          .set_intense(repr.intense);
+      Ok(Self { spec })
+  }
+}
+
+// Palette names stay upper case and termcolor's basic colors lower case, so
+// `RED` (a palette entry) and `red` (the terminal's own red) both round-trip
+#[cfg(feature = "serde")]
+fn color_to_string(color: TermColor) -> String {
+  use super::colors::Color;
+
+  if let Some(name) = Color::name_of(color) { return name.to_string(); }
+  match color {
+      TermColor::Rgb(r, g, b) => format!("#{:02x}{:02x}{:02x}", r, g, b),
+      TermColor::Ansi256(n) => n.to_string(),
+      other => format!("{:?}", other).to_ascii_lowercase(),
   }
+}
+
+#[cfg(feature = "serde")]
+fn color_from_str(text: &str) -> Result<TermColor, String> {
+  use super::colors::Color;
 
-  // /// Returns a ColorSpec for inverted text (background and foreground swapped).
-  // pub fn invert() -> ColorSpec {
-  //     let mut spec = ColorSpec::new();
-  //     spec.set_invert(true);
-  //     spec
-  // }
+  let basic = text.chars().all(|c| !c.is_ascii_uppercase());
+  basic.then(|| text.parse::<TermColor>().ok()).flatten()
+      .or_else(|| Color::parse(text))
+      .or_else(|| text.parse::<TermColor>().ok())
+      .ok_or_else(|| format!("unknown color `{}`", text))
+}
+
+// A lone color field in config and theme files, spelled like a style's `fg`
+#[cfg(feature = "serde")]
+pub(crate) mod serde_color {
+  use serde::{Deserialize, Deserializer, Serializer};
+  use termcolor::Color as TermColor;
 
+  pub(crate) fn serialize<S: Serializer>(color: &TermColor, serializer: S) -> Result<S::Ok, S::Error> {
+      serializer.serialize_str(&super::color_to_string(*color))
+  }
 
+  pub(crate) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<TermColor, D::Error> {
+      let text = String::deserialize(deserializer)?;
+      super::color_from_str(&text).map_err(serde::de::Error::custom)
+  }
 }
@@ -0,0 +1,70 @@
+//! `log` crate facade: routes `log::info!` and friends from any library
+//! through the global logger, so they share its glyphs, colors, levels
+//! and sinks.
+//!
+//! ```
+//! stderr::log_bridge::init().unwrap();
+//!
+//! log::info!("shows up as a regular info line");
+//! log::warn!(target: "net", "and this one as a warning");
+//! ```
+//!
+//! Levels map as in `From<log::Level> for LogLevel`: `Error`, `Warn`,
+//! `Info`, `Debug` and `Trace` map onto the levels of the same name, and
+//! whether a record prints follows the global logger's configuration.
+
+use log::{LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use crate::stderr::{logger as LOGGER, LogLevel};
+
+/// The `log::Log` implementation behind [`init`]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StderrLog {
+    targets: bool,
+}
+
+impl StderrLog {
+    pub const fn new() -> Self {
+        Self { targets: false }
+    }
+
+    /// Prefixes each message with its `log` target (usually the module path)
+    pub const fn with_targets(mut self, targets: bool) -> Self {
+        self.targets = targets;
+        self
+    }
+
+    /// Installs this as the global `log` logger. Fails if another logger
+    /// was installed first.
+    pub fn install(self) -> Result<(), SetLoggerError> {
+        log::set_logger(Box::leak(Box::new(self)))?;
+        // Filtering happens per record against the live configuration
+        log::set_max_level(LevelFilter::Trace);
+        Ok(())
+    }
+}
+
+impl Log for StderrLog {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        LOGGER.enabled(LogLevel::from(metadata.level()))
+    }
+
+    fn log(&self, record: &Record) {
+        let level = LogLevel::from(record.level());
+        if !LOGGER.enabled(level) { return; }
+        if self.targets {
+            LOGGER.log(level, format_args!("{}: {}", record.target(), record.args()));
+        } else {
+            LOGGER.log(level, record.args());
+        }
+    }
+
+    fn flush(&self) {
+        let _ = LOGGER.raw().flush();
+    }
+}
+
+/// Installs the global logger as the `log` facade's backend
+pub fn init() -> Result<(), SetLoggerError> {
+    StderrLog::new().install()
+}
@@ -1,4 +1,9 @@
 //! Enhanced macros for stderr with hierarchical tracing support
+//!
+//! All macros log through the global `logger` and follow its locking rules
+//! (see `stderr::static_logger`): arguments are evaluated before the lock is
+//! taken, and the lock is never held while user code runs, so macros can be
+//! nested freely and used inside `Drop`/`Debug` impls.
 
 // --- Core Logging Macros (unchanged) ---
 
@@ -30,10 +35,13 @@ macro_rules! qerror {
     };
 }
 
+/// Debug message; the arguments are only formatted when debug output is enabled
 #[macro_export]
 macro_rules! qdebug {
     ($($arg:tt)*) => {
-        $crate::logger.debug(&format!($($arg)*))
+        if $crate::LogLevel::Debug.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Debug) {
+            $crate::logger.debug(&format!($($arg)*))
+        }
     };
 }
 
@@ -44,24 +52,186 @@ macro_rules! qokay {
     };
 }
 
+/// Note message
+#[macro_export]
+macro_rules! qnote {
+    ($($arg:tt)*) => {
+        $crate::logger.note(&format!($($arg)*))
+    };
+}
+
+/// Dev-only message; the arguments are only formatted when dev output is enabled
+#[macro_export]
+macro_rules! qdevlog {
+    ($($arg:tt)*) => {
+        if $crate::LogLevel::DevLog.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::DevLog) {
+            $crate::logger.devlog(&format!($($arg)*))
+        }
+    };
+}
+
+/// Magic message; the arguments are only formatted when silly output is enabled
+#[macro_export]
+macro_rules! qmagic {
+    ($($arg:tt)*) => {
+        if $crate::LogLevel::Magic.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Magic) {
+            $crate::logger.magic(&format!($($arg)*))
+        }
+    };
+}
+
+/// Silly message; the arguments are only formatted when silly output is enabled
+#[macro_export]
+macro_rules! qsilly {
+    ($($arg:tt)*) => {
+        if $crate::LogLevel::Silly.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Silly) {
+            $crate::logger.silly(&format!($($arg)*))
+        }
+    };
+}
+
+/// Logs an error and exits the process with status 1
+#[macro_export]
+macro_rules! qfatal {
+    ($($arg:tt)*) => {
+        $crate::logger.fatal(&format!($($arg)*))
+    };
+}
+
+// --- Debug Pretty-Print Macros ---
+
+/// Pretty-prints a `Debug` value at the info level
+#[macro_export]
+macro_rules! qinfo_debug {
+    ($value:expr) => {
+        {
+            let value = &$value;
+            $crate::logger.raw().info_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the warn level
+#[macro_export]
+macro_rules! qwarn_debug {
+    ($value:expr) => {
+        {
+            let value = &$value;
+            $crate::logger.raw().warn_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the error level
+#[macro_export]
+macro_rules! qerror_debug {
+    ($value:expr) => {
+        {
+            let value = &$value;
+            $crate::logger.raw().error_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the okay level
+#[macro_export]
+macro_rules! qokay_debug {
+    ($value:expr) => {
+        {
+            let value = &$value;
+            $crate::logger.raw().okay_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the note level
+#[macro_export]
+macro_rules! qnote_debug {
+    ($value:expr) => {
+        {
+            let value = &$value;
+            $crate::logger.raw().note_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the debug level (skipped entirely when disabled)
+#[macro_export]
+macro_rules! qdebug_debug {
+    ($value:expr) => {
+        if $crate::LogLevel::Debug.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Debug) {
+            let value = &$value;
+            $crate::logger.raw().debug_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the devlog level (skipped entirely when disabled)
+#[macro_export]
+macro_rules! qdevlog_debug {
+    ($value:expr) => {
+        if $crate::LogLevel::DevLog.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::DevLog) {
+            let value = &$value;
+            $crate::logger.raw().devlog_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the trace level (skipped entirely when disabled)
+#[macro_export]
+macro_rules! qtrace_debug {
+    ($value:expr) => {
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let value = &$value;
+            $crate::logger.raw().trace_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the magic level (skipped entirely when disabled)
+#[macro_export]
+macro_rules! qmagic_debug {
+    ($value:expr) => {
+        if $crate::LogLevel::Magic.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Magic) {
+            let value = &$value;
+            $crate::logger.raw().magic_debug(value)
+        }
+    };
+}
+
+/// Pretty-prints a `Debug` value at the silly level (skipped entirely when disabled)
+#[macro_export]
+macro_rules! qsilly_debug {
+    ($value:expr) => {
+        if $crate::LogLevel::Silly.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Silly) {
+            let value = &$value;
+            $crate::logger.raw().silly_debug(value)
+        }
+    };
+}
+
 #[macro_export]
 macro_rules! qpretty {
-    ($prefix:expr, $value:expr) => {
+    ($prefix:expr, $value:expr) => {{
+        let prefix = $prefix;
+        let value = &$value;
         $crate::logger.raw().print_with_prefix_debug(
             $crate::Color::MAGENTA,
-            $prefix,
-            &$value
+            prefix,
+            value
         ).ok();
-    };
+    }};
 }
 
 // --- Enhanced Trace Macros ---
 
-/// Simple trace (existing functionality)
+/// Simple trace; the arguments are only formatted when tracing is enabled
 #[macro_export]
 macro_rules! qtrace {
     ($($arg:tt)*) => {
-        $crate::logger.trace(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            $crate::logger.trace(&format!($($arg)*))
+        }
     };
 }
 
@@ -70,7 +240,11 @@ macro_rules! qtrace {
 #[macro_export]
 macro_rules! qtrace_fn {
     ($func_name:expr, $($arg:tt)*) => {
-        $crate::logger.raw().trace_fn($func_name, &format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let func_name: &str = $func_name;
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_fn(func_name, &msg)
+        }
     };
 }
 
@@ -79,7 +253,10 @@ macro_rules! qtrace_fn {
 #[macro_export]
 macro_rules! qtrace_auto {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_auto(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_auto(&msg)
+        }
     };
 }
 
@@ -88,7 +265,7 @@ macro_rules! qtrace_auto {
 #[macro_export]
 macro_rules! qtrace_auto {
     ($($arg:tt)*) => {
-        $crate::logger.trace(&format!($($arg)*))
+        $crate::qtrace!($($arg)*)
     };
 }
 
@@ -102,11 +279,14 @@ macro_rules! qtrace_auto {
 // --- Trace Scope Macros ---
 
 /// Create a trace scope for automatic entry/exit logging
+///
+/// The scope lives until the end of the enclosing block; the global lock is
+/// only taken for the entry and exit lines.
 #[cfg(feature = "trace")]
 #[macro_export]
 macro_rules! qtrace_scope {
     ($func_name:expr) => {
-        let _trace_scope = $crate::logger.raw().trace_scope($func_name);
+        let _trace_scope = $crate::logger.trace_scope($func_name);
     };
 }
 
@@ -115,7 +295,7 @@ macro_rules! qtrace_scope {
 #[macro_export]
 macro_rules! qtrace_scope_auto {
     () => {
-        let _trace_scope = $crate::logger.raw().trace_scope(function_name::function_name!());
+        let _trace_scope = $crate::logger.trace_scope(function_name::function_name!());
     };
 }
 
@@ -126,7 +306,10 @@ macro_rules! qtrace_scope_auto {
 #[macro_export]
 macro_rules! qtrace_add {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_add(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_add(&msg)
+        }
     };
 }
 
@@ -135,7 +318,10 @@ macro_rules! qtrace_add {
 #[macro_export]
 macro_rules! qtrace_sub {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_sub(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_sub(&msg)
+        }
     };
 }
 
@@ -144,7 +330,10 @@ macro_rules! qtrace_sub {
 #[macro_export]
 macro_rules! qtrace_found {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_found(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_found(&msg)
+        }
     };
 }
 
@@ -153,7 +342,10 @@ macro_rules! qtrace_found {
 #[macro_export]
 macro_rules! qtrace_done {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_done(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_done(&msg)
+        }
     };
 }
 
@@ -162,7 +354,34 @@ macro_rules! qtrace_done {
 #[macro_export]
 macro_rules! qtrace_item {
     ($($arg:tt)*) => {
-        $crate::logger.raw().trace_item(&format!($($arg)*))
+        if $crate::LogLevel::Trace.is_compiled_in() && $crate::logger.enabled($crate::LogLevel::Trace) {
+            let msg = format!($($arg)*);
+            $crate::logger.raw().trace_item(&msg)
+        }
+    };
+}
+
+// --- Build Info ---
+
+/// The calling crate's [`BuildInfo`](crate::meta::BuildInfo): name and
+/// version from Cargo, plus whatever `stderr::meta::emit_build_env()` set in
+/// its build script
+///
+/// ```
+/// let info = stderr::build_info!();
+/// assert_eq!(info.name, env!("CARGO_PKG_NAME"));
+/// ```
+#[macro_export]
+macro_rules! build_info {
+    () => {
+        $crate::meta::BuildInfo {
+            name: env!("CARGO_PKG_NAME").to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            git_commit: option_env!("STDERR_GIT_COMMIT").map(str::to_string),
+            git_dirty: option_env!("STDERR_GIT_DIRTY") == Some("true"),
+            build_date: option_env!("STDERR_BUILD_DATE").map(str::to_string),
+            rustc: option_env!("STDERR_RUSTC_VERSION").map(str::to_string),
+        }
     };
 }
 
@@ -171,25 +390,103 @@ macro_rules! qtrace_item {
 /// Set context and automatically display banner if changed
 #[macro_export]
 macro_rules! qcontext {
-    ($context:expr) => {
-        $crate::logger.raw().set_context($context)
-    };
+    ($context:expr) => {{
+        let context: &str = $context;
+        $crate::logger.raw().set_context(context)
+    }};
 }
 
 /// Execute code within a temporary context
+///
+/// The lock is released while `$code` runs, so the block may log freely.
+/// The outer context is restored when the block ends, including on early
+/// return or panic.
 #[macro_export]
 macro_rules! qwith_context {
     ($context:expr, $code:block) => {{
-        let mut logger = $crate::logger.raw();
-        let old_context = logger.current_context.clone();
-        logger.set_context($context);
-        let result = $code;
-        if let Some(ctx) = old_context {
-            logger.set_context(&ctx);
-        } else {
-            logger.clear_context();
-        }
-        result
+        let context: &str = $context;
+        let _context_scope = $crate::logger.context_scope(context);
+        $code
+    }};
+}
+
+// --- Formatting Macros ---
+
+/// Box a formatted message: `qbox!(Heavy, "deployed {}", name)` or
+/// `qbox!("plain light box")`
+#[cfg(feature = "formatting")]
+#[macro_export]
+macro_rules! qbox {
+    ($style:ident, $($arg:tt)+) => {{
+        let msg = format!($($arg)+);
+        $crate::logger.raw().boxed(&msg, $crate::BorderStyle::$style).ok();
+    }};
+    ($($arg:tt)+) => {{
+        let msg = format!($($arg)+);
+        $crate::logger.raw().boxed(&msg, $crate::BorderStyle::default()).ok();
+    }};
+}
+
+/// Banner with an optional fill character: `qbanner!('-'; "Step {}", n)` or
+/// `qbanner!("Title")` (filled with `=`)
+#[cfg(feature = "formatting")]
+#[macro_export]
+macro_rules! qbanner {
+    ($fill:expr; $($arg:tt)+) => {{
+        let fill: char = $fill;
+        let msg = format!($($arg)+);
+        $crate::logger.raw().banner(&msg, fill).ok();
+    }};
+    ($($arg:tt)+) => {{
+        let msg = format!($($arg)+);
+        $crate::logger.raw().banner(&msg, '=').ok();
+    }};
+}
+
+/// Table with headers: `qtable!(["Name", "Size"]; rows)`, where `rows` is a
+/// slice/array/Vec of `TableRow`s; or a raw `qtable!(rows)` whose first row
+/// is the header
+#[cfg(feature = "formatting")]
+#[macro_export]
+macro_rules! qtable {
+    ($headers:expr; $rows:expr) => {{
+        let headers = &$headers;
+        let rows = &$rows;
+        $crate::logger.raw().table(headers, rows).ok();
+    }};
+    ($rows:expr) => {{
+        let rows: &[&[&str]] = &$rows;
+        $crate::logger.raw().simple_table(rows).ok();
+    }};
+}
+
+// --- Interactive Macros ---
+
+/// Yes/no prompt that evaluates to a `bool`: `if qconfirm!("Delete {}?", name) { ... }`
+///
+/// Anything other than an explicit "yes" is `false`, including quitting and
+/// running without a terminal (fail closed).
+#[cfg(feature = "interactive")]
+#[macro_export]
+macro_rules! qconfirm {
+    ($($arg:tt)+) => {{
+        let prompt = format!($($arg)+);
+        let answer = $crate::logger.confirm_with_policy(&prompt, $crate::ConfirmPolicy::FailClosed);
+        matches!(answer, Ok(Some(true)))
+    }};
+}
+
+/// Numbered choice that evaluates to `Option<usize>`:
+/// `qselect!("Pick env"; ["dev", "staging", "prod"])`
+///
+/// `None` when the user quits or no terminal is available.
+#[cfg(feature = "interactive")]
+#[macro_export]
+macro_rules! qselect {
+    ($prompt:expr; $items:expr) => {{
+        let prompt: &str = &$prompt;
+        let items: &[&str] = &$items;
+        $crate::logger.select(prompt, items).ok().flatten()
     }};
 }
 
@@ -237,3 +534,21 @@ macro_rules! qtrace_done {
 macro_rules! qtrace_item {
     ($($arg:tt)*) => {};
 }
+
+#[cfg(not(feature = "formatting"))]
+#[macro_export]
+macro_rules! qbox {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "formatting"))]
+#[macro_export]
+macro_rules! qbanner {
+    ($($arg:tt)*) => {};
+}
+
+#[cfg(not(feature = "formatting"))]
+#[macro_export]
+macro_rules! qtable {
+    ($($arg:tt)*) => {};
+}
@@ -22,3 +22,112 @@ CORE CONCEPTS:
 pub fn help_string() -> String {
     format!("Version: {}\n{}", STDERR_VERSION, HELP_TEXT)
 }
+
+/// Version and build details of an application, for `--version` output.
+///
+/// Fill it with [`build_info!`](crate::build_info) in the application, after
+/// calling [`emit_build_env`] from its build script; fields the build script
+/// didn't provide stay `None`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BuildInfo {
+    pub name: String,
+    pub version: String,
+    /// Short commit hash
+    pub git_commit: Option<String>,
+    /// Whether the working tree had uncommitted changes
+    pub git_dirty: bool,
+    /// UTC build date, `YYYY-MM-DD`
+    pub build_date: Option<String>,
+    /// Output of `rustc --version`
+    pub rustc: Option<String>,
+}
+
+impl BuildInfo {
+    /// `name version (commit-dirty date)`, on one line
+    pub fn summary(&self) -> String {
+        let mut line = format!("{} {}", self.name, self.version);
+        let details: Vec<String> = [self.commit(), self.build_date.clone()].into_iter().flatten().collect();
+        if !details.is_empty() {
+            line.push_str(&format!(" ({})", details.join(" ")));
+        }
+        line
+    }
+
+    /// Commit hash with a `-dirty` suffix when the tree had changes
+    pub fn commit(&self) -> Option<String> {
+        let commit = self.git_commit.as_ref()?;
+        Some(if self.git_dirty { format!("{}-dirty", commit) } else { commit.clone() })
+    }
+}
+
+/// This crate's own build info
+pub fn build_info() -> BuildInfo {
+    BuildInfo {
+        name: env!("CARGO_PKG_NAME").to_string(),
+        version: STDERR_VERSION.to_string(),
+        ..BuildInfo::default()
+    }
+}
+
+/// For an application's `build.rs`: sets `STDERR_GIT_COMMIT`,
+/// `STDERR_GIT_DIRTY`, `STDERR_BUILD_DATE` and `STDERR_RUSTC_VERSION` for
+/// [`build_info!`](crate::build_info) to pick up. Anything that can't be
+/// determined (no git, no `rustc`) is left unset.
+///
+/// `SOURCE_DATE_EPOCH` is honored for reproducible builds.
+///
+/// ```no_run
+/// // in build.rs's `main`, with `rdx-stderr` under [build-dependencies]
+/// stderr::meta::emit_build_env();
+/// ```
+pub fn emit_build_env() {
+    use std::process::Command;
+
+    let run = |cmd: &str, args: &[&str]| -> Option<String> {
+        let out = Command::new(cmd).args(args).output().ok()?;
+        if !out.status.success() { return None; }
+        Some(String::from_utf8_lossy(&out.stdout).trim().to_string())
+    };
+
+    if let Some(commit) = run("git", &["rev-parse", "--short", "HEAD"]) {
+        println!("cargo:rustc-env=STDERR_GIT_COMMIT={}", commit);
+        let dirty = run("git", &["status", "--porcelain"]).is_some_and(|s| !s.is_empty());
+        println!("cargo:rustc-env=STDERR_GIT_DIRTY={}", dirty);
+        // Worktrees and subdirectory crates keep these outside `./.git`
+        if let Some(git_dir) = run("git", &["rev-parse", "--git-dir"]) {
+            let git_dir = std::path::Path::new(&git_dir);
+            println!("cargo:rerun-if-changed={}", git_dir.join("HEAD").display());
+            println!("cargo:rerun-if-changed={}", git_dir.join("index").display());
+        }
+    }
+
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_string());
+    if let Some(version) = run(&rustc, &["--version"]) {
+        println!("cargo:rustc-env=STDERR_RUSTC_VERSION={}", version);
+    }
+
+    let secs = std::env::var("SOURCE_DATE_EPOCH").ok()
+        .and_then(|s| s.parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0)
+        });
+    println!("cargo:rustc-env=STDERR_BUILD_DATE={}", civil_date(secs / 86_400));
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+}
+
+// Days since 1970-01-01 to `YYYY-MM-DD` (Howard Hinnant's civil_from_days)
+pub(crate) fn civil_date(days: u64) -> String {
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
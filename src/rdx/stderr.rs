@@ -1,14 +1,47 @@
 //! Stderr module - Modular logging with optional features
 
 // Main stderr implementation in the stderr/ subdirectory
+#[allow(clippy::module_inception)]
 #[path = "stderr/stderr.rs"]
 pub mod stderr;
 
+// Layout width
+#[path = "stderr/width.rs"]
+pub mod width;
+
+// Buffered output stream shared by all print paths
+#[path = "stderr/output.rs"]
+pub mod output;
+
+// CI vendor detection and log markup
+#[path = "stderr/ci.rs"]
+pub mod ci;
+
+// Sink trait and built-in sinks (syslog, journald)
+#[path = "stderr/sinks.rs"]
+pub mod sinks;
+
+#[cfg(feature = "otel")]
+#[path = "stderr/otel.rs"]
+pub mod otel;
+
+// io::Write adapter
+#[path = "stderr/adapters.rs"]
+pub mod adapters;
+
+// CLI flag mapping
+#[path = "stderr/args.rs"]
+pub mod args;
+
 // Feature-gated extension modules in the stderr/ subdirectory
 #[cfg(feature = "trace")]
 #[path = "stderr/trace.rs"]
 pub mod trace;
 
+#[cfg(feature = "tracing-layer")]
+#[path = "stderr/tracing_layer.rs"]
+pub mod tracing_layer;
+
 #[cfg(feature = "interactive")]
 #[path = "stderr/interactive.rs"]
 pub mod interactive;
@@ -17,25 +50,112 @@ pub mod interactive;
 #[path = "stderr/formatting.rs"]
 pub mod formatting;
 
+#[cfg(feature = "formatting")]
+#[path = "stderr/help.rs"]
+pub mod help;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/layout.rs"]
+pub mod layout;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/table.rs"]
+pub mod table;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/tree.rs"]
+pub mod tree;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/highlight.rs"]
+pub mod highlight;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/diff.rs"]
+pub mod diff;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/code.rs"]
+pub mod code;
+
+#[cfg(feature = "formatting")]
+#[path = "stderr/figlet.rs"]
+pub mod figlet;
+
+#[path = "stderr/json.rs"]
+mod json;
+
+// Cloneable shared handle
+#[path = "stderr/handle.rs"]
+pub mod handle;
+
+// Several progress bars drawn as one live region
+#[path = "stderr/progress.rs"]
+pub mod progress;
+
 // Static logger in the stderr/ subdirectory
 #[path = "stderr/static_logger.rs"]
 pub mod static_logger;
 
 // Re-export everything from the main stderr implementation
 pub use stderr::*;
+pub use output::FlushPolicy;
+pub use width::{SoftWrap, WidthPolicy, NARROW_WIDTH};
+pub use ci::CiVendor;
+pub use sinks::{Record, Sink};
+pub use adapters::LevelWriter;
+pub use handle::StderrHandle;
+pub use progress::{MultiProgress, ProgressBar};
+
+#[cfg(all(unix, feature = "syslog"))]
+pub use sinks::SyslogSink;
+#[cfg(all(unix, feature = "journald"))]
+pub use sinks::JournaldSink;
+#[cfg(feature = "otel")]
+pub use otel::OtelSink;
+
+pub use args::{LogFlag, LOG_FLAGS};
+#[cfg(feature = "clap")]
+pub use args::StderrArgs;
 
 // Feature-gated re-exports
 #[cfg(feature = "trace")]
 pub use trace::TraceScope;
 
+#[cfg(feature = "tracing-layer")]
+pub use tracing_layer::StderrLayer;
+
 #[cfg(feature = "interactive")]
-pub use interactive::{ConfirmBuilder, InteractiveExt};
+pub use interactive::{ConfirmBuilder, InputBuilder, InteractiveExt};
+
+#[cfg(feature = "formatting")]
+pub use formatting::{TableRow, FormattingExt, SectionScope};
+
+#[cfg(feature = "formatting")]
+pub use help::HelpBuilder;
 
 #[cfg(feature = "formatting")]
-pub use formatting::{TableRow, FormattingExt};
+pub use layout::Panel;
+
+#[cfg(feature = "formatting")]
+pub use table::{Align, Overflow, SortOrder, TableBuilder};
+
+#[cfg(feature = "formatting")]
+pub use tree::TreeNode;
+
+#[cfg(feature = "formatting")]
+pub use diff::DiffBuilder;
+
+#[cfg(feature = "formatting")]
+pub use code::CodeBuilder;
+
+#[cfg(feature = "formatting")]
+pub use figlet::BigBannerBuilder;
 
 // Static logger
-pub use static_logger::{LOGGER as logger, StaticLogger};
+pub use static_logger::{LOGGER as logger, StaticLogger, StaticContextScope, FlushGuard, LoggerGuard, init};
+#[cfg(feature = "trace")]
+pub use static_logger::StaticTraceScope;
 
 // Type aliases
 pub type Logger = Stderr;
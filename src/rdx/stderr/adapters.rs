@@ -0,0 +1,125 @@
+//! `io::Write` / `fmt::Write` adapter that turns a stream of text into log lines
+
+use std::fmt;
+use std::io::{self, Write};
+
+use super::handle::StderrHandle;
+use super::stderr::{LogLevel, Stderr};
+use super::static_logger::LOGGER;
+
+/// Line-buffering writer that logs each complete line at a fixed level,
+/// created by `Stderr::writer_for`, `StderrHandle::writer_for` or
+/// `logger.writer_for`.
+///
+/// Anything left without a trailing newline is logged on `flush()` or when
+/// the writer is dropped.
+///
+/// It implements `fmt::Write` too, so `write!`/`writeln!` go straight to
+/// the logger:
+///
+/// ```
+/// use std::fmt::Write;
+/// use stderr::{LogLevel, Stderr};
+///
+/// let mut log = Stderr::new();
+/// let mut w = log.writer_for(LogLevel::Info);
+/// for (name, size) in [("a.txt", 12), ("b.txt", 340)] {
+///     writeln!(w, "{:<8}{:>6} bytes", name, size).unwrap();
+/// }
+/// ```
+///
+/// Piping a child process:
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use std::process::{Command, Stdio};
+/// use stderr::{LogLevel, Stderr};
+///
+/// let mut log = Stderr::new().with_label("make");
+/// let mut child = Command::new("make").stderr(Stdio::piped()).spawn()?;
+///
+/// // every line the child prints shows up as `[make][△] ...`
+/// std::io::copy(&mut child.stderr.take().unwrap(), &mut log.writer_for(LogLevel::Warn))?;
+/// child.wait()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct LevelWriter<'a> {
+    target: Target<'a>,
+    level: LogLevel,
+    pending: Vec<u8>,
+}
+
+enum Target<'a> {
+    Local(&'a mut Stderr),
+    // A shared or the global logger, locked once per line
+    Shared(StderrHandle),
+    Global,
+}
+
+impl<'a> LevelWriter<'a> {
+    pub(crate) fn local(log: &'a mut Stderr, level: LogLevel) -> Self {
+        Self { target: Target::Local(log), level, pending: Vec::new() }
+    }
+
+    /// The level every line is logged at
+    pub fn level(&self) -> LogLevel {
+        self.level
+    }
+
+    fn push(&mut self, bytes: &[u8]) -> io::Result<()> {
+        self.pending.extend_from_slice(bytes);
+        while let Some(end) = self.pending.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = self.pending.drain(..=end).collect();
+            self.emit(&line[..end])?;
+        }
+        Ok(())
+    }
+
+    fn emit(&mut self, line: &[u8]) -> io::Result<()> {
+        let line = String::from_utf8_lossy(line);
+        let line = line.strip_suffix('\r').unwrap_or(&line);
+        match &mut self.target {
+            Target::Local(log) => log.try_log(self.level, line),
+            Target::Shared(handle) => handle.try_log(self.level, line),
+            Target::Global => LOGGER.try_log(self.level, line),
+        }
+    }
+}
+
+impl LevelWriter<'static> {
+    pub(crate) fn global(level: LogLevel) -> Self {
+        Self { target: Target::Global, level, pending: Vec::new() }
+    }
+
+    pub(crate) fn shared(handle: StderrHandle, level: LogLevel) -> Self {
+        Self { target: Target::Shared(handle), level, pending: Vec::new() }
+    }
+}
+
+impl Write for LevelWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.push(buf)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if !self.pending.is_empty() {
+            let line = std::mem::take(&mut self.pending);
+            self.emit(&line)?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Write for LevelWriter<'_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.push(s.as_bytes()).map_err(|_| fmt::Error)
+    }
+}
+
+impl Drop for LevelWriter<'_> {
+    fn drop(&mut self) {
+        let _ = self.flush();
+    }
+}
@@ -0,0 +1,200 @@
+//! Mapping common CLI verbosity flags onto `StderrConfig`
+
+use super::stderr::{ColorMode, LevelMask, LogLevel, OptionFlag, StderrConfig};
+
+/// A flag understood by [`StderrConfig::from_args`], for generated help
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LogFlag {
+    /// Spellings, e.g. `"-q, --quiet"`
+    pub names: &'static str,
+    pub help: &'static str,
+    /// Environment variable with the same effect, if any
+    pub env: Option<&'static str>,
+    /// Levels the flag switches on, for the level switches
+    pub enables: &'static [LogLevel],
+}
+
+/// The flags `from_args` recognizes; keep in step with its `match`. The
+/// level switches (`--debug`, `--trace`) take their levels from here, for
+/// both `from_args` and `StderrArgs`.
+pub const LOG_FLAGS: &[LogFlag] = &[
+    LogFlag { names: "-q, --quiet", help: "Suppress all output except errors", env: Some("QUIET_MODE"), enables: &[] },
+    LogFlag { names: "--silent", help: "Suppress all output, errors included", env: Some("SILENT_MODE"), enables: &[] },
+    LogFlag { names: "-v, --verbose", help: "More output: -v debug, -vv trace, -vvv silly", env: None, enables: &[] },
+    LogFlag { names: "--debug", help: "Show debug and dev messages", env: None, enables: &[LogLevel::Debug, LogLevel::DevLog] },
+    LogFlag { names: "--trace", help: "Show trace messages", env: Some("TRACE_MODE"), enables: &[LogLevel::Trace] },
+    LogFlag { names: "--log-level LEVEL", help: "Show levels up to LEVEL (error, warn, info, debug, trace, ...)", env: Some("LOG_LEVEL"), enables: &[] },
+    LogFlag { names: "--no-color", help: "Disable colors", env: Some("NO_COLOR"), enables: &[] },
+];
+
+// The levels a switch such as `--debug` turns on, per `LOG_FLAGS`
+fn enables(name: &str) -> &'static [LogLevel] {
+    LOG_FLAGS.iter()
+        .find(|flag| flag.names.split(", ").any(|spelling| spelling == name))
+        .map_or(&[], |flag| flag.enables)
+}
+
+impl StderrConfig {
+    // -v debug, -vv trace, -vvv silly; each step keeps the ones below it
+    fn apply_verbosity(&mut self, verbose: usize) {
+        if verbose >= 1 { self.enable(LogLevel::Debug); }
+        if verbose >= 2 { self.enable(LogLevel::Trace); }
+        if verbose >= 3 { self.set_flag(OptionFlag::Silly, true); }
+    }
+
+    /// Pulls the standard logging flags out of an argument list, for apps
+    /// that don't use clap.
+    ///
+    /// Recognizes `-q/--quiet`, `--silent`, `-v`/`-vv`/`-vvv` (or repeated `--verbose`),
+    /// `--debug`, `--trace`, `--log-level LEVEL` (or `--log-level=LEVEL`) and
+    /// `--no-color`, on top of the env-var defaults. Verbosity flags add to
+    /// the `--log-level` threshold wherever they appear.
+    /// Everything else, including the program name, is returned in order;
+    /// nothing after a `--` separator is interpreted.
+    ///
+    /// ```
+    /// use stderr::{LogLevel, StderrConfig};
+    ///
+    /// let (config, _) = StderrConfig::from_args(["app", "--debug"]);
+    /// assert!(config.enabled.contains(LogLevel::DevLog));
+    ///
+    /// let (config, rest) = StderrConfig::from_args(["app", "-vv", "build", "--release"]);
+    /// assert!(config.enabled.contains(LogLevel::Debug));
+    /// assert!(config.enabled.contains(LogLevel::Trace));
+    /// assert_eq!(rest, ["app", "build", "--release"]);
+    ///
+    /// let (config, _) = StderrConfig::from_args(["app", "--trace", "--log-level", "warn"]);
+    /// assert_eq!(config.level(), Some(LogLevel::Trace));
+    /// assert!(!config.enabled.contains(LogLevel::Info));
+    /// ```
+    pub fn from_args<I, S>(args: I) -> (Self, Vec<String>)
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let mut config = Self::from_env();
+        let mut verbose = 0;
+        let mut level = None;
+        let mut extra = LevelMask::NONE;
+        let mut rest = Vec::new();
+        let mut args = args.into_iter().map(Into::into);
+
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--" => {
+                    rest.push(arg);
+                    rest.extend(args.by_ref());
+                }
+                "-q" | "--quiet" => config.quiet = true,
+                "--silent" => config.silent = true,
+                "--verbose" => verbose += 1,
+                "--debug" | "--trace" => {
+                    for &level in enables(&arg) { extra.insert(level); }
+                }
+                "--no-color" => config.color = ColorMode::Never,
+                "--log-level" => match args.next() {
+                    Some(value) => match value.parse::<LogLevel>() {
+                        Ok(parsed) => level = Some(parsed),
+                        Err(_) => rest.extend([arg, value]),
+                    },
+                    None => rest.push(arg),
+                },
+                flag if flag.starts_with("--log-level=") => {
+                    match flag.trim_start_matches("--log-level=").parse::<LogLevel>() {
+                        Ok(parsed) => level = Some(parsed),
+                        Err(_) => rest.push(arg),
+                    }
+                }
+                flag if flag.len() > 1 && flag.strip_prefix('-').is_some_and(|vs| vs.bytes().all(|b| b == b'v')) => {
+                    verbose += flag.len() - 1;
+                }
+                _ => rest.push(arg),
+            }
+        }
+
+        if let Some(level) = level {
+            config.set_level(level);
+        }
+        config.enabled = config.enabled.union(extra);
+        config.apply_verbosity(verbose);
+        (config, rest)
+    }
+}
+
+/// Standard logging flags for clap-based CLIs, meant to be `#[command(flatten)]`ed
+/// into the app's own argument struct.
+///
+/// | flag            | effect                                   |
+/// |-----------------|------------------------------------------|
+/// | `-q, --quiet`   | suppress all output except errors        |
+/// | `--silent`      | suppress all output, errors included     |
+/// | `-v`            | debug messages                           |
+/// | `-vv`           | ... plus trace messages                  |
+/// | `-vvv`          | ... plus silly messages                  |
+/// | `--debug`       | debug and dev messages                   |
+/// | `--log-level L` | every level up to `L` (`LOG_LEVEL`)      |
+/// | `--color WHEN`  | `auto`, `always` or `never`              |
+///
+/// ```
+/// use clap::Parser;
+/// use stderr::{Stderr, StderrArgs, StderrConfig};
+///
+/// #[derive(Parser)]
+/// struct Cli {
+///     #[command(flatten)]
+///     log: StderrArgs,
+/// }
+///
+/// let cli = Cli::parse_from(["app", "-vv", "--color", "never"]);
+/// let mut log = Stderr::with_config(StderrConfig::from(&cli.log));
+/// log.trace("visible with -vv");
+/// ```
+#[cfg(feature = "clap")]
+#[derive(clap::Args, Debug, Clone, Default)]
+pub struct StderrArgs {
+    /// Suppress all log output except errors
+    #[arg(short, long, global = true, conflicts_with = "verbose")]
+    pub quiet: bool,
+
+    /// Suppress all log output, errors included
+    #[arg(long, global = true, conflicts_with = "verbose")]
+    pub silent: bool,
+
+    /// More output: -v debug, -vv trace, -vvv silly
+    #[arg(short, long, global = true, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+
+    /// Enable debug and dev output
+    #[arg(long, global = true)]
+    pub debug: bool,
+
+    /// Show levels up to LEVEL (error, warn, info, debug, trace, ...)
+    #[arg(long, global = true, value_name = "LEVEL")]
+    pub log_level: Option<LogLevel>,
+
+    /// When to use colors
+    #[arg(long, global = true, value_enum, value_name = "WHEN", default_value_t = ColorMode::Auto)]
+    pub color: ColorMode,
+}
+
+/// Flags only ever switch things on, so env-var settings (`DEBUG_MODE`, ...)
+/// still apply when a flag is absent.
+#[cfg(feature = "clap")]
+impl From<&StderrArgs> for StderrConfig {
+    fn from(args: &StderrArgs) -> Self {
+        let mut config = StderrConfig::from_env();
+        config.quiet |= args.quiet;
+        config.silent |= args.silent;
+        if let Some(level) = args.log_level {
+            config.set_level(level);
+        }
+        if args.debug {
+            for &level in enables("--debug") { config.enable(level); }
+        }
+        config.apply_verbosity(args.verbose as usize);
+        if args.color != ColorMode::Auto {
+            config.color = args.color;
+        }
+        config
+    }
+}
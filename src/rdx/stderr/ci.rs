@@ -0,0 +1,173 @@
+//! CI vendor detection and log markup (collapsible sections, service messages)
+
+use std::panic::Location;
+
+use crate::utils::helpers::env;
+use super::stderr::LogLevel;
+
+#[cfg(feature = "formatting")]
+pub(crate) use sections::*;
+
+/// CI systems whose job logs understand extra markup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CiVendor {
+    /// Collapsible `section_start` / `section_end` blocks
+    GitLab,
+    /// `##teamcity[...]` service messages and blocks
+    TeamCity,
+    /// `##vso[...]` logging commands and `##[group]` blocks
+    AzureDevOps,
+    /// `::group::` blocks, plus opt-in `::error::` / `::warning::` annotations
+    GitHubActions,
+}
+
+impl CiVendor {
+    /// Detects the CI system from its well-known environment variables
+    pub fn detect() -> Option<Self> {
+        if env("GITLAB_CI").is_ok() {
+            Some(CiVendor::GitLab)
+        } else if env("TEAMCITY_VERSION").is_ok() {
+            Some(CiVendor::TeamCity)
+        } else if env("TF_BUILD").is_ok() {
+            Some(CiVendor::AzureDevOps)
+        } else if env("GITHUB_ACTIONS").is_ok_and(|v| v == "true") {
+            Some(CiVendor::GitHubActions)
+        } else {
+            None
+        }
+    }
+}
+
+/// Whether we're running under some CI system, known adapter or not
+pub fn is_ci() -> bool {
+    ["CI", "GITHUB_ACTIONS", "JENKINS_URL", "BUILDKITE", "CIRCLECI", "TRAVIS", "BUILD_NUMBER"]
+        .iter()
+        .any(|var| env(var).is_ok())
+        || CiVendor::detect().is_some()
+}
+
+/// `HH:MM:SS` (UTC) for plain-CI timestamps
+pub(crate) fn clock() -> String {
+    let secs = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    format!("{:02}:{:02}:{:02}", secs / 3600 % 24, secs / 60 % 60, secs % 60)
+}
+
+/// Service-message form of an error or warning, for vendors that surface
+/// those in the build summary. `None` means print the normal line.
+pub(crate) fn issue_message(vendor: CiVendor, level: LogLevel, msg: &str) -> Option<String> {
+    let (teamcity, azure) = match level {
+        LogLevel::Error => ("ERROR", "error"),
+        LogLevel::Warn => ("WARNING", "warning"),
+        _ => return None,
+    };
+    match vendor {
+        CiVendor::GitLab => None,
+        CiVendor::TeamCity => Some(format!("##teamcity[message text='{}' status='{}']", teamcity_escape(msg), teamcity)),
+        CiVendor::AzureDevOps => Some(format!("##vso[task.logissue type={}]{}", azure, azure_escape(msg))),
+        // Annotations are opt-in and printed after the line (see `annotation`)
+        CiVendor::GitHubActions => None,
+    }
+}
+
+/// GitHub Actions annotation for an error or warning, pointing at the call
+/// site when there is one. `None` for other levels.
+pub(crate) fn annotation(level: LogLevel, msg: &str, caller: Option<&Location>) -> Option<String> {
+    let kind = match level {
+        LogLevel::Error => "error",
+        LogLevel::Warn => "warning",
+        _ => return None,
+    };
+    let message = github_escape(msg);
+    Some(match caller {
+        Some(at) => format!("::{} file={},line={}::{}", kind, github_property_escape(at.file()), at.line(), message),
+        None => format!("::{}::{}", kind, message),
+    })
+}
+
+/// Service-message form of a progress update. `None` means print the normal line.
+pub(crate) fn progress_message(vendor: CiVendor, msg: &str, percent: Option<u8>) -> Option<String> {
+    match (vendor, percent) {
+        (CiVendor::TeamCity, _) => Some(format!("##teamcity[progressMessage '{}']", teamcity_escape(msg))),
+        (CiVendor::AzureDevOps, Some(pct)) => Some(format!("##vso[task.setprogress value={};]{}", pct.min(100), azure_escape(msg))),
+        _ => None,
+    }
+}
+
+fn teamcity_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '|' => out.push_str("||"),
+            '\'' => out.push_str("|'"),
+            '\n' => out.push_str("|n"),
+            '\r' => out.push_str("|r"),
+            '[' => out.push_str("|["),
+            ']' => out.push_str("|]"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn azure_escape(text: &str) -> String {
+    text.replace('%', "%AZP25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn github_escape(text: &str) -> String {
+    text.replace('%', "%25").replace('\r', "%0D").replace('\n', "%0A")
+}
+
+fn github_property_escape(text: &str) -> String {
+    github_escape(text).replace(':', "%3A").replace(',', "%2C")
+}
+
+// Collapsible sections, drawn by `Stderr::section`
+#[cfg(feature = "formatting")]
+mod sections {
+    use std::io::{self, Write};
+    use std::time::{SystemTime, UNIX_EPOCH};
+    use termcolor::{ColorSpec, WriteColor};
+    use crate::esc::colors::Color as ESC;
+    use super::{teamcity_escape, CiVendor};
+
+    /// Section ids may only contain `[a-z0-9_.-]`
+    fn section_id(title: &str) -> String {
+        let id: String = title.trim().chars()
+            .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c.to_ascii_lowercase() } else { '_' })
+            .collect();
+        if id.is_empty() { "section".to_string() } else { id }
+    }
+
+    fn timestamp() -> u64 {
+        SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+    }
+
+    pub(crate) fn write_section_start<W: WriteColor>(w: &mut W, vendor: CiVendor, title: &str) -> io::Result<()> {
+        match vendor {
+            CiVendor::TeamCity => writeln!(w, "##teamcity[blockOpened name='{}']", teamcity_escape(title)),
+            CiVendor::AzureDevOps => writeln!(w, "##[group]{}", title),
+            CiVendor::GitHubActions => writeln!(w, "::group::{}", title.replace(['\r', '\n'], " ")),
+            CiVendor::GitLab => {
+                write!(w, "\x1b[0Ksection_start:{}:{}\r\x1b[0K", timestamp(), section_id(title))?;
+                let mut spec = ColorSpec::new();
+                spec.set_fg(Some(ESC::BLUE)).set_bold(true);
+                w.set_color(&spec)?;
+                write!(w, "{}", title)?;
+                w.reset()?;
+                writeln!(w)
+            }
+        }
+    }
+
+    pub(crate) fn write_section_end<W: Write>(w: &mut W, vendor: CiVendor, title: &str) -> io::Result<()> {
+        match vendor {
+            CiVendor::TeamCity => writeln!(w, "##teamcity[blockClosed name='{}']", teamcity_escape(title)),
+            CiVendor::AzureDevOps => writeln!(w, "##[endgroup]"),
+            CiVendor::GitHubActions => writeln!(w, "::endgroup::"),
+            CiVendor::GitLab => writeln!(w, "\x1b[0Ksection_end:{}:{}\r\x1b[0K", timestamp(), section_id(title)),
+        }
+    }
+}
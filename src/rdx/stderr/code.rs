@@ -0,0 +1,148 @@
+//! Source excerpts in a light box with a line-number gutter, for "error
+//! occurred here" output
+
+use std::io;
+use termcolor::WriteColor;
+
+#[cfg(feature = "syntax")]
+use super::highlight::highlight_lines;
+use super::layout::fit;
+use super::stderr::{Stderr, OptionFlag};
+use super::width::NARROW_WIDTH;
+use crate::esc::boxes::{BorderStyle, BoxChars};
+use crate::esc::colors::Color as ESC;
+use crate::esc::style::Style;
+use crate::utils::ansi::{truncate_ansi, visible_width};
+
+/// Builder from [`Stderr::code_builder`]
+///
+/// ```
+/// use stderr::Stderr;
+///
+/// let src = "[server]\nport = 80\nhost = \"0.0.0.0\"\n";
+/// let mut log = Stderr::new();
+/// log.code_builder(src).lang("toml").start_line(41).highlight(42).print().unwrap();
+/// // ┌──────┬─ toml ───────────┐
+/// // │   41 │ [server]         │
+/// // │ ▶ 42 │ port = 80        │
+/// // │   43 │ host = "0.0.0.0" │
+/// // └──────┴──────────────────┘
+/// ```
+pub struct CodeBuilder<'a> {
+    stderr: &'a mut Stderr,
+    src: &'a str,
+    lang: String,
+    start_line: usize,
+    highlight: Option<usize>,
+    marker: Option<&'static str>,
+}
+
+impl<'a> CodeBuilder<'a> {
+    /// Names the language in the top edge; with the `syntax` feature,
+    /// `rust`, `json`, `toml` and `yaml` are also colored
+    pub fn lang(mut self, lang: impl Into<String>) -> Self {
+        self.lang = lang.into();
+        self
+    }
+
+    /// Number of the first line, when `src` is an excerpt (default 1)
+    pub fn start_line(mut self, line: usize) -> Self {
+        self.start_line = line;
+        self
+    }
+
+    /// Marks a line (by its number, as shown in the gutter)
+    pub fn highlight(mut self, line: usize) -> Self {
+        self.highlight = Some(line);
+        self
+    }
+
+    /// The glyph beside the highlighted line (default `▶`, `>` without Unicode)
+    pub fn marker(mut self, glyph: &'static str) -> Self {
+        self.marker = Some(glyph);
+        self
+    }
+
+    pub fn print(self) -> io::Result<()> {
+        if self.stderr.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let ansi = self.stderr.writer.supports_color() && !self.stderr.is_plain();
+        let lines = self.lines(ansi);
+        self.stderr.write_lines("code", &lines)
+    }
+
+    /// Renders the excerpt to a `String` instead of the stream
+    pub fn render(self, ansi: bool) -> String {
+        let ansi = ansi && !self.stderr.is_plain();
+        self.lines(ansi).iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn lines(&self, ansi: bool) -> Vec<String> {
+        let log = &*self.stderr;
+        let src = self.src.replace('\t', "    ");
+        let plain = || -> Vec<String> { src.lines().map(str::to_string).collect() };
+        #[cfg(feature = "syntax")]
+        let code = highlight_lines(&src, &self.lang, ansi).unwrap_or_else(plain);
+        #[cfg(not(feature = "syntax"))]
+        let code = plain();
+        if code.is_empty() { return Vec::new(); }
+
+        let paint = |style: &Style, piece: &str| {
+            if ansi && !piece.is_empty() {
+                format!("{}{}{}", Style::to_ansi(style.spec()), piece, Style::ANSI_RESET)
+            } else {
+                piece.to_string()
+            }
+        };
+        let marker = self.marker.unwrap_or(if log.ascii_only() { ">" } else { "\u{25B6}" });
+        let number_width = (self.start_line + code.len() - 1).to_string().len();
+        let gutter_width = 1 + visible_width(marker) + 1 + number_width + 1;
+        let gutter = |n: usize| {
+            if self.highlight == Some(n) {
+                format!(" {}", paint(&Style::new().fg(ESC::RED).bold(), &format!("{} {:>w$} ", marker, n, w = number_width)))
+            } else {
+                let pad = " ".repeat(1 + visible_width(marker) + 1);
+                format!("{}{}", pad, paint(&Style::new().fg(ESC::GREY), &format!("{:>w$} ", n, w = number_width)))
+            }
+        };
+        let numbered = (self.start_line..).zip(&code);
+
+        let width = log.width.boxes();
+        if width < NARROW_WIDTH {
+            let room = width.saturating_sub(gutter_width).max(1);
+            return numbered.map(|(n, line)| format!("{}{}", gutter(n), truncate_ansi(line, room))).collect();
+        }
+
+        let chars = BoxChars::from_style(&log.border(BorderStyle::Light));
+        let title = (!self.lang.is_empty()).then(|| format!(" {} ", self.lang));
+        let title_width = title.as_deref().map_or(0, visible_width);
+        let room = width.saturating_sub(gutter_width + 5);
+        let code_width = code.iter().map(|line| visible_width(line)).max().unwrap_or(0).max(title_width).min(room);
+
+        let boxes = &log.theme.boxes;
+        let span = code_width + 2;
+        let top = match title.as_deref().map(|title| truncate_ansi(title, span.saturating_sub(1))) {
+            Some(title) => format!("{}{}{}", chars.horizontal, title, chars.horizontal.repeat(span - 1 - visible_width(&title))),
+            None => chars.horizontal.repeat(span),
+        };
+        let mut lines = vec![paint(boxes, &format!("{}{}{}{}{}", chars.top_left, chars.horizontal.repeat(gutter_width), chars.top_t, top, chars.top_right))];
+        let side = paint(boxes, chars.vertical);
+        for (n, line) in numbered {
+            lines.push(format!("{}{}{} {} {}", side, gutter(n), side, fit(line, code_width), side));
+        }
+        lines.push(paint(boxes, &format!("{}{}{}{}{}", chars.bottom_left, chars.horizontal.repeat(gutter_width), chars.bottom_t, chars.horizontal.repeat(span), chars.bottom_right)));
+        lines
+    }
+}
+
+impl Stderr {
+    /// Prints source in a light box with line numbers; `lang_hint` (e.g.
+    /// `"toml"`, or `""` for none) labels the box
+    pub fn code(&mut self, src: &str, lang_hint: &str) -> io::Result<()> {
+        self.code_builder(src).lang(lang_hint).print()
+    }
+
+    /// A code block with a starting line number and a highlighted line
+    pub fn code_builder<'a>(&'a mut self, src: &'a str) -> CodeBuilder<'a> {
+        CodeBuilder { stderr: self, src, lang: String::new(), start_line: 1, highlight: None, marker: None }
+    }
+}
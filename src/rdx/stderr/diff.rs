@@ -0,0 +1,296 @@
+//! Line diffs for "config drift" and "about to overwrite" output: removed
+//! lines in red, added lines in green and context in grey, grouped into
+//! unified-diff hunks
+
+use std::io;
+use termcolor::{Color, ColorSpec, WriteColor};
+
+use super::stderr::{Stderr, OptionFlag};
+use crate::esc::colors::Color as ESC;
+use crate::utils::helpers::render_to_string;
+
+/// Builder from [`Stderr::diff_builder`]
+///
+/// ```
+/// use stderr::Stderr;
+///
+/// let old = "host = \"a\"\nport = 80\n";
+/// let new = "host = \"a\"\nport = 8080\n";
+///
+/// let mut log = Stderr::new();
+/// let text = log.diff_builder(old, new).inline(true).render(false);
+/// assert_eq!(text, "@@ -1,2 +1,2 @@\n host = \"a\"\n-port = 80\n+port = 8080\n");
+/// ```
+pub struct DiffBuilder<'a> {
+    stderr: &'a mut Stderr,
+    old: &'a str,
+    new: &'a str,
+    context: usize,
+    inline: bool,
+}
+
+impl<'a> DiffBuilder<'a> {
+    /// Unchanged lines shown around each change (default 3)
+    pub fn context(mut self, lines: usize) -> Self {
+        self.context = lines;
+        self
+    }
+
+    /// Highlights the changed words when a removed line is directly
+    /// replaced by an added one
+    pub fn inline(mut self, on: bool) -> Self {
+        self.inline = on;
+        self
+    }
+
+    /// Prints the diff; identical texts print nothing
+    pub fn print(self) -> io::Result<()> {
+        if self.stderr.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let DiffBuilder { stderr, old, new, context, inline } = self;
+        stderr.write_block("diff", |log| write_diff(&mut log.writer, old, new, context, inline))
+    }
+
+    /// Renders the diff to a `String` instead of the stream
+    pub fn render(self, ansi: bool) -> String {
+        let ansi = ansi && !self.stderr.is_plain();
+        render_to_string(ansi, |buf| write_diff(buf, self.old, self.new, self.context, self.inline))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    // Indices into the old and new sequences
+    Equal(usize, usize),
+    Delete(usize),
+    Insert(usize),
+}
+
+// Edits beyond this many give up on a minimal script: the differing middle
+// is shown as all removed, then all added
+const MAX_EDITS: usize = 1000;
+
+// Shortest edit script (Myers' O(ND) algorithm); the common prefix and
+// suffix are matched up front so typical small edits stay cheap. Within a
+// run of changes, deletions come before insertions.
+fn diff_ops<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Op> {
+    let prefix = a.iter().zip(b).take_while(|(x, y)| x == y).count();
+    let suffix = a[prefix..].iter().rev().zip(b[prefix..].iter().rev()).take_while(|(x, y)| x == y).count();
+    let (mid_a, mid_b) = (&a[prefix..a.len() - suffix], &b[prefix..b.len() - suffix]);
+
+    let mut ops: Vec<Op> = (0..prefix).map(|i| Op::Equal(i, i)).collect();
+    let mut middle = match shortest_edit(mid_a, mid_b) {
+        Some(middle) => middle,
+        None => (0..mid_a.len()).map(Op::Delete).chain((0..mid_b.len()).map(Op::Insert)).collect(),
+    };
+    for run in middle.split_mut(|op| matches!(op, Op::Equal(..))) {
+        run.sort_by_key(|op| matches!(op, Op::Insert(_)));
+    }
+    ops.extend(middle.into_iter().map(|op| match op {
+        Op::Equal(i, j) => Op::Equal(prefix + i, prefix + j),
+        Op::Delete(i) => Op::Delete(prefix + i),
+        Op::Insert(j) => Op::Insert(prefix + j),
+    }));
+    ops.extend((0..suffix).map(|k| Op::Equal(a.len() - suffix + k, b.len() - suffix + k)));
+    ops
+}
+
+// Myers' greedy forward search, then a walk back through the saved
+// frontiers; `None` past `MAX_EDITS`
+fn shortest_edit<T: PartialEq>(a: &[T], b: &[T]) -> Option<Vec<Op>> {
+    let (n, m) = (a.len() as isize, b.len() as isize);
+    let max = (a.len() + b.len()).min(MAX_EDITS) as isize;
+    // v[k + offset]: furthest x reached on diagonal k = x - y
+    let offset = max + 1;
+    let mut v = vec![0isize; 2 * offset as usize + 1];
+    // trace[d]: diagonals -d..=d of `v` before step d
+    let mut trace: Vec<Vec<isize>> = Vec::new();
+    let at = |k: isize| (k + offset) as usize;
+    let goes_down = |v: &[isize], k: isize, d: isize, base: isize| k == -d || (k != d && v[(k - 1 - base) as usize] < v[(k + 1 - base) as usize]);
+
+    let mut end = None;
+    'search: for d in 0..=max {
+        trace.push(v[at(-d)..=at(d)].to_vec());
+        for k in (-d..=d).step_by(2) {
+            let mut x = if goes_down(&v, k, d, -offset) { v[at(k + 1)] } else { v[at(k - 1)] + 1 };
+            let mut y = x - k;
+            while x < n && y < m && a[x as usize] == b[y as usize] {
+                x += 1;
+                y += 1;
+            }
+            v[at(k)] = x;
+            if x >= n && y >= m {
+                end = Some(d);
+                break 'search;
+            }
+        }
+    }
+    end?;
+
+    let mut ops = Vec::new();
+    let (mut x, mut y) = (n, m);
+    for (d, frontier) in trace.iter().enumerate().rev() {
+        let d = d as isize;
+        if d == 0 {
+            // Only the matching run from the start is left
+            while x > 0 {
+                x -= 1;
+                y -= 1;
+                ops.push(Op::Equal(x as usize, y as usize));
+            }
+            break;
+        }
+        let k = x - y;
+        let prev_k = if goes_down(frontier, k, d, -d) { k + 1 } else { k - 1 };
+        let prev_x = frontier[(prev_k + d) as usize];
+        let prev_y = prev_x - prev_k;
+        while x > prev_x && y > prev_y {
+            x -= 1;
+            y -= 1;
+            ops.push(Op::Equal(x as usize, y as usize));
+        }
+        ops.push(if x == prev_x { Op::Insert(prev_y as usize) } else { Op::Delete(prev_x as usize) });
+        (x, y) = (prev_x, prev_y);
+    }
+    ops.reverse();
+    Some(ops)
+}
+
+// Runs of word characters, runs of whitespace, and single punctuation marks
+fn words(line: &str) -> Vec<&str> {
+    let class = |c: char| if c.is_alphanumeric() || c == '_' { 0 } else if c.is_whitespace() { 1 } else { 2 };
+    let mut out = Vec::new();
+    let mut start = 0;
+    let mut chars = line.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        let joins = chars.peek().is_some_and(|&(_, next)| class(c) != 2 && class(next) == class(c));
+        if !joins {
+            out.push(&line[start..i + c.len_utf8()]);
+            start = i + c.len_utf8();
+        }
+    }
+    out
+}
+
+fn write_diff<W: WriteColor>(w: &mut W, old: &str, new: &str, context: usize, inline: bool) -> io::Result<()> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+    let ops = diff_ops(&a, &b);
+
+    // Hunks: changes closer than two contexts apart share one
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| !matches!(ops[k], Op::Equal(..))).collect();
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for &k in &changes {
+        let start = k.saturating_sub(context);
+        let end = (k + context + 1).min(ops.len());
+        match hunks.last_mut() {
+            Some(last) if start <= last.1 => last.1 = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    for (start, end) in hunks {
+        let hunk = &ops[start..end];
+        // Line numbers (1-based) where the hunk starts in each text; an
+        // empty side names the line before it, as in `diff -u`
+        let old_len = hunk.iter().filter(|op| !matches!(op, Op::Insert(_))).count();
+        let new_len = hunk.iter().filter(|op| !matches!(op, Op::Delete(_))).count();
+        let old_start = ops[..start].iter().filter(|op| !matches!(op, Op::Insert(_))).count() + usize::from(old_len > 0);
+        let new_start = ops[..start].iter().filter(|op| !matches!(op, Op::Delete(_))).count() + usize::from(new_len > 0);
+        paint(w, ESC::CYAN, &format!("@@ -{},{} +{},{} @@", old_start, old_len, new_start, new_len))?;
+        writeln!(w)?;
+
+        let mut k = 0;
+        while k < hunk.len() {
+            match hunk[k] {
+                Op::Equal(i, _) => {
+                    paint(w, ESC::GREY, &format!(" {}", a[i]))?;
+                    writeln!(w)?;
+                    k += 1;
+                }
+                _ => {
+                    let deletes: Vec<usize> = hunk[k..].iter().map_while(|op| match op { Op::Delete(i) => Some(*i), _ => None }).collect();
+                    let inserts: Vec<usize> = hunk[k + deletes.len()..].iter().map_while(|op| match op { Op::Insert(j) => Some(*j), _ => None }).collect();
+                    k += deletes.len() + inserts.len();
+                    if inline && deletes.len() == inserts.len() {
+                        let pairs: Vec<_> = deletes.iter().zip(&inserts).map(|(&i, &j)| inline_pair(a[i], b[j])).collect();
+                        for (removed, _) in &pairs {
+                            write_changed(w, '-', ESC::RED, removed)?;
+                        }
+                        for (_, added) in &pairs {
+                            write_changed(w, '+', ESC::GREEN, added)?;
+                        }
+                    } else {
+                        for i in deletes {
+                            write_changed(w, '-', ESC::RED, &[(false, a[i])])?;
+                        }
+                        for j in inserts {
+                            write_changed(w, '+', ESC::GREEN, &[(false, b[j])])?;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+// The old and new line as segments, `true` for the words that changed
+type Segments<'a> = Vec<(bool, &'a str)>;
+
+fn inline_pair<'a>(old: &'a str, new: &'a str) -> (Segments<'a>, Segments<'a>) {
+    let (old_words, new_words) = (words(old), words(new));
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for op in diff_ops(&old_words, &new_words) {
+        match op {
+            Op::Equal(x, y) => {
+                removed.push((false, old_words[x]));
+                added.push((false, new_words[y]));
+            }
+            Op::Delete(x) => removed.push((true, old_words[x])),
+            Op::Insert(y) => added.push((true, new_words[y])),
+        }
+    }
+    (removed, added)
+}
+
+fn paint<W: WriteColor>(w: &mut W, color: Color, text: &str) -> io::Result<()> {
+    w.set_color(ColorSpec::new().set_fg(Some(color)))?;
+    write!(w, "{}", text)?;
+    w.reset()
+}
+
+// A changed line; `true` segments are the words that changed
+fn write_changed<W: WriteColor>(w: &mut W, sign: char, color: Color, segments: &[(bool, &str)]) -> io::Result<()> {
+    let mut base = ColorSpec::new();
+    base.set_fg(Some(color));
+    let mut changed = base.clone();
+    changed.set_bold(true).set_underline(true);
+
+    w.set_color(&base)?;
+    write!(w, "{}", sign)?;
+    for &(is_changed, text) in segments {
+        if is_changed {
+            w.set_color(&changed)?;
+            write!(w, "{}", text)?;
+            w.set_color(&base)?;
+        } else {
+            write!(w, "{}", text)?;
+        }
+    }
+    w.reset()?;
+    writeln!(w)
+}
+
+impl Stderr {
+    /// Prints a colored line diff of `old` against `new`: `-` lines in red,
+    /// `+` lines in green, three lines of grey context around each change
+    pub fn diff(&mut self, old: &str, new: &str) -> io::Result<()> {
+        self.diff_builder(old, new).print()
+    }
+
+    /// A diff with its context size and word-level highlighting configurable
+    pub fn diff_builder<'a>(&'a mut self, old: &'a str, new: &'a str) -> DiffBuilder<'a> {
+        DiffBuilder { stderr: self, old, new, context: 3, inline: false }
+    }
+}
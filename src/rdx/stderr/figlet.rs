@@ -0,0 +1,210 @@
+//! Block-letter banners for splash headers: text drawn five rows tall in a
+//! built-in font, in one color or a left-to-right gradient
+
+use std::io;
+use termcolor::{Color, WriteColor};
+
+use super::formatting::write_banner;
+use super::stderr::{Stderr, OptionFlag};
+use crate::esc::colors::Color as ESC;
+use crate::esc::style::Style;
+use crate::utils::helpers::{render_to_string, runs};
+
+// Rows per letter
+const HEIGHT: usize = 5;
+
+// `#` marks a filled cell; letters are drawn in upper case
+const FONT: &[(char, [&str; HEIGHT])] = &[
+    ('A', [" ### ", "#   #", "#####", "#   #", "#   #"]),
+    ('B', ["#### ", "#   #", "#### ", "#   #", "#### "]),
+    ('C', [" ####", "#    ", "#    ", "#    ", " ####"]),
+    ('D', ["#### ", "#   #", "#   #", "#   #", "#### "]),
+    ('E', ["#####", "#    ", "#### ", "#    ", "#####"]),
+    ('F', ["#####", "#    ", "#### ", "#    ", "#    "]),
+    ('G', [" ####", "#    ", "#  ##", "#   #", " ####"]),
+    ('H', ["#   #", "#   #", "#####", "#   #", "#   #"]),
+    ('I', ["###", " # ", " # ", " # ", "###"]),
+    ('J', ["  ###", "   # ", "   # ", "#  # ", " ##  "]),
+    ('K', ["#   #", "#  # ", "###  ", "#  # ", "#   #"]),
+    ('L', ["#    ", "#    ", "#    ", "#    ", "#####"]),
+    ('M', ["#   #", "## ##", "# # #", "#   #", "#   #"]),
+    ('N', ["#   #", "##  #", "# # #", "#  ##", "#   #"]),
+    ('O', [" ### ", "#   #", "#   #", "#   #", " ### "]),
+    ('P', ["#### ", "#   #", "#### ", "#    ", "#    "]),
+    ('Q', [" ### ", "#   #", "# # #", "#  # ", " ## #"]),
+    ('R', ["#### ", "#   #", "#### ", "#  # ", "#   #"]),
+    ('S', [" ####", "#    ", " ### ", "    #", "#### "]),
+    ('T', ["#####", "  #  ", "  #  ", "  #  ", "  #  "]),
+    ('U', ["#   #", "#   #", "#   #", "#   #", " ### "]),
+    ('V', ["#   #", "#   #", "#   #", " # # ", "  #  "]),
+    ('W', ["#   #", "#   #", "# # #", "## ##", "#   #"]),
+    ('X', ["#   #", " # # ", "  #  ", " # # ", "#   #"]),
+    ('Y', ["#   #", " # # ", "  #  ", "  #  ", "  #  "]),
+    ('Z', ["#####", "   # ", "  #  ", " #   ", "#####"]),
+    ('0', [" ### ", "#  ##", "# # #", "##  #", " ### "]),
+    ('1', [" # ", "## ", " # ", " # ", "###"]),
+    ('2', [" ### ", "#   #", "  ## ", " #   ", "#####"]),
+    ('3', ["#### ", "    #", " ### ", "    #", "#### "]),
+    ('4', ["#  # ", "#  # ", "#####", "   # ", "   # "]),
+    ('5', ["#####", "#    ", "#### ", "    #", "#### "]),
+    ('6', [" ### ", "#    ", "#### ", "#   #", " ### "]),
+    ('7', ["#####", "    #", "   # ", "  #  ", "  #  "]),
+    ('8', [" ### ", "#   #", " ### ", "#   #", " ### "]),
+    ('9', [" ### ", "#   #", " ####", "    #", " ### "]),
+    (' ', ["   ", "   ", "   ", "   ", "   "]),
+    ('-', ["    ", "    ", "####", "    ", "    "]),
+    ('_', ["    ", "    ", "    ", "    ", "####"]),
+    ('.', [" ", " ", " ", " ", "#"]),
+    (':', [" ", "#", " ", "#", " "]),
+    ('!', ["#", "#", "#", " ", "#"]),
+    ('?', [" ### ", "#   #", "  ## ", "     ", "  #  "]),
+    ('/', ["    #", "   # ", "  #  ", " #   ", "#    "]),
+];
+
+// Characters missing from the font are drawn as `?`
+fn glyph(c: char) -> &'static [&'static str; HEIGHT] {
+    let c = c.to_ascii_uppercase();
+    FONT.iter().find(|(k, _)| *k == c).or_else(|| FONT.iter().find(|(k, _)| *k == '?')).map(|(_, rows)| rows).unwrap_or(&FONT[0].1)
+}
+
+// The rows of `text` in the font as `#`/space grids, one column between letters
+fn grid(text: &str) -> [String; HEIGHT] {
+    let mut rows: [String; HEIGHT] = Default::default();
+    for (i, c) in text.chars().enumerate() {
+        for (row, piece) in rows.iter_mut().zip(glyph(c)) {
+            if i > 0 { row.push(' '); }
+            row.push_str(piece);
+        }
+    }
+    rows
+}
+
+// Width of `text` drawn in the font
+fn grid_width(text: &str) -> usize {
+    grid(text)[0].chars().count()
+}
+
+/// Builder from [`Stderr::big_banner_builder`]
+///
+/// ```
+/// use stderr::{Color, Stderr};
+///
+/// let mut log = Stderr::new();
+/// let text = log.big_banner_builder("Hi").render(false);
+/// assert_eq!(text.lines().next(), Some("█   █ ███"));
+/// assert_eq!(text.lines().count(), 5);
+///
+/// log.big_banner_builder("BookDB").gradient(Color::rgb(0, 200, 255), Color::rgb(200, 0, 255)).print().unwrap();
+/// ```
+pub struct BigBannerBuilder<'a> {
+    stderr: &'a mut Stderr,
+    text: &'a str,
+    gradient: Option<(Color, Color)>,
+}
+
+impl<'a> BigBannerBuilder<'a> {
+    /// Shades the letters from `from` on the left to `to` on the right,
+    /// instead of the theme's banner color
+    pub fn gradient(mut self, from: Color, to: Color) -> Self {
+        self.gradient = Some((from, to));
+        self
+    }
+
+    pub fn print(self) -> io::Result<()> {
+        if self.stderr.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        // Block letters mean nothing to a JSON reader; record the words
+        if self.stderr.is_json() { return self.stderr.write_json_block("banner", self.text); }
+        let ansi = self.stderr.writer.supports_color() && !self.stderr.is_plain();
+        let lines = self.lines(ansi);
+        self.stderr.write_lines("banner", &lines)
+    }
+
+    /// Renders the banner to a `String` instead of the stream
+    pub fn render(self, ansi: bool) -> String {
+        let ansi = ansi && !self.stderr.is_plain();
+        self.lines(ansi).iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn lines(&self, ansi: bool) -> Vec<String> {
+        let log = &*self.stderr;
+        let width = log.width.layout();
+
+        // Words that don't fit on one line go onto the next block; a single
+        // word wider than the terminal falls back to a plain banner
+        let mut blocks: Vec<String> = Vec::new();
+        for word in self.text.split_whitespace() {
+            match blocks.last_mut() {
+                Some(block) if grid_width(&format!("{} {}", block, word)) <= width => {
+                    block.push(' ');
+                    block.push_str(word);
+                }
+                _ => blocks.push(word.to_string()),
+            }
+        }
+        if blocks.iter().any(|block| grid_width(block) > width) {
+            let fill = log.fill('=');
+            return render_to_string(ansi, |buf| write_banner(buf, width, self.text, fill, log.theme.banner.spec()))
+                .lines()
+                .map(str::to_string)
+                .collect();
+        }
+
+        let cell = if log.ascii_only() { "#" } else { "\u{2588}" };
+        let span = blocks.iter().map(|block| grid_width(block)).max().unwrap_or(0);
+        let truecolor = log.writer.truecolor();
+        // The style of each column: the banner style, or a step of the gradient
+        let style_at = |x: usize| match self.gradient {
+            Some((from, to)) => Style::new().fg(ESC::downgrade(blend(from, to, x, span), truecolor)),
+            None => log.theme.banner.clone(),
+        };
+
+        let mut lines = Vec::new();
+        for (i, block) in blocks.iter().enumerate() {
+            if i > 0 { lines.push(String::new()); }
+            for row in grid(block) {
+                // Runs of filled cells sharing a style, painted together
+                let cells: Vec<(usize, char)> = row.trim_end().chars().enumerate().collect();
+                let mut line = String::new();
+                for run in runs(&cells, |a, b| a.1 == b.1 && (a.1 != '#' || !ansi || style_at(a.0) == style_at(b.0))) {
+                    let (x, c) = run[0];
+                    let piece = if c == '#' { cell.repeat(run.len()) } else { " ".repeat(run.len()) };
+                    if c == '#' && ansi {
+                        line.push_str(&format!("{}{}{}", Style::to_ansi(style_at(x).spec()), piece, Style::ANSI_RESET));
+                    } else {
+                        line.push_str(&piece);
+                    }
+                }
+                lines.push(line);
+            }
+        }
+        lines
+    }
+}
+
+// The color `x` columns into a gradient `span` columns wide; colors without
+// an RGB value don't blend and switch over halfway
+fn blend(from: Color, to: Color, x: usize, span: usize) -> Color {
+    let t = if span > 1 { x as f32 / (span - 1) as f32 } else { 0.0 };
+    match (ESC::to_rgb(from), ESC::to_rgb(to)) {
+        (Some(a), Some(b)) => {
+            let mix = |a: u8, b: u8| (a as f32 + (b as f32 - a as f32) * t).round() as u8;
+            Color::Rgb(mix(a.0, b.0), mix(a.1, b.1), mix(a.2, b.2))
+        }
+        _ if t < 0.5 => from,
+        _ => to,
+    }
+}
+
+impl Stderr {
+    /// Prints `text` in block letters five rows tall, in the theme's banner
+    /// color, for splash headers; see [`big_banner_builder`](Self::big_banner_builder)
+    /// for a color gradient
+    pub fn big_banner(&mut self, text: &str) -> io::Result<()> {
+        self.big_banner_builder(text).print()
+    }
+
+    /// A block-letter banner with an optional color gradient
+    pub fn big_banner_builder<'a>(&'a mut self, text: &'a str) -> BigBannerBuilder<'a> {
+        BigBannerBuilder { stderr: self, text, gradient: None }
+    }
+}
@@ -1,16 +1,61 @@
 //! Formatting features for stderr - tables, boxes, banners, advanced layouts
 
 use std::io::{self, Write};
+use std::ops::{Deref, DerefMut};
 use termcolor::{ColorSpec, WriteColor};
 use super::stderr::{Stderr, OptionFlag};
+use super::ci;
 use crate::esc::boxes::{BorderStyle, BoxChars};
 use crate::esc::colors::Color as ESC;
-use crate::utils::helpers::{repeat_char, term_width};
+use crate::esc::style::Style;
+use crate::utils::helpers::{render_to_string, repeat_char};
+use crate::utils::text::{strip_ansi, truncate, Ellipsis};
 use crate::utils::flag::flag_table;
+use crate::meta::BuildInfo;
+use crate::utils::ansi::{truncate_ansi, visible_width, wrap_ansi};
+use super::width::NARROW_WIDTH;
+use super::table::{ColumnFormat, Overflow};
+
+/// RAII guard from [`Stderr::section_scope`]; derefs to the logger and
+/// closes the section when dropped
+#[must_use = "the section is closed as soon as the guard is dropped"]
+pub struct SectionScope<'a> {
+    stderr: &'a mut Stderr,
+    title: String,
+}
+
+impl Deref for SectionScope<'_> {
+    type Target = Stderr;
+
+    fn deref(&self) -> &Stderr {
+        self.stderr
+    }
+}
+
+impl DerefMut for SectionScope<'_> {
+    fn deref_mut(&mut self) -> &mut Stderr {
+        self.stderr
+    }
+}
+
+impl Drop for SectionScope<'_> {
+    fn drop(&mut self) {
+        let _ = self.stderr.close_section_scope(&self.title);
+    }
+}
 
 /// Trait for types that can be displayed as table rows
+///
+/// With the `derive` feature, `#[derive(TableRow)]` implements it for
+/// structs, taking headers from the field names.
 pub trait TableRow {
     fn columns(&self) -> Vec<String>;
+
+    /// Column headers for [`Stderr::table_of`]; empty unless the type
+    /// defines them
+    fn headers() -> Vec<String> where Self: Sized {
+        Vec::new()
+    }
 }
 
 impl TableRow for Vec<String> {
@@ -25,55 +70,216 @@ impl TableRow for &[&str] {
     }
 }
 
+impl TableRow for Vec<&str> {
+    fn columns(&self) -> Vec<String> {
+        self.iter().map(|s| s.to_string()).collect()
+    }
+}
+
+impl<const N: usize> TableRow for [&str; N] {
+    fn columns(&self) -> Vec<String> {
+        self.iter().map(|s| s.to_string()).collect()
+    }
+}
+
 #[cfg(feature = "formatting")]
 impl Stderr {
-    /// Creates a banner with the specified fill character
+    // Plain mode and non-Unicode terminals swap box borders for ASCII ones
+    // (custom sets that are already ASCII are kept)
+    pub(crate) fn border(&self, style: BorderStyle) -> BorderStyle {
+        match style {
+            BorderStyle::Custom(chars) if chars.is_ascii() => style,
+            _ if self.ascii_only() => BorderStyle::Ascii,
+            _ => style,
+        }
+    }
+
+    /// Creates a banner with the specified fill character.
+    /// Under CI this starts a collapsible section instead (see [`section`](Self::section)).
     pub fn banner(&mut self, msg: &str, fill_char: char) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        // JSON readers get the words, not the fill
+        if self.is_json() { return self.write_json_block("banner", msg); }
+        if self.ci.is_some() { return self.section(msg); }
+        let fill_char = self.fill(fill_char);
+        let msg = self.banner_title(msg);
+        let (width, spec) = (self.width.layout(), self.theme.banner.spec().clone());
+        self.write_block("banner", |log| write_banner(&mut log.writer, width, &msg, fill_char, &spec))
+    }
+
+    // The title with the theme's banner glyph in front, if it has one
+    fn banner_title(&self, msg: &str) -> String {
+        match self.theme.glyphs.banner {
+            "" => msg.to_string(),
+            glyph => format!("{} {}", glyph, msg),
+        }
+    }
+
+    /// Starts a named phase of output, closing the previous one.
+    ///
+    /// In CI logs the phase becomes a collapsible section (GitHub Actions and
+    /// Azure DevOps groups, GitLab sections, TeamCity blocks); elsewhere it
+    /// prints a `-` banner.
+    ///
+    /// Phases are flat and follow one another, which is what CI groups
+    /// support (GitHub Actions can't nest them), so `section` keeps this
+    /// shape; nested, indented output is [`section_scope`](Self::section_scope).
+    pub fn section(&mut self, title: &str) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.end_section()?;
+        if self.is_json() { return self.write_json_block("section", title); }
+        match self.ci {
+            Some(vendor) => {
+                self.write_block("section", |log| ci::write_section_start(&mut log.writer, vendor, title))?;
+                self.open_section = Some(title.to_string());
+                Ok(())
+            }
+            None => {
+                let (width, spec) = (self.width.layout(), self.theme.banner.spec().clone());
+                self.write_block("section", |log| write_banner(&mut log.writer, width, title, '-', &spec))
+            }
+        }
+    }
 
-        let msg_len = msg.chars().count() + 2; // account for one space on each side
-        if msg_len >= self.width {
-            writeln!(&mut self.writer, " {} ", msg)?;
-            return Ok(());
+    /// Closes the section opened by [`section`](Self::section), if any
+    pub fn end_section(&mut self) -> io::Result<()> {
+        match (self.ci, self.open_section.take()) {
+            (Some(vendor), Some(title)) => self.write_block("section", |log| ci::write_section_end(&mut log.writer, vendor, &title)),
+            _ => Ok(()),
         }
-        let total_fill = self.width - msg_len;
-        let left_fill = total_fill / 2;
-        let right_fill = total_fill - left_fill;
-        let left_bar = repeat_char(fill_char, left_fill);
-        let right_bar = repeat_char(fill_char, right_fill);
+    }
+
+    /// Same as [`section`](Self::section), under the name GitHub Actions uses
+    pub fn group(&mut self, title: &str) -> io::Result<()> {
+        self.section(title)
+    }
 
-        let mut spec = ColorSpec::new();
-        spec.set_fg(Some(ESC::BLUE)).set_bold(true);
+    /// Same as [`end_section`](Self::end_section)
+    pub fn end_group(&mut self) -> io::Result<()> {
+        self.end_section()
+    }
+
+    /// Opens a nested section: prints a `-` banner (a collapsible section in
+    /// CI logs), indents everything printed (log lines, tables, boxes,
+    /// trees, raw prints) one level until the guard drops, then prints a
+    /// closing rule. Unlike [`section`](Self::section) it doesn't close an
+    /// open phase, so scopes can nest inside one.
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// {
+    ///     let mut db = log.section_scope("Database setup");
+    ///     db.info("creating schema");      //   [i] creating schema
+    ///     db.okay("migrations applied");   //   [✔] migrations applied
+    /// }
+    /// log.info("done");                    // [i] done
+    /// ```
+    pub fn section_scope(&mut self, title: &str) -> SectionScope<'_> {
+        let _ = self.write_scope_open(title);
+        self.set_indent(self.indent + 1);
+        SectionScope { stderr: self, title: title.to_string() }
+    }
+
+    fn write_scope_open(&mut self, title: &str) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        if self.is_json() { return self.write_json_block("section", title); }
+        if let Some(vendor) = self.ci {
+            return self.write_block("section", |log| ci::write_section_start(&mut log.writer, vendor, title));
+        }
+        let (width, spec) = (self.width.layout(), self.theme.banner.spec().clone());
+        self.write_block("section", |log| write_banner(&mut log.writer, width, title, '-', &spec))
+    }
 
-        self.writer.reset()?;
-        write!(&mut self.writer, "{} ", left_bar)?;
-        self.set_color(&spec)?;
-        write!(&mut self.writer, "{}", msg)?;
-        self.writer.reset()?;
-        writeln!(&mut self.writer, " {}", right_bar)?;
+    pub(crate) fn close_section_scope(&mut self, title: &str) -> io::Result<()> {
+        self.set_indent(self.indent.saturating_sub(1));
+        if self.check_flag(OptionFlag::Quiet) || self.is_json() { return Ok(()); }
+        if let Some(vendor) = self.ci {
+            return self.write_block("section", |log| ci::write_section_end(&mut log.writer, vendor, title));
+        }
+        let width = self.width.layout();
+        self.write_block("section", |log| writeln!(&mut log.writer, "{}", repeat_char('-', width)))
+    }
 
-        Ok(())
+    /// Renders a banner to a `String` instead of the stream.
+    /// Pass `ansi = false` for plain text suitable for files or further composition.
+    pub fn render_banner(&self, msg: &str, fill_char: char, ansi: bool) -> String {
+        let fill_char = self.fill(fill_char);
+        let msg = self.banner_title(msg);
+        render_to_string(ansi && !self.is_plain(), |buf| write_banner(buf, self.width.layout(), &msg, fill_char, self.theme.banner.spec()))
     }
 
     /// Renders a message in a box with the specified border style
     pub fn boxed(&mut self, msg: &str, style: BorderStyle) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        if self.is_json() { return self.write_json_block("box", msg); }
+        let style = self.border(style);
+        let (spec, width) = (self.theme.boxes.spec().clone(), self.width.boxes());
+        self.write_block("box", |log| write_boxed(&mut log.writer, msg, &style, &spec, width))
+    }
+
+    /// Caps how wide boxes get (`None` for the layout width); longer lines
+    /// are word-wrapped inside the borders
+    ///
+    /// ```
+    /// use stderr::{BorderStyle, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_box_max_width(Some(24));
+    /// let text = log.render_boxed("a fairly long sentence that will not fit on one line", BorderStyle::Ascii, false);
+    /// assert!(text.lines().all(|line| line.chars().count() <= 24));
+    /// ```
+    pub fn set_box_max_width(&mut self, columns: Option<usize>) {
+        self.width.box_max = columns;
+    }
 
-        let chars = BoxChars::from_style(&style);
-        let lines: Vec<&str> = msg.lines().collect();
-        let content_width = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
-        let box_width = content_width + 2;
+    /// Like [`boxed`](Self::boxed), with the border and text drawn in `style`
+    ///
+    /// ```
+    /// use stderr::{BorderStyle, Color, Stderr, Style};
+    ///
+    /// let alert = Style::new().fg(Color::RED).bold();
+    /// Stderr::new().boxed_styled("Deploy blocked", BorderStyle::Heavy, alert).unwrap();
+    /// ```
+    pub fn boxed_styled(&mut self, msg: &str, border: BorderStyle, style: impl Into<Style>) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        if self.is_json() { return self.write_json_block("box", msg); }
+        let border = self.border(border);
+        let (style, width) = (style.into(), self.width.boxes());
+        self.write_block("box", |log| write_boxed(&mut log.writer, msg, &border, style.spec(), width))
+    }
 
-        let top_border = std::iter::repeat(chars.horizontal).take(box_width).collect::<String>();
-        let bottom_border = &top_border; // It's the same
+    /// Renders a boxed message to a `String` instead of the stream.
+    /// The plain (`ansi = false`) output can be embedded inside other boxes.
+    pub fn render_boxed(&self, msg: &str, style: BorderStyle, ansi: bool) -> String {
+        let style = self.border(style);
+        render_to_string(ansi && !self.is_plain(), |buf| write_boxed(buf, msg, &style, self.theme.boxes.spec(), self.width.boxes()))
+    }
 
-        self.set_fg(ESC::WHITE)?;
-        writeln!(&mut self.writer, "{}{}{}", chars.top_left, top_border, chars.top_right)?;
-        for line in &lines {
-            writeln!(&mut self.writer, "{} {:<width$} {}", chars.vertical, line, chars.vertical, width = content_width)?;
-        }
-        writeln!(&mut self.writer, "{}{}{}", chars.bottom_left, bottom_border, chars.bottom_right)?;
-        self.writer.reset()
+    /// Prints build details in a titled box, the usual `--version -v` display
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// Stderr::new().version_banner(&stderr::build_info!()).unwrap();
+    /// ```
+    pub fn version_banner(&mut self, info: &BuildInfo) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let title = format!("{} {}", info.name, info.version);
+        let rows = [
+            ("commit", info.commit()),
+            ("built", info.build_date.clone()),
+            ("rustc", info.rustc.clone()),
+        ];
+        let text = rows.into_iter()
+            .filter_map(|(key, value)| Some(format!("{:<7} {}", format!("{}:", key), value?)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        if self.is_json() { return self.write_json_block("box", format!("{}\n{}", title, text).trim_end()); }
+        let border = self.border(BorderStyle::Light);
+        let (spec, width) = (self.theme.boxes.spec().clone(), self.width.boxes());
+        self.write_block("box", |log| write_titled_boxed(&mut log.writer, Some(&title), &text, &border, &spec, width))
     }
 
     /// Renders a message in a box with light, single-line borders.
@@ -91,56 +297,28 @@ impl Stderr {
         self.boxed(msg, BorderStyle::Double)
     }
 
-    /// Simple table formatter for basic data display
-    /// Useful for BookDB's ls commands
+    /// Renders a message in a box with light borders and rounded corners.
+    pub fn box_rounded(&mut self, msg: &str) -> io::Result<()> {
+        self.boxed(msg, BorderStyle::Rounded)
+    }
+
+    /// Simple table formatter for basic data display; the first row is the
+    /// header. Useful for BookDB's ls commands. See
+    /// [`table_builder`](Self::table_builder) for alignment and ordering.
     pub fn simple_table(&mut self, rows: &[&[&str]]) -> io::Result<()> {
-        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        if rows.is_empty() { return Ok(()); }
+        let Some((header, body)) = rows.split_first() else { return Ok(()) };
+        self.table_builder().columns(header.iter().copied()).rows(body.iter().copied()).print()
+    }
 
-        // Calculate column widths
-        let num_cols = rows[0].len();
-        let mut col_widths = vec![0; num_cols];
-        
-        for row in rows {
-            for (i, cell) in row.iter().enumerate() {
-                if i < col_widths.len() {
-                    col_widths[i] = col_widths[i].max(cell.chars().count());
-                }
-            }
-        }
+    /// Like [`simple_table`](Self::simple_table), with the header row in `header`
+    pub fn table_styled(&mut self, rows: &[&[&str]], header: impl Into<Style>) -> io::Result<()> {
+        let Some((names, body)) = rows.split_first() else { return Ok(()) };
+        self.table_builder().columns(names.iter().copied()).header_style(header).rows(body.iter().copied()).print()
+    }
 
-        // Print rows
-        for (row_idx, row) in rows.iter().enumerate() {
-            let mut line = String::new();
-            for (col_idx, cell) in row.iter().enumerate() {
-                if col_idx < col_widths.len() {
-                    line.push_str(&format!("{:<width$}", cell, width = col_widths[col_idx]));
-                    if col_idx < row.len() - 1 {
-                        line.push_str("  "); // Column separator
-                    }
-                }
-            }
-            
-            // Highlight header row
-            if row_idx == 0 {
-                self.set_bold_fg(ESC::BLUE)?;
-                writeln!(&mut self.writer, "{}", line)?;
-                self.reset()?;
-                
-                // Add separator line under header
-                let separator: String = col_widths.iter()
-                    .map(|&w| "-".repeat(w))
-                    .collect::<Vec<_>>()
-                    .join("  ");
-                self.set_fg(ESC::GREY)?;
-                writeln!(&mut self.writer, "{}", separator)?;
-                self.reset()?;
-            } else {
-                writeln!(&mut self.writer, "{}", line)?;
-            }
-        }
-        
-        Ok(())
+    /// Renders a simple table to a `String` instead of the stream.
+    pub fn render_table(&self, rows: &[&[&str]], ansi: bool) -> String {
+        render_to_string(ansi && !self.is_plain(), |buf| write_table(buf, rows, self.theme.headers.spec(), &[], &[], self.width.layout()))
     }
 
     /// Advanced table formatter with custom row types
@@ -163,36 +341,112 @@ impl Stderr {
         self.simple_table(&table_data)
     }
 
+    /// Like [`table`](Self::table), with the headers from [`TableRow::headers`]
+    /// (or the first row, for types without headers)
+    pub fn table_of<T: TableRow>(&mut self, rows: &[T]) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let mut rows: Vec<Vec<String>> = rows.iter().map(TableRow::columns).collect();
+        let mut headers = T::headers();
+        if headers.is_empty() && !rows.is_empty() {
+            headers = rows.remove(0);
+        }
+        self.table_builder().columns(headers).rows(rows).print()
+    }
+
+    /// Prints a GitHub-flavored Markdown pipe table, without colors, for
+    /// output meant to be pasted into docs
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.table_markdown(&["flag", "meaning"], &[["-q", "quiet"], ["-v", "verbose"]]).unwrap();
+    /// // | flag | meaning |
+    /// // | ---- | ------- |
+    /// // | -q   | quiet   |
+    /// // | -v   | verbose |
+    /// ```
+    pub fn table_markdown<T: TableRow>(&mut self, headers: &[&str], rows: &[T]) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let body: Vec<Vec<String>> = rows.iter().map(TableRow::columns).collect();
+        let mut all_rows: Vec<Vec<&str>> = vec![headers.to_vec()];
+        all_rows.extend(body.iter().map(|row| row.iter().map(String::as_str).collect()));
+        let all_rows: Vec<&[&str]> = all_rows.iter().map(Vec::as_slice).collect();
+        self.write_block("table", |log| write_markdown(&mut log.writer, &all_rows))
+    }
+
+    /// Writes `simple_table` data (header row first) as CSV, for
+    /// machine-readable output
+    pub fn table_csv(&mut self, rows: &[&[&str]]) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.write_block("table", |log| write_delimited(&mut log.writer, rows, ','))
+    }
+
+    /// Like [`table_csv`](Self::table_csv), separated by tabs
+    pub fn table_tsv(&mut self, rows: &[&[&str]]) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.write_block("table", |log| write_delimited(&mut log.writer, rows, '\t'))
+    }
+
+    /// Prints bytes in the classic `hexdump -C` layout (offset, hex, ASCII),
+    /// with non-printable bytes in grey; 8 bytes per row on narrow terminals
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.hexdump(b"GET / HTTP/1.1\r\n").unwrap();
+    /// assert_eq!(
+    ///     log.render_hexdump(b"hi\0", false),
+    ///     "00000000  68 69 00                                          |hi.|\n00000003\n",
+    /// );
+    /// ```
+    pub fn hexdump(&mut self, bytes: &[u8]) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let width = self.width.layout();
+        self.write_block("hexdump", |log| write_hexdump(&mut log.writer, bytes, width))
+    }
+
+    /// Renders a hexdump to a `String` instead of the stream
+    pub fn render_hexdump(&self, bytes: &[u8], ansi: bool) -> String {
+        render_to_string(ansi && !self.is_plain(), |buf| write_hexdump(buf, bytes, self.width.layout()))
+    }
+
     /// This is a convenience wrapper around the `util::flag_table` function.
     pub fn print_flag_table<T>(&mut self, bitmask: T, labels: &[&str], style: BorderStyle) -> io::Result<()>
     where
         T: std::ops::Shr<usize, Output = T> + std::ops::BitAnd<T, Output = T> + From<u8> + Copy + PartialEq,
     {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        let current_term_width = term_width();
-        let table_string = flag_table(bitmask, labels, style, current_term_width);
-        write!(&mut self.writer, "{}", table_string)?;
-        self.writer.flush()
+        let table_string = flag_table(bitmask, labels, self.border(style), self.width.layout());
+        self.write_block("flags", |log| {
+            write!(&mut log.writer, "{}", table_string)?;
+            log.writer.flush()
+        })
     }
 
     /// Print a list with bullet points (useful for BookDB lists)
     pub fn list(&mut self, items: &[&str], bullet: &str) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
         
-        for item in items {
-            writeln!(&mut self.writer, "{} {}", bullet, item)?;
-        }
-        Ok(())
+        self.write_block("list", |log| {
+            for item in items {
+                writeln!(&mut log.writer, "{} {}", bullet, item)?;
+            }
+            Ok(())
+        })
     }
 
     /// Print a numbered list
     pub fn numbered_list(&mut self, items: &[&str]) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
         
-        for (i, item) in items.iter().enumerate() {
-            writeln!(&mut self.writer, "{}. {}", i + 1, item)?;
-        }
-        Ok(())
+        self.write_block("list", |log| {
+            for (i, item) in items.iter().enumerate() {
+                writeln!(&mut log.writer, "{}. {}", i + 1, item)?;
+            }
+            Ok(())
+        })
     }
 
     /// Print items in columns (useful for BookDB's ls output)
@@ -201,26 +455,388 @@ impl Stderr {
         if items.is_empty() { return Ok(()); }
         
         // Calculate column width
-        let max_width = items.iter().map(|s| s.chars().count()).max().unwrap_or(0);
+        let max_width = items.iter().map(|s| visible_width(s)).max().unwrap_or(0);
         let col_width = max_width + 2; // Add padding
         
-        for chunk in items.chunks(num_cols) {
-            let mut line = String::new();
-            for item in chunk {
-                line.push_str(&format!("{:<width$}", item, width = col_width));
+        self.write_block("list", |log| {
+            for chunk in items.chunks(num_cols) {
+                let mut line = String::new();
+                for item in chunk {
+                    line.push_str(item);
+                    line.push_str(&repeat_char(' ', col_width - visible_width(item)));
+                }
+                writeln!(&mut log.writer, "{}", line.trim_end())?;
             }
-            writeln!(&mut self.writer, "{}", line.trim_end())?;
+            Ok(())
+        })
+    }
+}
+
+// --- Renderers ---
+//
+// Each renderer writes to any `WriteColor`, so the same code backs both the
+// streaming methods (`banner`, `boxed`, ...) and the `render_*` string variants.
+
+pub(crate) fn write_banner<W: WriteColor>(w: &mut W, width: usize, msg: &str, fill_char: char, spec: &ColorSpec) -> io::Result<()> {
+    // Narrow terminals: cut the title, keep at least one fill char per side
+    let shortened;
+    let msg = if visible_width(msg) + 4 > width && width >= 5 {
+        shortened = truncate_ansi(msg, width - 4);
+        shortened.as_str()
+    } else {
+        msg
+    };
+    let msg_len = visible_width(msg) + 2; // account for one space on each side
+    if msg_len >= width {
+        writeln!(w, " {} ", msg)?;
+        return Ok(());
+    }
+    let total_fill = width - msg_len;
+    let left_fill = total_fill / 2;
+    let right_fill = total_fill - left_fill;
+    let left_bar = repeat_char(fill_char, left_fill);
+    let right_bar = repeat_char(fill_char, right_fill);
+
+    w.reset()?;
+    write!(w, "{} ", left_bar)?;
+    w.set_color(spec)?;
+    write!(w, "{}", msg)?;
+    w.reset()?;
+    writeln!(w, " {}", right_bar)?;
+
+    Ok(())
+}
+
+// Lines wider than the layout width are wrapped to fit inside the borders;
+// on narrow terminals the borders are dropped for indented text
+pub(crate) fn write_boxed<W: WriteColor>(
+    w: &mut W,
+    msg: &str,
+    style: &BorderStyle,
+    spec: &ColorSpec,
+    width: usize,
+) -> io::Result<()> {
+    write_titled_boxed(w, None, msg, style, spec, width)
+}
+
+// A box with `title` set into its top border: `┌─ title ───┐`
+pub(crate) fn write_titled_boxed<W: WriteColor>(
+    w: &mut W,
+    title: Option<&str>,
+    msg: &str,
+    style: &BorderStyle,
+    spec: &ColorSpec,
+    width: usize,
+) -> io::Result<()> {
+    if width < NARROW_WIDTH {
+        w.set_color(spec)?;
+        if let Some(title) = title {
+            writeln!(w, "{}", truncate_ansi(title, width))?;
+        }
+        for line in msg.lines().flat_map(|line| wrap_ansi(line, width.saturating_sub(2))) {
+            writeln!(w, "  {}", line)?;
+        }
+        return w.reset();
+    }
+    let chars = BoxChars::from_style(style);
+    let inner = width.saturating_sub(4).max(1);
+    let lines: Vec<String> = msg.lines()
+        .flat_map(|line| {
+            if visible_width(line) > inner { wrap_ansi(line, inner) } else { vec![line.to_string()] }
+        })
+        .collect();
+    // The title sits between `─ ` and ` ` and is cut to the widest box
+    let title = title.map(|title| truncate_ansi(title, inner.saturating_sub(2).max(1)));
+    let title_width = title.as_deref().map_or(0, |title| visible_width(title) + 2);
+    let content_width = lines.iter().map(|l| visible_width(l)).max().unwrap_or(0).max(title_width);
+    let box_width = content_width + 2;
+
+    let top_border = match &title {
+        Some(title) => format!("{} {} {}", chars.horizontal, title, chars.horizontal.repeat(box_width - title_width - 1)),
+        None => chars.horizontal.repeat(box_width),
+    };
+    let bottom_border = chars.horizontal.repeat(box_width);
+
+    w.set_color(spec)?;
+    writeln!(w, "{}{}{}", chars.top_left, top_border, chars.top_right)?;
+    for line in &lines {
+        let pad = repeat_char(' ', content_width - visible_width(line));
+        writeln!(w, "{} {}{} {}", chars.vertical, line, pad, chars.vertical)?;
+    }
+    writeln!(w, "{}{}{}", chars.bottom_left, bottom_border, chars.bottom_right)?;
+    w.reset()
+}
+
+// `row_specs` colors body rows (index 0 is the first row after the header);
+// rows past its end are drawn plain
+pub(crate) fn write_table<W: WriteColor>(
+    w: &mut W,
+    rows: &[&[&str]],
+    header: &ColorSpec,
+    formats: &[ColumnFormat],
+    row_specs: &[Option<ColorSpec>],
+    width: usize,
+) -> io::Result<()> {
+    if rows.is_empty() { return Ok(()); }
+    if width < NARROW_WIDTH { return write_stacked(w, rows, header, row_specs, width); }
+    write_grid(w, rows, header, formats, row_specs, width)
+}
+
+// Columns are capped at their `max_width`, and if the layout width is still
+// exceeded shrunk (widest first). Cells that don't fit are cut with `…` or
+// wrapped, per column; columns missing from `formats` get the defaults.
+pub(crate) fn write_grid<W: WriteColor>(
+    w: &mut W,
+    rows: &[&[&str]],
+    header: &ColorSpec,
+    formats: &[ColumnFormat],
+    row_specs: &[Option<ColorSpec>],
+    width: usize,
+) -> io::Result<()> {
+    if rows.is_empty() { return Ok(()); }
+
+    let num_cols = rows[0].len();
+    let mut col_widths = column_widths(rows);
+    let format = |i: usize| formats.get(i).copied().unwrap_or_default();
+    for (i, col_width) in col_widths.iter_mut().enumerate() {
+        if let Some(max) = format(i).max_width {
+            *col_width = (*col_width).min(max);
+        }
+    }
+
+    let gaps = 2 * num_cols.saturating_sub(1);
+    while col_widths.iter().sum::<usize>() + gaps > width {
+        let widest = (0..num_cols).max_by_key(|&i| col_widths[i]).unwrap_or(0);
+        if col_widths[widest] <= 3 { break; }
+        col_widths[widest] -= 1;
+    }
+
+    // Print rows; a row with wrapped cells takes several lines
+    for (row_idx, row) in rows.iter().enumerate() {
+        let cells: Vec<Vec<String>> = row.iter().take(num_cols).enumerate()
+            .map(|(col_idx, cell)| {
+                let col_width = col_widths[col_idx];
+                if visible_width(cell) <= col_width {
+                    vec![cell.to_string()]
+                } else {
+                    let ellipsis = match format(col_idx).overflow {
+                        Overflow::Wrap if row_idx > 0 => return wrap_ansi(cell, col_width),
+                        Overflow::Truncate | Overflow::Wrap => Ellipsis::End,
+                        Overflow::TruncateMiddle => Ellipsis::Middle,
+                        Overflow::TruncateStart => Ellipsis::Start,
+                    };
+                    vec![truncate(cell, col_width, ellipsis)]
+                }
+            })
+            .collect();
+        let height = cells.iter().map(Vec::len).max().unwrap_or(1);
+        let lines: Vec<String> = (0..height)
+            .map(|line_idx| {
+                let mut line = String::new();
+                for (col_idx, lines) in cells.iter().enumerate() {
+                    let cell = lines.get(line_idx).map(String::as_str).unwrap_or("");
+                    line.push_str(&format(col_idx).align.pad(cell, visible_width(cell), col_widths[col_idx]));
+                    if col_idx < cells.len() - 1 {
+                        line.push_str("  "); // Column separator
+                    }
+                }
+                line
+            })
+            .collect();
+
+        // Highlight header row
+        if row_idx == 0 {
+            w.set_color(header)?;
+            for line in &lines {
+                writeln!(w, "{}", line)?;
+            }
+            w.reset()?;
+
+            // Add separator line under header
+            let separator: String = col_widths.iter()
+                .map(|&width| "-".repeat(width))
+                .collect::<Vec<_>>()
+                .join("  ");
+            w.set_color(ColorSpec::new().set_fg(Some(ESC::GREY)))?;
+            writeln!(w, "{}", separator)?;
+            w.reset()?;
+        } else {
+            let spec = row_specs.get(row_idx - 1).and_then(Option::as_ref);
+            if let Some(spec) = spec { w.set_color(spec)?; }
+            for line in &lines {
+                writeln!(w, "{}", line)?;
+            }
+            if spec.is_some() { w.reset()?; }
         }
-        
-        Ok(())
     }
+
+    Ok(())
+}
+
+// GitHub-flavored pipe table: no colors, escape codes stripped, `|` escaped;
+// cells are padded to the column width so the source lines up too
+pub(crate) fn write_markdown<W: Write>(w: &mut W, rows: &[&[&str]]) -> io::Result<()> {
+    if rows.is_empty() { return Ok(()); }
+    let cells: Vec<Vec<String>> = rows.iter()
+        .map(|row| row.iter().map(|cell| strip_ansi(cell).replace('|', "\\|").replace('\n', "<br>")).collect())
+        .collect();
+    let cells: Vec<Vec<&str>> = cells.iter().map(|row| row.iter().map(String::as_str).collect()).collect();
+    let cells: Vec<&[&str]> = cells.iter().map(Vec::as_slice).collect();
+    let col_widths: Vec<usize> = column_widths(&cells).into_iter().map(|width| width.max(3)).collect();
+
+    let line = |row: &[&str]| {
+        let padded: Vec<String> = col_widths.iter().enumerate()
+            .map(|(i, &width)| {
+                let cell = row.get(i).copied().unwrap_or("");
+                format!("{}{}", cell, " ".repeat(width - visible_width(cell)))
+            })
+            .collect();
+        format!("| {} |", padded.join(" | "))
+    };
+    writeln!(w, "{}", line(cells[0]))?;
+    let separator: Vec<String> = col_widths.iter().map(|&width| "-".repeat(width)).collect();
+    writeln!(w, "| {} |", separator.join(" | "))?;
+    for row in &cells[1..] {
+        writeln!(w, "{}", line(row))?;
+    }
+    Ok(())
+}
+
+// One record per line, fields quoted (RFC 4180 style) when they hold the
+// delimiter, a quote or a line break; escape codes are stripped
+pub(crate) fn write_delimited<W: Write>(w: &mut W, rows: &[&[&str]], delimiter: char) -> io::Result<()> {
+    for row in rows {
+        let fields: Vec<String> = row.iter()
+            .map(|cell| {
+                let cell = strip_ansi(cell);
+                if cell.contains([delimiter, '"', '\n', '\r']) {
+                    format!("\"{}\"", cell.replace('"', "\"\""))
+                } else {
+                    cell
+                }
+            })
+            .collect();
+        writeln!(w, "{}", fields.join(&delimiter.to_string()))?;
+    }
+    Ok(())
+}
+
+// `hexdump -C`: rows identical to the one before collapse to a single `*`,
+// and the last line is the total length
+pub(crate) fn write_hexdump<W: WriteColor>(w: &mut W, bytes: &[u8], width: usize) -> io::Result<()> {
+    // A 16-byte row is 78 columns
+    let per_row = if width >= 78 { 16 } else { 8 };
+    let mut faint = ColorSpec::new();
+    faint.set_fg(Some(ESC::GREY));
+    let printable = |b: u8| b.is_ascii_graphic() || b == b' ';
+
+    let mut previous: Option<&[u8]> = None;
+    let mut squeezed = false;
+    for (row_idx, row) in bytes.chunks(per_row).enumerate() {
+        if row.len() == per_row && previous == Some(row) {
+            if !squeezed { writeln!(w, "*")?; }
+            squeezed = true;
+            continue;
+        }
+        previous = Some(row);
+        squeezed = false;
+
+        w.set_color(&faint)?;
+        write!(w, "{:08x}", row_idx * per_row)?;
+        w.reset()?;
+        write!(w, "  ")?;
+        for i in 0..per_row {
+            if i == per_row / 2 { write!(w, " ")?; }
+            match row.get(i) {
+                Some(&b) if printable(b) => write!(w, "{:02x} ", b)?,
+                Some(&b) => {
+                    w.set_color(&faint)?;
+                    write!(w, "{:02x}", b)?;
+                    w.reset()?;
+                    write!(w, " ")?;
+                }
+                None => write!(w, "   ")?,
+            }
+        }
+        write!(w, " |")?;
+        for &b in row {
+            if printable(b) {
+                write!(w, "{}", b as char)?;
+            } else {
+                w.set_color(&faint)?;
+                write!(w, ".")?;
+                w.reset()?;
+            }
+        }
+        writeln!(w, "|")?;
+    }
+    w.set_color(&faint)?;
+    write!(w, "{:08x}", bytes.len())?;
+    w.reset()?;
+    writeln!(w)
+}
+
+// Widest cell per column; the first row sets the column count
+fn column_widths(rows: &[&[&str]]) -> Vec<usize> {
+    let mut col_widths = vec![0; rows.first().map_or(0, |row| row.len())];
+    for row in rows {
+        for (col_width, cell) in col_widths.iter_mut().zip(row.iter()) {
+            *col_width = (*col_width).max(visible_width(cell));
+        }
+    }
+    col_widths
+}
+
+// Narrow-terminal table: one `header: value` line per cell, records separated
+// by a blank line; long values wrap with a two-space hanging indent
+pub(crate) fn write_stacked<W: WriteColor>(
+    w: &mut W,
+    rows: &[&[&str]],
+    header_spec: &ColorSpec,
+    row_specs: &[Option<ColorSpec>],
+    width: usize,
+) -> io::Result<()> {
+    let headers = rows[0];
+    for (record_idx, row) in rows[1..].iter().enumerate() {
+        if record_idx > 0 { writeln!(w)?; }
+        let spec = row_specs.get(record_idx).and_then(Option::as_ref);
+        for (col_idx, cell) in row.iter().enumerate() {
+            let header = headers.get(col_idx).copied().unwrap_or("");
+            w.set_color(header_spec)?;
+            write!(w, "{}:", header)?;
+            w.reset()?;
+            if let Some(spec) = spec { w.set_color(spec)?; }
+            let value_width = width.saturating_sub(visible_width(header) + 2);
+            let lines = if visible_width(cell) <= value_width {
+                vec![cell.to_string()]
+            } else {
+                wrap_ansi(cell, width.saturating_sub(2))
+            };
+            match lines.as_slice() {
+                [single] if visible_width(single) <= value_width => writeln!(w, " {}", single)?,
+                _ => {
+                    writeln!(w)?;
+                    for line in &lines {
+                        writeln!(w, "  {}", line)?;
+                    }
+                }
+            }
+            if spec.is_some() { w.reset()?; }
+        }
+    }
+    Ok(())
 }
 
 /// Trait for adding formatting extensions (if needed for modular design)
 pub trait FormattingExt {
     fn banner(&mut self, msg: &str, fill_char: char) -> io::Result<()>;
+    fn section(&mut self, title: &str) -> io::Result<()>;
+    fn end_section(&mut self) -> io::Result<()>;
     fn boxed(&mut self, msg: &str, style: BorderStyle) -> io::Result<()>;
     fn simple_table(&mut self, rows: &[&[&str]]) -> io::Result<()>;
+    fn render_banner(&self, msg: &str, fill_char: char, ansi: bool) -> String;
+    fn render_boxed(&self, msg: &str, style: BorderStyle, ansi: bool) -> String;
+    fn render_table(&self, rows: &[&[&str]], ansi: bool) -> String;
     fn table<T: TableRow>(&mut self, headers: &[&str], rows: &[T]) -> io::Result<()>;
     fn list(&mut self, items: &[&str], bullet: &str) -> io::Result<()>;
     fn columns(&mut self, items: &[&str], num_cols: usize) -> io::Result<()>;
@@ -232,6 +848,14 @@ impl FormattingExt for Stderr {
         self.banner(msg, fill_char)
     }
 
+    fn section(&mut self, title: &str) -> io::Result<()> {
+        self.section(title)
+    }
+
+    fn end_section(&mut self) -> io::Result<()> {
+        self.end_section()
+    }
+
     fn boxed(&mut self, msg: &str, style: BorderStyle) -> io::Result<()> {
         self.boxed(msg, style)
     }
@@ -240,6 +864,18 @@ impl FormattingExt for Stderr {
         self.simple_table(rows)
     }
 
+    fn render_banner(&self, msg: &str, fill_char: char, ansi: bool) -> String {
+        self.render_banner(msg, fill_char, ansi)
+    }
+
+    fn render_boxed(&self, msg: &str, style: BorderStyle, ansi: bool) -> String {
+        self.render_boxed(msg, style, ansi)
+    }
+
+    fn render_table(&self, rows: &[&[&str]], ansi: bool) -> String {
+        self.render_table(rows, ansi)
+    }
+
     fn table<T: TableRow>(&mut self, headers: &[&str], rows: &[T]) -> io::Result<()> {
         self.table(headers, rows)
     }
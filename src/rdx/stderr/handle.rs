@@ -0,0 +1,146 @@
+//! `StderrHandle`: a cloneable, thread-safe reference to one logger
+//!
+//! Every clone points at the same `Stderr` (config, label, sinks, buffered
+//! output). Like the global `logger`, each method holds the lock for one
+//! call only, and a panic while logging does not poison the handle.
+
+use std::fmt::Display;
+use std::io;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::adapters::LevelWriter;
+use super::stderr::{LogLevel, Stderr};
+
+/// A cheap, cloneable handle to a shared `Stderr`, for passing a logger into
+/// threads, closures and structs without the global static.
+///
+/// ```
+/// use stderr::Stderr;
+///
+/// let log = Stderr::builder().label("pool").build().handle();
+///
+/// let workers: Vec<_> = (0..4)
+///     .map(|id| {
+///         let log = log.clone();
+///         std::thread::spawn(move || log.info(format_args!("worker {} done", id)))
+///     })
+///     .collect();
+/// for worker in workers {
+///     worker.join().unwrap();
+/// }
+/// log.okay("all workers finished");
+/// ```
+#[derive(Clone)]
+pub struct StderrHandle {
+    inner: Arc<Mutex<Stderr>>,
+}
+
+impl StderrHandle {
+    pub fn new(log: Stderr) -> Self {
+        Self { inner: Arc::new(Mutex::new(log)) }
+    }
+
+    /// Locks the logger for direct access to the full `Stderr` API.
+    ///
+    /// Keep the guard short-lived; every clone blocks until it is dropped.
+    pub fn lock(&self) -> MutexGuard<'_, Stderr> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Runs `f` with the logger locked
+    pub fn with<R>(&self, f: impl FnOnce(&mut Stderr) -> R) -> R {
+        f(&mut self.lock())
+    }
+
+    /// `Stderr::batch` with the lock held throughout, so other clones can't
+    /// interleave with the batch
+    pub fn batch<R>(&self, f: impl FnOnce(&mut Stderr) -> R) -> R {
+        self.lock().batch(f)
+    }
+
+    pub fn info(&self, msg: impl Display) {
+        self.lock().info(msg);
+    }
+
+    #[track_caller]
+    pub fn warn(&self, msg: impl Display) {
+        self.lock().warn(msg);
+    }
+
+    #[track_caller]
+    pub fn error(&self, msg: impl Display) {
+        self.lock().error(msg);
+    }
+
+    pub fn okay(&self, msg: impl Display) {
+        self.lock().okay(msg);
+    }
+
+    pub fn note(&self, msg: impl Display) {
+        self.lock().note(msg);
+    }
+
+    pub fn debug(&self, msg: impl Display) {
+        self.lock().debug(msg);
+    }
+
+    pub fn devlog(&self, msg: impl Display) {
+        self.lock().devlog(msg);
+    }
+
+    pub fn trace(&self, msg: impl Display) {
+        self.lock().trace(msg);
+    }
+
+    pub fn magic(&self, msg: impl Display) {
+        self.lock().magic(msg);
+    }
+
+    pub fn silly(&self, msg: impl Display) {
+        self.lock().silly(msg);
+    }
+
+    pub fn log(&self, level: LogLevel, msg: impl Display) {
+        self.lock().log(level, msg);
+    }
+
+    pub fn try_log(&self, level: LogLevel, msg: impl Display) -> io::Result<()> {
+        self.lock().try_log(level, msg)
+    }
+
+    pub fn progress(&self, msg: &str, percent: Option<u8>) {
+        self.lock().progress(msg, percent);
+    }
+
+    /// Whether a message at `level` would currently go anywhere
+    pub fn is_enabled(&self, level: LogLevel) -> bool {
+        self.lock().is_enabled(level)
+    }
+
+    /// An `io::Write` that logs each line at `level`, locking once per line.
+    /// It owns a clone of the handle, so it can be moved to another thread.
+    pub fn writer_for(&self, level: LogLevel) -> LevelWriter<'static> {
+        LevelWriter::shared(self.clone(), level)
+    }
+
+    pub fn flush(&self) -> io::Result<()> {
+        self.lock().flush()
+    }
+
+    /// Logs an error and exits the process with status 1
+    pub fn fatal(&self, msg: impl Display) -> ! {
+        self.lock().fatal(msg)
+    }
+}
+
+impl From<Stderr> for StderrHandle {
+    fn from(log: Stderr) -> Self {
+        Self::new(log)
+    }
+}
+
+impl std::fmt::Debug for StderrHandle {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("StderrHandle").finish_non_exhaustive()
+    }
+}
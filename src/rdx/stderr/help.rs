@@ -0,0 +1,224 @@
+//! Structured help pages: usage, aligned sections, examples and a footer
+
+use std::io;
+use termcolor::{ColorSpec, WriteColor};
+use super::stderr::{Stderr, OptionFlag};
+use super::formatting::write_boxed;
+use super::width::NARROW_WIDTH;
+use super::args::LOG_FLAGS;
+use crate::esc::boxes::BorderStyle;
+use crate::esc::colors::Color as ESC;
+use crate::utils::ansi::{visible_width, wrap_ansi};
+use crate::utils::helpers::render_to_string;
+
+// Widest the name column of a section may get, as a share of the width
+const NAME_COLUMN_SHARE: usize = 3;
+
+/// A help page built from parts, rendered with aligned, colored sections.
+///
+/// ```
+/// use stderr::{HelpBuilder, Stderr};
+///
+/// let help = HelpBuilder::new("deploy")
+///     .about("Ships the current build to an environment")
+///     .usage("deploy [OPTIONS] <ENV>")
+///     .section("COMMANDS", &[("plan", "Show what would change"), ("apply", "Make the changes")])
+///     .section("OPTIONS", &[("-f, --force", "Skip the confirmation prompt")])
+///     .example("deploy apply staging", "deploy to staging")
+///     .footer("Docs: https://example.com/deploy");
+///
+/// let mut log = Stderr::new();
+/// log.print_help(&help).unwrap();
+/// assert!(log.render_help(&help, false).contains("COMMANDS:"));
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct HelpBuilder {
+    pub(crate) name: String,
+    pub(crate) about: Option<String>,
+    pub(crate) usage: Vec<String>,
+    pub(crate) sections: Vec<(String, Vec<(String, String)>)>,
+    pub(crate) examples: Vec<(String, String)>,
+    pub(crate) footer: Option<String>,
+}
+
+impl HelpBuilder {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self { name: name.into(), ..Self::default() }
+    }
+
+    /// One-paragraph description shown under the name
+    pub fn about(mut self, text: impl Into<String>) -> Self {
+        self.about = Some(text.into());
+        self
+    }
+
+    /// Adds a usage line; call again for alternative forms
+    pub fn usage(mut self, line: impl Into<String>) -> Self {
+        self.usage.push(line.into());
+        self
+    }
+
+    /// Adds a titled section of `(name, description)` rows, e.g. `COMMANDS`
+    pub fn section(mut self, title: impl Into<String>, rows: &[(&str, &str)]) -> Self {
+        let rows = rows.iter().map(|&(name, desc)| (name.to_string(), desc.to_string())).collect();
+        self.sections.push((title.into(), rows));
+        self
+    }
+
+    /// Adds a row to the last section with this title, creating it if needed
+    pub fn row(mut self, title: &str, name: impl Into<String>, desc: impl Into<String>) -> Self {
+        let row = (name.into(), desc.into());
+        match self.sections.iter_mut().rev().find(|(t, _)| t == title) {
+            Some((_, rows)) => rows.push(row),
+            None => self.sections.push((title.to_string(), vec![row])),
+        }
+        self
+    }
+
+    /// Adds an example command with a short explanation (may be empty)
+    pub fn example(mut self, command: impl Into<String>, desc: impl Into<String>) -> Self {
+        self.examples.push((command.into(), desc.into()));
+        self
+    }
+
+    /// Adds a `LOGGING` section generated from the flags that
+    /// [`StderrConfig::from_args`](crate::StderrConfig::from_args) parses,
+    /// with their environment-variable equivalents
+    ///
+    /// ```
+    /// use stderr::{HelpBuilder, Stderr};
+    ///
+    /// let help = HelpBuilder::new("app").usage("app [OPTIONS]").logging_flags();
+    /// let text = Stderr::new().render_help(&help, false);
+    /// assert!(text.contains("--quiet"));
+    /// assert!(text.contains("[env: QUIET_MODE]"));
+    /// ```
+    pub fn logging_flags(mut self) -> Self {
+        for flag in LOG_FLAGS {
+            let desc = match flag.env {
+                Some(env) => format!("{} [env: {}]", flag.help, env),
+                None => flag.help.to_string(),
+            };
+            self = self.row("LOGGING", flag.names, desc);
+        }
+        self
+    }
+
+    /// Closing note, e.g. a docs link or where to report bugs
+    pub fn footer(mut self, text: impl Into<String>) -> Self {
+        self.footer = Some(text.into());
+        self
+    }
+}
+
+#[cfg(feature = "formatting")]
+impl Stderr {
+    /// Prints a help page built with [`HelpBuilder`]
+    pub fn print_help(&mut self, help: &HelpBuilder) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let border = self.border(BorderStyle::Light);
+        let (spec, width) = (self.theme.boxes.spec().clone(), self.width.layout());
+        self.write_block("help", |log| write_help(&mut log.writer, help, &border, &spec, width))
+    }
+
+    /// Renders a help page to a `String`, e.g. for a pager or a file
+    pub fn render_help(&self, help: &HelpBuilder, ansi: bool) -> String {
+        let border = self.border(BorderStyle::Light);
+        render_to_string(ansi && !self.is_plain(), |buf| write_help(buf, help, &border, self.theme.boxes.spec(), self.width.layout()))
+    }
+}
+
+fn write_help<W: WriteColor>(w: &mut W, help: &HelpBuilder, border: &BorderStyle, box_spec: &ColorSpec, width: usize) -> io::Result<()> {
+    let mut header = help.name.clone();
+    if let Some(about) = &help.about {
+        header.push_str("\n\n");
+        header.push_str(about);
+    }
+    write_boxed(w, &header, border, box_spec, width)?;
+
+    if !help.usage.is_empty() {
+        write_heading(w, "USAGE")?;
+        for line in &help.usage {
+            write_wrapped(w, line, 2, width)?;
+        }
+    }
+
+    for (title, rows) in &help.sections {
+        write_heading(w, title)?;
+        write_rows(w, rows, width)?;
+    }
+
+    if !help.examples.is_empty() {
+        write_heading(w, "EXAMPLES")?;
+        for (command, desc) in &help.examples {
+            if !desc.is_empty() {
+                w.set_color(ColorSpec::new().set_dimmed(true))?;
+                write_wrapped(w, &format!("# {}", desc), 2, width)?;
+                w.reset()?;
+            }
+            w.set_color(ColorSpec::new().set_fg(Some(ESC::GREEN)))?;
+            write_wrapped(w, &format!("$ {}", command), 2, width)?;
+            w.reset()?;
+        }
+    }
+
+    if let Some(footer) = &help.footer {
+        writeln!(w)?;
+        w.set_color(ColorSpec::new().set_dimmed(true))?;
+        write_wrapped(w, footer, 0, width)?;
+        w.reset()?;
+    }
+    Ok(())
+}
+
+fn write_heading<W: WriteColor>(w: &mut W, title: &str) -> io::Result<()> {
+    writeln!(w)?;
+    w.set_color(ColorSpec::new().set_fg(Some(ESC::YELLOW)).set_bold(true))?;
+    write!(w, "{}:", title)?;
+    w.reset()?;
+    writeln!(w)
+}
+
+// Name column sized to the widest name (within a share of the width),
+// descriptions wrapped with a hanging indent; narrow terminals put the
+// description under its name
+fn write_rows<W: WriteColor>(w: &mut W, rows: &[(String, String)], width: usize) -> io::Result<()> {
+    let narrow = width < NARROW_WIDTH;
+    let name_width = rows.iter()
+        .map(|(name, _)| visible_width(name))
+        .filter(|&n| n <= width / NAME_COLUMN_SHARE)
+        .max()
+        .unwrap_or(0);
+    let desc_indent = 2 + name_width + 2;
+
+    for (name, desc) in rows {
+        let name_len = visible_width(name);
+        w.set_color(ColorSpec::new().set_fg(Some(ESC::GREEN)))?;
+        write!(w, "  {}", name)?;
+        w.reset()?;
+
+        if desc.is_empty() {
+            writeln!(w)?;
+        } else if narrow || name_len > name_width {
+            writeln!(w)?;
+            write_wrapped(w, desc, if narrow { 6 } else { desc_indent }, width)?;
+        } else {
+            let lines = wrap_ansi(desc, width.saturating_sub(desc_indent));
+            for (i, line) in lines.iter().enumerate() {
+                if i == 0 {
+                    writeln!(w, "{:pad$}{}", "", line, pad = desc_indent - 2 - name_len)?;
+                } else {
+                    writeln!(w, "{:indent$}{}", "", line, indent = desc_indent)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn write_wrapped<W: WriteColor>(w: &mut W, text: &str, indent: usize, width: usize) -> io::Result<()> {
+    for line in wrap_ansi(text, width.saturating_sub(indent)) {
+        writeln!(w, "{:indent$}{}", "", line, indent = indent)?;
+    }
+    Ok(())
+}
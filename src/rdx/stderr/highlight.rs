@@ -0,0 +1,466 @@
+//! Syntax coloring for config dumps: TOML and YAML text is tokenized line by
+//! line (nothing is parsed, so invalid input still prints as-is) and drawn
+//! in the same colors as `json`. The `syntax` feature adds Rust and JSON
+//! for `code` blocks.
+
+use std::io;
+use termcolor::{ColorSpec, WriteColor};
+
+use super::stderr::{Stderr, OptionFlag};
+use crate::esc::colors::Color as ESC;
+use crate::utils::helpers::render_to_string;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Token {
+    Plain,
+    Key,
+    Section,
+    Str,
+    Number,
+    Keyword,
+    Comment,
+}
+
+impl Token {
+    fn spec(self) -> Option<ColorSpec> {
+        let mut spec = ColorSpec::new();
+        match self {
+            Token::Plain => return None,
+            Token::Key => spec.set_fg(Some(ESC::BLUE2)),
+            Token::Section => spec.set_fg(Some(ESC::BLUE2)).set_bold(true),
+            Token::Str => spec.set_fg(Some(ESC::GREEN)),
+            Token::Number => spec.set_fg(Some(ESC::YELLOW)),
+            Token::Keyword => spec.set_fg(Some(ESC::MAGENTA)),
+            Token::Comment => spec.set_fg(Some(ESC::GREY)),
+        };
+        Some(spec)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Lang {
+    Toml,
+    Yaml,
+    #[cfg(feature = "syntax")]
+    Rust,
+    #[cfg(feature = "syntax")]
+    Json,
+}
+
+type Line<'a> = Vec<(Token, &'a str)>;
+
+// Carried from line to line
+#[derive(Default)]
+struct State {
+    // Closing delimiter of an open TOML multi-line string
+    open_string: Option<&'static str>,
+    // Open TOML brackets: inside a multi-line array every line is a value
+    depth: usize,
+    // Indent of the YAML key that started a `|` / `>` block scalar
+    block: Option<usize>,
+    // Inside a Rust `/* */` comment
+    #[cfg(feature = "syntax")]
+    comment: bool,
+}
+
+fn push<'a>(line: &mut Line<'a>, token: Token, text: &'a str) {
+    if !text.is_empty() { line.push((token, text)); }
+}
+
+// Byte index of the first char matching `pred` outside quoted strings
+fn find_unquoted(text: &str, pred: impl Fn(&str, usize) -> bool) -> Option<usize> {
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) => {
+                if escaped { escaped = false; } else if c == '\\' && q == '"' { escaped = true; } else if c == q { quote = None; }
+            }
+            None if c == '"' || c == '\'' => quote = Some(c),
+            None if pred(text, i) => return Some(i),
+            None => {}
+        }
+    }
+    None
+}
+
+fn toml_line<'a>(text: &'a str, state: &mut State) -> Line<'a> {
+    let mut line = Line::new();
+    let mut rest = text;
+    if let Some(delim) = state.open_string {
+        let Some(end) = rest.find(delim) else {
+            push(&mut line, Token::Str, rest);
+            return line;
+        };
+        push(&mut line, Token::Str, &rest[..end + 3]);
+        rest = &rest[end + 3..];
+        state.open_string = None;
+        value(&mut line, rest, Lang::Toml, state);
+        return line;
+    }
+
+    let body = rest.trim_start();
+    push(&mut line, Token::Plain, &rest[..rest.len() - body.len()]);
+    if state.depth > 0 {
+        value(&mut line, body, Lang::Toml, state);
+    } else if body.starts_with('#') {
+        push(&mut line, Token::Comment, body);
+    } else if body.starts_with('[') {
+        let end = find_unquoted(body, |s, i| s[i..].starts_with(']') && !s[i + 1..].starts_with(']'))
+            .map_or(body.len(), |i| i + 1);
+        push(&mut line, Token::Section, &body[..end]);
+        value(&mut line, &body[end..], Lang::Toml, state);
+    } else if let Some(eq) = find_unquoted(body, |s, i| s[i..].starts_with('=')) {
+        let key = body[..eq].trim_end();
+        push(&mut line, Token::Key, key);
+        push(&mut line, Token::Plain, &body[key.len()..eq + 1]);
+        value(&mut line, &body[eq + 1..], Lang::Toml, state);
+    } else {
+        value(&mut line, body, Lang::Toml, state);
+    }
+    line
+}
+
+fn yaml_line<'a>(text: &'a str, state: &mut State) -> Line<'a> {
+    let mut line = Line::new();
+    let body = text.trim_start();
+    let indent = text.len() - body.len();
+    if let Some(block) = state.block {
+        if body.is_empty() || indent > block {
+            push(&mut line, Token::Str, text);
+            return line;
+        }
+        state.block = None;
+    }
+
+    push(&mut line, Token::Plain, &text[..indent]);
+    if body.starts_with('#') {
+        push(&mut line, Token::Comment, body);
+        return line;
+    }
+    if body == "---" || body == "..." || body.starts_with("--- ") {
+        push(&mut line, Token::Comment, body);
+        return line;
+    }
+    let mut body = body;
+    while body == "-" || body.starts_with("- ") {
+        let item = body[1..].len() - body[1..].trim_start().len() + 1;
+        push(&mut line, Token::Plain, &body[..item]);
+        body = &body[item..];
+    }
+    let colon = find_unquoted(body, |s, i| {
+        s[i..].starts_with(':') && (i + 1 == s.len() || s[i + 1..].starts_with([' ', '\t']))
+    });
+    let colon = colon.filter(|&i| !body[..i].contains(" #") && !body.starts_with(['{', '[']));
+    match colon {
+        Some(colon) => {
+            push(&mut line, Token::Key, &body[..colon]);
+            push(&mut line, Token::Plain, ":");
+            let value_text = &body[colon + 1..];
+            if value_text.trim_start().starts_with(['|', '>']) {
+                state.block = Some(indent);
+            }
+            value(&mut line, value_text, Lang::Yaml, state);
+        }
+        None => value(&mut line, body, Lang::Yaml, state),
+    }
+    line
+}
+
+// Strings, numbers, keywords, comments and punctuation after a key
+fn value<'a>(line: &mut Line<'a>, text: &'a str, lang: Lang, state: &mut State) {
+    let mut rest = text;
+    let mut after_space = true;
+    while let Some(c) = rest.chars().next() {
+        let len = match c {
+            '#' if lang == Lang::Toml || after_space => {
+                push(line, Token::Comment, rest);
+                return;
+            }
+            '"' | '\'' if lang == Lang::Toml && (rest.starts_with("\"\"\"") || rest.starts_with("'''")) => {
+                let delim = if c == '"' { "\"\"\"" } else { "'''" };
+                match rest[3..].find(delim) {
+                    Some(end) => {
+                        let mut len = 3 + end + 3;
+                        // A quote right before the delimiter belongs to the string
+                        while rest[len..].starts_with(c) { len += 1; }
+                        push(line, Token::Str, &rest[..len]);
+                        len
+                    }
+                    None => {
+                        push(line, Token::Str, rest);
+                        state.open_string = Some(delim);
+                        return;
+                    }
+                }
+            }
+            '"' | '\'' => {
+                let len = string_len(rest, c);
+                push(line, Token::Str, &rest[..len]);
+                len
+            }
+            '[' | '{' => {
+                state.depth += 1;
+                push(line, Token::Plain, &rest[..1]);
+                1
+            }
+            ']' | '}' => {
+                state.depth = state.depth.saturating_sub(1);
+                push(line, Token::Plain, &rest[..1]);
+                1
+            }
+            c if c.is_whitespace() || matches!(c, ',' | '=' | ':') => {
+                push(line, Token::Plain, &rest[..c.len_utf8()]);
+                c.len_utf8()
+            }
+            _ => {
+                let len = rest
+                    .find(|c: char| c.is_whitespace() || matches!(c, ',' | ']' | '}') || (lang == Lang::Toml && c == '#'))
+                    .unwrap_or(rest.len());
+                // `key:` inside a YAML flow mapping
+                if lang == Lang::Yaml && len > 1 && rest[..len].ends_with(':') {
+                    push(line, Token::Key, &rest[..len - 1]);
+                    push(line, Token::Plain, ":");
+                } else {
+                    push(line, word(&rest[..len], lang), &rest[..len]);
+                }
+                len
+            }
+        };
+        after_space = c.is_whitespace();
+        rest = &rest[len..];
+    }
+}
+
+// Length of a quoted string starting at `text[0]`, through its closing quote
+// (or the end of the line if it never closes)
+fn string_len(text: &str, quote: char) -> usize {
+    let mut escaped = false;
+    for (i, c) in text.char_indices().skip(1) {
+        if escaped { escaped = false; } else if c == '\\' && quote == '"' { escaped = true; } else if c == quote { return i + 1; }
+    }
+    text.len()
+}
+
+// Bare words: keywords and numbers stand out; in YAML anything else is a
+// plain string, in TOML it's a date, time or special float
+fn word(word: &str, lang: Lang) -> Token {
+    let keyword = match lang {
+        Lang::Yaml => matches!(word, "true" | "false" | "True" | "False" | "null" | "Null" | "~" | "yes" | "no"),
+        _ => matches!(word, "true" | "false"),
+    };
+    if keyword {
+        Token::Keyword
+    } else if word.trim_start_matches(['+', '-', '.']).starts_with(|c: char| c.is_ascii_digit())
+        || matches!(word, "inf" | "nan" | "+inf" | "-inf" | ".inf" | "-.inf" | ".nan")
+    {
+        Token::Number
+    } else if lang == Lang::Yaml && word.starts_with(['&', '*', '!']) {
+        Token::Keyword
+    } else if lang == Lang::Yaml && word.starts_with(['|', '>']) {
+        Token::Plain
+    } else if lang == Lang::Yaml {
+        Token::Str
+    } else {
+        Token::Plain
+    }
+}
+
+#[cfg(feature = "syntax")]
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern",
+    "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut", "pub",
+    "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true", "type", "unsafe",
+    "use", "where", "while",
+];
+
+// Keywords, strings and chars, numbers, comments, attributes, macros and
+// capitalized names (types); strings and block comments may span lines
+#[cfg(feature = "syntax")]
+fn rust_line<'a>(text: &'a str, state: &mut State) -> Line<'a> {
+    let mut line = Line::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        if state.comment {
+            let end = rest.find("*/").map_or(rest.len(), |i| i + 2);
+            state.comment = end == rest.len() && !rest.ends_with("*/");
+            push(&mut line, Token::Comment, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+        if let Some(delim) = state.open_string {
+            let end = closing(rest, delim);
+            if end.is_some() { state.open_string = None; }
+            let end = end.unwrap_or(rest.len());
+            push(&mut line, Token::Str, &rest[..end]);
+            rest = &rest[end..];
+            continue;
+        }
+
+        let c = rest.chars().next().unwrap_or_default();
+        let ident_len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        let (token, len) = if rest.starts_with("//") {
+            (Token::Comment, rest.len())
+        } else if rest.starts_with("/*") {
+            state.comment = true;
+            (Token::Comment, 2)
+        } else if let Some(delim) = string_start(rest) {
+            // The prefix (`b`, `r#`, ...) and opening quote, then the body
+            let open = rest.find('"').map_or(1, |i| i + 1);
+            let len = closing(&rest[open..], delim).map(|end| open + end);
+            if len.is_none() { state.open_string = Some(delim); }
+            (Token::Str, len.unwrap_or(rest.len()))
+        } else if c == '\'' {
+            // A char literal, or else a lifetime
+            match rest[1..].find('\'') {
+                Some(end) if end <= 10 && (rest[1..].starts_with('\\') || rest[1..1 + end].chars().count() == 1) => (Token::Str, end + 2),
+                _ => (Token::Keyword, 1 + rest[1..].find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len() - 1)),
+            }
+        } else if c.is_ascii_digit() {
+            let len = rest.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '.')).unwrap_or(rest.len());
+            // `0..10` is a range, not a float
+            (Token::Number, rest[..len].find("..").unwrap_or(len))
+        } else if rest.starts_with("#[") || rest.starts_with("#![") {
+            (Token::Comment, rest.find(']').map_or(rest.len(), |i| i + 1))
+        } else if ident_len > 0 && rest[ident_len..].starts_with('!') && !rest[ident_len..].starts_with("!=") {
+            (Token::Section, ident_len + 1)
+        } else if ident_len > 0 && RUST_KEYWORDS.contains(&&rest[..ident_len]) {
+            (Token::Keyword, ident_len)
+        } else if ident_len > 0 && c.is_uppercase() {
+            (Token::Key, ident_len)
+        } else if ident_len > 0 {
+            (Token::Plain, ident_len)
+        } else {
+            (Token::Plain, c.len_utf8())
+        };
+        push(&mut line, token, &rest[..len]);
+        rest = &rest[len..];
+    }
+    line
+}
+
+// The closing delimiter of a string literal starting here: `"` for normal
+// and byte strings, `"#`... for raw ones
+#[cfg(feature = "syntax")]
+fn string_start(text: &str) -> Option<&'static str> {
+    let body = text.strip_prefix('b').unwrap_or(text);
+    if body.starts_with('"') { return Some("\""); }
+    let hashes = body.strip_prefix('r')?;
+    let count = hashes.len() - hashes.trim_start_matches('#').len();
+    if !hashes[count..].starts_with('"') { return None; }
+    ["\"", "\"#", "\"##", "\"###"].get(count).copied()
+}
+
+// Length through the closing `delim`, skipping escaped quotes in normal strings
+#[cfg(feature = "syntax")]
+fn closing(text: &str, delim: &str) -> Option<usize> {
+    if delim != "\"" {
+        return text.find(delim).map(|i| i + delim.len());
+    }
+    let mut escaped = false;
+    for (i, c) in text.char_indices() {
+        if escaped { escaped = false; } else if c == '\\' { escaped = true; } else if c == '"' { return Some(i + 1); }
+    }
+    None
+}
+
+// Keys (strings followed by `:`), strings, numbers and `true`/`false`/`null`
+#[cfg(feature = "syntax")]
+fn json_line(text: &str) -> Line<'_> {
+    let mut line = Line::new();
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        let word_len = rest.find(|c: char| !(c.is_alphanumeric() || matches!(c, '.' | '-' | '+'))).unwrap_or(rest.len());
+        let (token, len) = if c == '"' {
+            let len = string_len(rest, '"');
+            let key = rest[len..].trim_start().starts_with(':');
+            (if key { Token::Key } else { Token::Str }, len)
+        } else if word_len > 0 && matches!(&rest[..word_len], "true" | "false" | "null") {
+            (Token::Keyword, word_len)
+        } else if word_len > 0 && (c.is_ascii_digit() || c == '-') {
+            (Token::Number, word_len)
+        } else {
+            (Token::Plain, c.len_utf8())
+        };
+        push(&mut line, token, &rest[..len]);
+        rest = &rest[len..];
+    }
+    line
+}
+
+// `text` colored per line for a language hint (`rust` / `rs`, `json`,
+// `toml`, `yaml` / `yml`), or `None` when there's no highlighter for it
+#[cfg(feature = "syntax")]
+pub(crate) fn highlight_lines(text: &str, hint: &str, ansi: bool) -> Option<Vec<String>> {
+    let lang = match hint.to_ascii_lowercase().as_str() {
+        "toml" => Lang::Toml,
+        "yaml" | "yml" => Lang::Yaml,
+        #[cfg(feature = "syntax")]
+        "rust" | "rs" => Lang::Rust,
+        #[cfg(feature = "syntax")]
+        "json" => Lang::Json,
+        _ => return None,
+    };
+    let rendered = render_to_string(ansi, |buf| write_highlighted(buf, text, lang));
+    Some(rendered.lines().map(str::to_string).collect())
+}
+
+fn write_highlighted<W: WriteColor>(w: &mut W, text: &str, lang: Lang) -> io::Result<()> {
+    let mut state = State::default();
+    for text in text.lines() {
+        let line = match lang {
+            Lang::Toml => toml_line(text, &mut state),
+            Lang::Yaml => yaml_line(text, &mut state),
+            #[cfg(feature = "syntax")]
+            Lang::Rust => rust_line(text, &mut state),
+            #[cfg(feature = "syntax")]
+            Lang::Json => json_line(text),
+        };
+        for (token, piece) in line {
+            match token.spec() {
+                Some(spec) => {
+                    w.set_color(&spec)?;
+                    write!(w, "{}", piece)?;
+                    w.reset()?;
+                }
+                None => write!(w, "{}", piece)?,
+            }
+        }
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+impl Stderr {
+    /// Prints TOML text with keys, section headers, strings, numbers and
+    /// comments colored, for config-dumping subcommands
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let config = "[server]\nport = 8080  # default\nhost = \"0.0.0.0\"\n";
+    /// let mut log = Stderr::new();
+    /// log.toml(config).unwrap();
+    /// assert_eq!(log.render_toml(config, false), config);
+    /// ```
+    pub fn toml(&mut self, text: &str) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.write_block("toml", |log| write_highlighted(&mut log.writer, text, Lang::Toml))
+    }
+
+    /// Like [`toml`](Self::toml), for YAML
+    pub fn yaml(&mut self, text: &str) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.write_block("yaml", |log| write_highlighted(&mut log.writer, text, Lang::Yaml))
+    }
+
+    /// Renders TOML like [`toml`](Self::toml) to a `String`
+    pub fn render_toml(&self, text: &str, ansi: bool) -> String {
+        render_to_string(ansi && !self.is_plain(), |buf| write_highlighted(buf, text, Lang::Toml))
+    }
+
+    /// Renders YAML like [`yaml`](Self::yaml) to a `String`
+    pub fn render_yaml(&self, text: &str, ansi: bool) -> String {
+        render_to_string(ansi && !self.is_plain(), |buf| write_highlighted(buf, text, Lang::Yaml))
+    }
+}
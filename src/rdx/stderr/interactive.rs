@@ -1,10 +1,12 @@
 //! Interactive features for stderr - prompts, confirmations, user input
 
-use std::io::{self, IsTerminal, Write};
+use std::io::{self, IsTerminal, Read, Write};
+use std::time::Duration;
 use termcolor::{Color, WriteColor};
-use super::stderr::{Stderr, OptionFlag};
+use super::stderr::{ConfirmPolicy, LogLevel, Stderr, OptionFlag};
 use crate::esc::boxes::BorderStyle;
 use crate::esc::colors::Color as ESC;
+use crate::utils::helpers::{line_read_pending, readline, readline_timeout};
 
 #[cfg(feature = "interactive")]
 impl Stderr {
@@ -19,6 +21,46 @@ impl Stderr {
         ConfirmBuilder::new(self, prompt)
     }
 
+    /// Creates a builder for a free-text input prompt.
+    pub fn input<'a>(&'a mut self, prompt: &'a str) -> InputBuilder<'a> {
+        InputBuilder::new(self, prompt)
+    }
+
+    /// Selection prompt; returns the chosen index, or `None` if the user
+    /// quits (or in quiet mode or at end of input, where no choice can be made).
+    ///
+    /// On a capable terminal the list is navigated with the arrow keys or
+    /// `j`/`k` and picked with Enter (`q` or Esc quits). Dumb terminals, plain mode
+    /// and non-unix platforms get a numbered list read line by line instead,
+    /// as does a select after a confirmation that timed out.
+    pub fn select(&mut self, prompt: &str, items: &[&str]) -> io::Result<Option<usize>> {
+        if items.is_empty() || self.check_flag(OptionFlag::Quiet) { return Ok(None); }
+        self.require_terminal()?;
+
+        // A prompt that timed out still has a line read waiting on stdin;
+        // the numbered list goes through that same reader
+        if self.supports_cursor_keys() && !line_read_pending() {
+            if let Some(raw) = RawMode::enable() {
+                return self.select_with_keys(prompt, items, raw);
+            }
+        }
+
+        self.write_select_menu(prompt, items)?;
+        loop {
+            self.write_select_prompt(items.len())?;
+
+            let input = readline()?;
+            // End of input: no choice can be made
+            if input.is_empty() { return Ok(None); }
+            self.prompt_answered();
+
+            match parse_select(&input, items.len()) {
+                Some(choice) => return Ok(choice),
+                None => self.warn("Invalid input. Please try again."),
+            }
+        }
+    }
+
     /// Display help text in a formatted box
     pub fn help(&mut self, help_text: &str) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) {
@@ -38,6 +80,9 @@ pub struct ConfirmBuilder<'a> {
     use_box: bool,
     style: BorderStyle,
     prompt_color: Option<Color>,
+    default: Option<bool>,
+    timeout: Option<(Duration, bool)>,
+    policy: Option<ConfirmPolicy>,
 }
 
 impl<'a> ConfirmBuilder<'a> {
@@ -49,9 +94,35 @@ impl<'a> ConfirmBuilder<'a> {
             use_box: false, // Don't use a box by default
             style: BorderStyle::default(), // Default to Light
             prompt_color: None,
+            default: None,
+            timeout: None,
+            policy: None,
         }
     }
 
+    /// Answer taken on empty input, shown capitalized (`[Y/n/q]`).
+    pub fn default(mut self, answer: bool) -> Self {
+        self.default = Some(answer);
+        self
+    }
+
+    /// How this prompt answers when nobody can be asked, overriding
+    /// `StderrConfig::confirm_policy`.
+    pub fn policy(mut self, policy: ConfirmPolicy) -> Self {
+        self.policy = Some(policy);
+        self
+    }
+
+    /// Gives up waiting after `after` and answers `answer`, saying so.
+    ///
+    /// Only [`ask`](Self::ask) applies the timeout; `ask_async` leaves
+    /// timing out to the caller's runtime. A line typed after the timeout
+    /// isn't lost: it answers the next prompt that reads from stdin.
+    pub fn timeout(mut self, after: Duration, answer: bool) -> Self {
+        self.timeout = Some((after, answer));
+        self
+    }
+
     pub fn prompt_color(mut self, color: Color) -> Self {
         self.prompt_color = Some(color);
         self
@@ -69,48 +140,479 @@ impl<'a> ConfirmBuilder<'a> {
         self
     }
 
-    /// Asks the user for confirmation and returns the result.
-    pub fn ask(self) -> io::Result<Option<bool>> {
-        if self.stderr.config.quiet { return Ok(Some(true)); }
-        if !io::stdin().is_terminal() {
-            return Err(io::Error::new(io::ErrorKind::Unsupported, "Cannot ask for confirmation in a non-interactive terminal."));
+    /// Asks the user for confirmation and returns the result: `None` when
+    /// the user quits or stdin reaches end of file. An empty line takes the
+    /// default.
+    pub fn ask(mut self) -> io::Result<Option<bool>> {
+        if let Some(answer) = self.begin()? { return Ok(answer); }
+
+        loop {
+            self.stderr.write_confirm_prompt(self.prompt, self.prompt_color, self.use_box, self.default)?;
+
+            let input = match self.timeout {
+                Some((after, answer)) => match readline_timeout(after)? {
+                    Some(input) => input,
+                    None => {
+                        writeln!(&mut self.stderr.writer)?;
+                        let choice = if answer { "yes" } else { "no" };
+                        self.stderr.info(format!("No answer after {}s, assuming {}", after.as_secs_f32(), choice));
+                        return Ok(Some(answer));
+                    }
+                },
+                None => readline()?,
+            };
+            // End of input is not an empty answer: never fall back to the
+            // default (a closed stdin must not say yes), just give up
+            if input.is_empty() { return Ok(None); }
+            self.stderr.prompt_answered();
+
+            match parse_confirm(&input, self.default) {
+                Some(answer) => return Ok(answer),
+                None => self.stderr.warn("Invalid input. Please try again."),
+            }
+        }
+    }
+
+    /// Async variant of [`ask`](Self::ask) that reads the answer through
+    /// tokio's stdin, so it doesn't block the runtime.
+    #[cfg(feature = "async")]
+    pub async fn ask_async(mut self) -> io::Result<Option<bool>> {
+        if let Some(answer) = self.begin()? { return Ok(answer); }
+
+        loop {
+            self.stderr.write_confirm_prompt(self.prompt, self.prompt_color, self.use_box, self.default)?;
+
+            let input = crate::utils::helpers::readline_async().await?;
+            if input.is_empty() { return Ok(None); }
+            self.stderr.prompt_answered();
+
+            match parse_confirm(&input, self.default) {
+                Some(answer) => return Ok(answer),
+                None => self.stderr.warn("Invalid input. Please try again."),
+            }
         }
+    }
+
+    /// Handles the quiet/non-tty shortcuts and draws the box if enabled.
+    /// Returns `Some(answer)` when no prompt is needed.
+    fn begin(&mut self) -> io::Result<Option<Option<bool>>> {
+        if let Some(answer) = self.stderr.confirm_preflight(self.policy)? { return Ok(Some(answer)); }
 
         // If boxing is enabled, draw the box first.
         if self.use_box {
             self.stderr.boxed(self.prompt, self.style)?;
         }
+        Ok(None)
+    }
+}
+
+/// Checks an input answer; `Err` carries the message shown to the user.
+type Validator<'a> = Box<dyn Fn(&str) -> Result<(), String> + 'a>;
+
+/// A builder for a free-text input prompt.
+///
+/// Created via `Stderr::input()`. Empty input takes the default, and input
+/// rejected by the validator is reported and asked for again.
+///
+/// ```no_run
+/// use stderr::Stderr;
+///
+/// let mut log = Stderr::new();
+/// let name = log.input("Project name")
+///     .default("myapp")
+///     .validate(|s| if s.contains(' ') { Err("no spaces allowed".into()) } else { Ok(()) })
+///     .ask()?;
+/// # Ok::<(), std::io::Error>(())
+/// ```
+pub struct InputBuilder<'a> {
+    stderr: &'a mut Stderr,
+    prompt: &'a str,
+    default: Option<String>,
+    validator: Option<Validator<'a>>,
+    use_box: bool,
+    style: BorderStyle,
+    prompt_color: Option<Color>,
+}
+
+impl<'a> InputBuilder<'a> {
+    /// Creates a new input builder.
+    fn new(stderr: &'a mut Stderr, prompt: &'a str) -> Self {
+        Self {
+            stderr,
+            prompt,
+            default: None,
+            validator: None,
+            use_box: false,
+            style: BorderStyle::default(),
+            prompt_color: None,
+        }
+    }
+
+    /// Value returned for empty input, shown in brackets after the prompt.
+    pub fn default(mut self, value: impl Into<String>) -> Self {
+        self.default = Some(value.into());
+        self
+    }
+
+    /// Checks each answer; an `Err` message is shown and the prompt repeats.
+    pub fn validate(mut self, f: impl Fn(&str) -> Result<(), String> + 'a) -> Self {
+        self.validator = Some(Box::new(f));
+        self
+    }
+
+    pub fn prompt_color(mut self, color: Color) -> Self {
+        self.prompt_color = Some(color);
+        self
+    }
+
+    /// Wraps the prompt in a box.
+    pub fn boxed(mut self, use_box: bool) -> Self {
+        self.use_box = use_box;
+        self
+    }
+
+    /// Sets the `BorderStyle` for the box if it is enabled.
+    pub fn style(mut self, style: BorderStyle) -> Self {
+        self.style = style;
+        self
+    }
+
+    /// Asks for input and returns the accepted answer.
+    ///
+    /// Returns the default (or `None` without one) in quiet mode, and `None`
+    /// when stdin reaches end of file.
+    pub fn ask(mut self) -> io::Result<Option<String>> {
+        if let Some(answer) = self.begin()? { return Ok(answer); }
+
+        loop {
+            self.write_prompt()?;
+
+            let input = readline()?;
+            if input.is_empty() { return Ok(None); }
+            self.stderr.prompt_answered();
+
+            if let Some(answer) = self.accept(&input) { return Ok(Some(answer)); }
+        }
+    }
+
+    /// Async variant of [`ask`](Self::ask) that reads the answer through
+    /// tokio's stdin, so it doesn't block the runtime.
+    #[cfg(feature = "async")]
+    pub async fn ask_async(mut self) -> io::Result<Option<String>> {
+        if let Some(answer) = self.begin()? { return Ok(answer); }
 
         loop {
-            if let Some(color) = self.prompt_color {
-                // If yes, use it.
-                self.stderr.set_bold_fg(color)?;
-            } else {
-                // If no, use the default bold white.
-                self.stderr.set_bold_fg(ESC::WHITE)?;
+            self.write_prompt()?;
+
+            let input = crate::utils::helpers::readline_async().await?;
+            if input.is_empty() { return Ok(None); }
+            self.stderr.prompt_answered();
+
+            if let Some(answer) = self.accept(&input) { return Ok(Some(answer)); }
+        }
+    }
+
+    /// Handles the quiet/non-tty shortcuts and draws the box if enabled.
+    /// Returns `Some(answer)` when no prompt is needed.
+    fn begin(&mut self) -> io::Result<Option<Option<String>>> {
+        if self.stderr.check_flag(OptionFlag::Quiet) { return Ok(Some(self.default.clone())); }
+        self.stderr.require_terminal()?;
+
+        if self.use_box {
+            self.stderr.boxed(self.prompt, self.style)?;
+        }
+        Ok(None)
+    }
+
+    fn write_prompt(&mut self) -> io::Result<()> {
+        let label = if self.use_box { "Your answer" } else { self.prompt };
+        self.stderr.hide_status();
+        self.stderr.set_bold_fg(self.prompt_color.unwrap_or(ESC::WHITE))?;
+        match &self.default {
+            Some(default) => write!(&mut self.stderr.writer, "{} [{}] > ", label, default)?,
+            None => write!(&mut self.stderr.writer, "{} > ", label)?,
+        }
+        self.stderr.writer.reset()?;
+        self.stderr.writer.flush()
+    }
+
+    /// Applies the default and the validator; `None` means "ask again".
+    fn accept(&mut self, input: &str) -> Option<String> {
+        let input = input.trim();
+        let answer = match (&self.default, input.is_empty()) {
+            (Some(default), true) => default.clone(),
+            _ => input.to_string(),
+        };
+        if let Some(validate) = &self.validator {
+            if let Err(reason) = validate(&answer) {
+                self.stderr.warn(format!("{}. Please try again.", reason));
+                return None;
+            }
+        }
+        Some(answer)
+    }
+}
+
+#[cfg(feature = "interactive")]
+impl Stderr {
+    /// Shared quiet/non-tty handling for every confirm variant: answers
+    /// through `policy` (else the configured one) when nobody can be asked.
+    pub(crate) fn confirm_preflight(&self, policy: Option<ConfirmPolicy>) -> io::Result<Option<Option<bool>>> {
+        let quiet = self.check_flag(OptionFlag::Quiet);
+        let headless = match self.require_terminal() {
+            Ok(()) if !quiet => return Ok(None),
+            Ok(()) => io::Error::new(io::ErrorKind::Unsupported, "Cannot ask for confirmation in quiet mode."),
+            Err(e) => e,
+        };
+        match policy.or(self.config.confirm_policy) {
+            Some(ConfirmPolicy::FailClosed) => Ok(Some(None)),
+            Some(ConfirmPolicy::AssumeYes) => Ok(Some(Some(true))),
+            Some(ConfirmPolicy::AssumeNo) => Ok(Some(Some(false))),
+            Some(ConfirmPolicy::Error) => Err(headless),
+            // Quiet mode never agrees on the user's behalf
+            None if quiet => Ok(Some(None)),
+            None => Err(headless),
+        }
+    }
+
+    /// Errors with `Unsupported` when stdin is not a terminal or the
+    /// plain-CI profile is active.
+    pub(crate) fn require_terminal(&self) -> io::Result<()> {
+        if self.ci_profile {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "Interactive prompts are disabled in CI."));
+        }
+        if !io::stdin().is_terminal() {
+            return Err(io::Error::new(io::ErrorKind::Unsupported, "Cannot ask for input in a non-interactive terminal."));
+        }
+        Ok(())
+    }
+
+    /// Whether the arrow-key select can redraw in place: stderr must be a
+    /// terminal that understands cursor movement.
+    fn supports_cursor_keys(&self) -> bool {
+        cfg!(unix)
+            && !self.is_plain()
+            && self.writer.is_terminal()
+            && std::env::var("TERM").is_ok_and(|term| !term.is_empty() && term != "dumb")
+    }
+
+    fn select_with_keys(&mut self, prompt: &str, items: &[&str], mut raw: RawMode) -> io::Result<Option<usize>> {
+        // The list is redrawn in place, so the status line stays lifted
+        // until it collapses
+        self.hide_status();
+        self.set_bold_fg(ESC::WHITE)?;
+        writeln!(&mut self.writer, "{}", prompt)?;
+        self.writer.reset()?;
+        write!(&mut self.writer, "\x1b[?25l")?;
+        raw.cursor_hidden = true;
+
+        let mut cursor = 0;
+        let result = loop {
+            self.write_select_items(items, cursor)?;
+            let key = match read_key(&raw) {
+                Ok(key) => key,
+                Err(e) => break Err(e),
+            };
+            match key {
+                Key::Up => cursor = cursor.checked_sub(1).unwrap_or(items.len() - 1),
+                Key::Down => cursor = (cursor + 1) % items.len(),
+                Key::Enter => break Ok(Some(cursor)),
+                Key::Quit => break Ok(None),
+                Key::Interrupt => break Err(io::Error::new(io::ErrorKind::Interrupted, "Selection interrupted.")),
+                Key::Other => {}
             }
-            
-            if self.use_box {
-                write!(&mut self.stderr.writer, "Your choice [y/n/q] -> ")?;
+            // Back to the top of the list for the redraw
+            write!(&mut self.writer, "\x1b[{}A", items.len())?;
+        };
+
+        // Collapse the list into the chosen item; dropping `raw` shows the
+        // cursor again
+        write!(&mut self.writer, "\x1b[{}A\x1b[J", items.len())?;
+        if let Ok(Some(index)) = result {
+            self.set_fg(ESC::BLUE)?;
+            writeln!(&mut self.writer, "  {}", items[index])?;
+            self.writer.reset()?;
+        }
+        self.writer.flush()?;
+        drop(raw);
+        self.show_status();
+        result
+    }
+
+    /// Writes the item list with `cursor` highlighted, one cleared line each.
+    fn write_select_items(&mut self, items: &[&str], cursor: usize) -> io::Result<()> {
+        // ASCII terminals (and ASCII glyph sets) get `>` instead of `❯`
+        let ascii_glyphs = LogLevel::ALL.into_iter().all(|level| self.theme.glyphs.get(level).is_ascii());
+        let marker = if self.ascii_only() || ascii_glyphs { ">" } else { "\u{276F}" };
+        for (i, item) in items.iter().enumerate() {
+            write!(&mut self.writer, "\r\x1b[2K")?;
+            if i == cursor {
+                self.set_bold_fg(ESC::BLUE)?;
+                writeln!(&mut self.writer, "{} {}", marker, item)?;
+                self.writer.reset()?;
             } else {
-                write!(&mut self.stderr.writer, "{} [y/n/q] > ", self.prompt)?;
+                writeln!(&mut self.writer, "  {}", item)?;
             }
+        }
+        self.writer.flush()
+    }
 
-            self.stderr.writer.reset()?;
-            self.stderr.writer.flush()?;
+    /// Writes the prompt followed by the numbered item list.
+    pub(crate) fn write_select_menu(&mut self, prompt: &str, items: &[&str]) -> io::Result<()> {
+        self.hide_status();
+        self.set_bold_fg(ESC::WHITE)?;
+        writeln!(&mut self.writer, "{}", prompt)?;
+        self.writer.reset()?;
+        let digits = items.len().to_string().len();
+        for (i, item) in items.iter().enumerate() {
+            self.set_fg(ESC::BLUE)?;
+            write!(&mut self.writer, "  {:>width$}) ", i + 1, width = digits)?;
+            self.writer.reset()?;
+            writeln!(&mut self.writer, "{}", item)?;
+        }
+        Ok(())
+    }
 
-            let mut input = String::new();
-            io::stdin().read_line(&mut input)?;
+    /// Writes the `[1-N/q]` input line and flushes it to the terminal.
+    pub(crate) fn write_select_prompt(&mut self, count: usize) -> io::Result<()> {
+        self.hide_status();
+        self.set_bold_fg(ESC::WHITE)?;
+        write!(&mut self.writer, "Your choice [1-{}/q] > ", count)?;
+        self.writer.reset()?;
+        self.writer.flush()
+    }
 
-            match input.trim().chars().next().unwrap_or_default() {
-                'y' | 'Y' => return Ok(Some(true)),
-                'n' | 'N' => return Ok(Some(false)),
-                'q' | 'Q' => return Ok(None),
-                _ => {
-                    let _ = self.stderr.warn("Invalid input. Please try again.");
-                }
-            }
+    /// Writes the `[y/n/q]` prompt line and flushes it to the terminal.
+    pub(crate) fn write_confirm_prompt(&mut self, prompt: &str, color: Option<Color>, boxed: bool, default: Option<bool>) -> io::Result<()> {
+        // The status line stays lifted until the answer is in (see
+        // `prompt_answered`)
+        self.hide_status();
+        // Use the requested prompt color, or the default bold white.
+        self.set_bold_fg(color.unwrap_or(ESC::WHITE))?;
+
+        // The default answer is capitalized
+        let choices = match default {
+            Some(true) => "Y/n/q",
+            Some(false) => "y/N/q",
+            None => "y/n/q",
+        };
+        if boxed {
+            write!(&mut self.writer, "Your choice [{}] -> ", choices)?;
+        } else {
+            write!(&mut self.writer, "{} [{}] > ", prompt, choices)?;
         }
+
+        self.writer.reset()?;
+        self.writer.flush()
+    }
+
+    /// Async variant of [`confirm`](Self::confirm).
+    #[cfg(feature = "async")]
+    pub async fn confirm_async(&mut self, prompt: &str) -> io::Result<Option<bool>> {
+        self.confirm_builder(prompt).ask_async().await
+    }
+}
+
+/// A key press in the arrow-key select.
+enum Key {
+    Up,
+    Down,
+    Enter,
+    Quit,
+    Interrupt,
+    Other,
+}
+
+/// Reads one key press from stdin, decoding arrow-key escape sequences.
+///
+/// The bytes after an Esc are only waited for briefly, since a lone Esc
+/// sends nothing more; on its own it quits like `q`.
+fn read_key(raw: &RawMode) -> io::Result<Key> {
+    let mut stdin = io::stdin().lock();
+    let mut byte = || -> io::Result<Option<u8>> {
+        let mut buf = [0u8; 1];
+        Ok((stdin.read(&mut buf)? == 1).then_some(buf[0]))
+    };
+    let Some(first) = byte()? else {
+        return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "stdin closed"));
+    };
+    Ok(match first {
+        b'\r' | b'\n' => Key::Enter,
+        b'k' => Key::Up,
+        b'j' => Key::Down,
+        b'q' | b'Q' => Key::Quit,
+        0x03 => Key::Interrupt,
+        0x1b => match raw.with_timeout(|| Ok((byte()?, byte()?)))? {
+            (Some(b'[' | b'O'), Some(b'A')) => Key::Up,
+            (Some(b'[' | b'O'), Some(b'B')) => Key::Down,
+            (None, _) => Key::Quit,
+            _ => Key::Other,
+        },
+        _ => Key::Other,
+    })
+}
+
+/// Puts the terminal into unbuffered, no-echo mode via `stty` and restores
+/// the saved settings on drop, along with the cursor if it was hidden.
+struct RawMode {
+    saved: String,
+    cursor_hidden: bool,
+}
+
+impl RawMode {
+    /// `None` when `stty` is unavailable, e.g. on non-unix platforms.
+    fn enable() -> Option<Self> {
+        let saved = stty(&["-g"])?;
+        stty(&["-icanon", "-echo", "-isig", "min", "1", "time", "0"])?;
+        Some(Self { saved: saved.trim().to_string(), cursor_hidden: false })
+    }
+
+    /// Runs `read` with reads giving up after a tenth of a second
+    fn with_timeout<T>(&self, read: impl FnOnce() -> io::Result<T>) -> io::Result<T> {
+        stty(&["min", "0", "time", "1"]);
+        let result = read();
+        stty(&["min", "1", "time", "0"]);
+        result
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        let _ = stty(&[self.saved.as_str()]);
+        if self.cursor_hidden {
+            let _ = write!(io::stderr(), "\x1b[?25h");
+        }
+    }
+}
+
+fn stty(args: &[&str]) -> Option<String> {
+    let output = std::process::Command::new("stty")
+        .args(args)
+        .stdin(std::process::Stdio::inherit())
+        .output()
+        .ok()?;
+    output.status.success().then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+/// Maps a line of user input to an answer; `None` means "ask again".
+/// Empty input takes `default` when there is one.
+pub(crate) fn parse_confirm(input: &str, default: Option<bool>) -> Option<Option<bool>> {
+    match input.trim().chars().next() {
+        Some('y' | 'Y') => Some(Some(true)),
+        Some('n' | 'N') => Some(Some(false)),
+        Some('q' | 'Q') => Some(None),
+        None => default.map(Some),
+        _ => None,
+    }
+}
+
+/// Maps a line of user input to a 0-based index; `None` means "ask again".
+pub(crate) fn parse_select(input: &str, count: usize) -> Option<Option<usize>> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("q") { return Some(None); }
+    match input.parse::<usize>() {
+        Ok(n) if (1..=count).contains(&n) => Some(Some(n - 1)),
+        _ => None,
     }
 }
 
@@ -118,6 +620,8 @@ impl<'a> ConfirmBuilder<'a> {
 pub trait InteractiveExt {
     fn confirm(&mut self, prompt: &str) -> io::Result<Option<bool>>;
     fn confirm_builder<'a>(&'a mut self, prompt: &'a str) -> ConfirmBuilder<'a>;
+    fn input<'a>(&'a mut self, prompt: &'a str) -> InputBuilder<'a>;
+    fn select(&mut self, prompt: &str, items: &[&str]) -> io::Result<Option<usize>>;
     fn help(&mut self, help_text: &str) -> io::Result<()>;
 }
 
@@ -131,6 +635,14 @@ impl InteractiveExt for Stderr {
         self.confirm_builder(prompt)
     }
 
+    fn input<'a>(&'a mut self, prompt: &'a str) -> InputBuilder<'a> {
+        self.input(prompt)
+    }
+
+    fn select(&mut self, prompt: &str, items: &[&str]) -> io::Result<Option<usize>> {
+        self.select(prompt, items)
+    }
+
     fn help(&mut self, help_text: &str) -> io::Result<()> {
         self.help(help_text)
     }
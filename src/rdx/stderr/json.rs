@@ -0,0 +1,177 @@
+//! JSON-lines rendering for `OutputFormat::Json`: one object per message,
+//! with ANSI codes stripped from the text. With the `serde` feature, also
+//! a colorized pretty printer for `serde_json::Value`s.
+
+use std::fmt::Write as _;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+#[cfg(feature = "serde")]
+use std::io::{self, Write};
+#[cfg(feature = "serde")]
+use serde_json::Value;
+#[cfg(feature = "serde")]
+use termcolor::{ColorSpec, WriteColor};
+
+use super::stderr::LogLevel;
+#[cfg(feature = "serde")]
+use super::stderr::{Stderr, OptionFlag};
+#[cfg(feature = "serde")]
+use crate::esc::colors::Color as ESC;
+#[cfg(feature = "serde")]
+use crate::utils::helpers::render_to_string;
+use crate::meta::civil_date;
+use crate::utils::text::strip_ansi;
+
+/// `{"timestamp":…,"level":…,"message":…}` plus `label` / `context` when set
+pub(crate) fn json_line(level: LogLevel, msg: &str, label: Option<&str>, context: Option<&str>) -> String {
+    let mut out = String::with_capacity(msg.len() + 80);
+    out.push_str("{\"timestamp\":");
+    push_str(&mut out, &timestamp());
+    out.push_str(",\"level\":");
+    push_str(&mut out, &level.to_string());
+    out.push_str(",\"message\":");
+    push_str(&mut out, &strip_ansi(msg));
+    if let Some(label) = label {
+        out.push_str(",\"label\":");
+        push_str(&mut out, label);
+    }
+    if let Some(context) = context {
+        out.push_str(",\"context\":");
+        push_str(&mut out, context);
+    }
+    out.push('}');
+    out
+}
+
+/// `{"timestamp":…,"kind":…,"text":…}` for block output such as tables and
+/// banners, plus `label` / `context` when set
+pub(crate) fn json_block(kind: &str, text: &str, label: Option<&str>, context: Option<&str>) -> String {
+    let mut out = String::with_capacity(text.len() + 80);
+    out.push_str("{\"timestamp\":");
+    push_str(&mut out, &timestamp());
+    out.push_str(",\"kind\":");
+    push_str(&mut out, kind);
+    out.push_str(",\"text\":");
+    push_str(&mut out, strip_ansi(text).trim_end_matches('\n'));
+    if let Some(label) = label {
+        out.push_str(",\"label\":");
+        push_str(&mut out, label);
+    }
+    if let Some(context) = context {
+        out.push_str(",\"context\":");
+        push_str(&mut out, context);
+    }
+    out.push('}');
+    out
+}
+
+// A JSON string literal
+fn push_str(out: &mut String, s: &str) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if (c as u32) < 0x20 => { let _ = write!(out, "\\u{:04x}", c as u32); }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
+
+// RFC 3339 UTC with milliseconds, e.g. `2024-05-01T12:00:00.250Z`
+fn timestamp() -> String {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    let secs = now.as_secs();
+    format!(
+        "{}T{:02}:{:02}:{:02}.{:03}Z",
+        civil_date(secs / 86_400),
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60,
+        now.subsec_millis(),
+    )
+}
+
+#[cfg(feature = "serde")]
+impl Stderr {
+    /// Pretty-prints a JSON value with keys, strings, numbers and literals
+    /// in their own colors, for API-debugging output
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let value = serde_json::json!({ "id": 7, "tags": ["a"], "next": null });
+    /// Stderr::new().json(&value).unwrap();
+    /// assert_eq!(
+    ///     Stderr::new().render_json(&serde_json::json!({ "id": 7 }), false),
+    ///     "{\n  \"id\": 7\n}\n",
+    /// );
+    /// ```
+    pub fn json(&mut self, value: &Value) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.write_block("json", |log| {
+            write_pretty(&mut log.writer, value, 0)?;
+            writeln!(&mut log.writer)
+        })
+    }
+
+    /// Renders a JSON value like [`json`](Self::json) to a `String`
+    pub fn render_json(&self, value: &Value, ansi: bool) -> String {
+        render_to_string(ansi && !self.is_plain(), |buf| {
+            write_pretty(buf, value, 0)?;
+            writeln!(buf)
+        })
+    }
+}
+
+// Two-space indented, one member per line; empty containers stay `[]` / `{}`
+#[cfg(feature = "serde")]
+fn write_pretty<W: WriteColor>(w: &mut W, value: &Value, depth: usize) -> io::Result<()> {
+    let indent = "  ".repeat(depth + 1);
+    match value {
+        Value::Null => paint(w, ESC::GREY, "null"),
+        Value::Bool(b) => paint(w, ESC::MAGENTA, &b.to_string()),
+        Value::Number(n) => paint(w, ESC::YELLOW, &n.to_string()),
+        Value::String(text) => paint(w, ESC::GREEN, &quoted(text)),
+        Value::Array(items) if items.is_empty() => write!(w, "[]"),
+        Value::Object(members) if members.is_empty() => write!(w, "{{}}"),
+        Value::Array(items) => {
+            writeln!(w, "[")?;
+            for (i, item) in items.iter().enumerate() {
+                write!(w, "{}", indent)?;
+                write_pretty(w, item, depth + 1)?;
+                writeln!(w, "{}", if i + 1 < items.len() { "," } else { "" })?;
+            }
+            write!(w, "{}]", &indent[2..])
+        }
+        Value::Object(members) => {
+            writeln!(w, "{{")?;
+            for (i, (key, member)) in members.iter().enumerate() {
+                write!(w, "{}", indent)?;
+                paint(w, ESC::BLUE2, &quoted(key))?;
+                write!(w, ": ")?;
+                write_pretty(w, member, depth + 1)?;
+                writeln!(w, "{}", if i + 1 < members.len() { "," } else { "" })?;
+            }
+            write!(w, "{}}}", &indent[2..])
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+fn paint<W: WriteColor>(w: &mut W, color: termcolor::Color, text: &str) -> io::Result<()> {
+    w.set_color(ColorSpec::new().set_fg(Some(color)))?;
+    write!(w, "{}", text)?;
+    w.reset()
+}
+
+#[cfg(feature = "serde")]
+fn quoted(text: &str) -> String {
+    let mut out = String::with_capacity(text.len() + 2);
+    push_str(&mut out, text);
+    out
+}
@@ -0,0 +1,265 @@
+//! Panels: text, tables and boxes composed side by side or stacked, for
+//! dashboards built from the same pieces as `boxed` and `simple_table`
+
+use std::io;
+use termcolor::WriteColor;
+
+use super::formatting::{write_grid, write_stacked};
+use super::stderr::{Stderr, OptionFlag};
+use super::table::Align;
+use super::width::NARROW_WIDTH;
+use crate::esc::boxes::{BorderStyle, BoxChars};
+use crate::esc::style::Style;
+use crate::utils::ansi::{truncate_ansi, visible_width, wrap_ansi};
+use crate::utils::helpers::render_to_string;
+
+// Columns between panels in a row
+const GAP: usize = 2;
+
+/// A piece of a layout, drawn to whatever width it is given
+///
+/// ```
+/// use stderr::{BorderStyle, Panel, Stderr};
+///
+/// let status = Panel::table(&[&["service", "state"], &["api", "up"], &["db", "up"]])
+///     .titled("Services");
+/// let notes = Panel::text("Deploy window opens at 14:00.\nFreeze starts Friday.")
+///     .boxed(BorderStyle::Rounded);
+///
+/// let mut log = Stderr::new();
+/// log.panel(&Panel::column([
+///     Panel::text("Dashboard"),
+///     Panel::row([status, notes]),
+/// ])).unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub enum Panel {
+    /// Text, word-wrapped to the width
+    Text(String),
+    /// A table whose first row is the header
+    Table(Vec<Vec<String>>),
+    /// Key/value lines: keys right-aligned in the header style, values
+    /// wrapped beside them
+    KeyValue(Vec<(String, String)>),
+    /// A panel inside a border filling the width, with an optional title
+    /// in the top edge
+    Boxed { inner: Box<Panel>, border: BorderStyle, title: Option<String> },
+    /// Panels side by side, sharing the width evenly (stacked when narrow)
+    Row(Vec<Panel>),
+    /// Panels top to bottom
+    Column(Vec<Panel>),
+}
+
+impl Panel {
+    pub fn text(text: impl Into<String>) -> Self {
+        Panel::Text(text.into())
+    }
+
+    pub fn table(rows: &[&[&str]]) -> Self {
+        Panel::Table(rows.iter().map(|row| row.iter().map(|cell| cell.to_string()).collect()).collect())
+    }
+
+    pub fn kv<K: Into<String>, V: Into<String>>(pairs: impl IntoIterator<Item = (K, V)>) -> Self {
+        Panel::KeyValue(pairs.into_iter().map(|(key, value)| (key.into(), value.into())).collect())
+    }
+
+    pub fn row(panels: impl IntoIterator<Item = Panel>) -> Self {
+        Panel::Row(panels.into_iter().collect())
+    }
+
+    pub fn column(panels: impl IntoIterator<Item = Panel>) -> Self {
+        Panel::Column(panels.into_iter().collect())
+    }
+
+    /// Draws this panel inside a border
+    pub fn boxed(self, border: BorderStyle) -> Self {
+        Panel::Boxed { inner: Box::new(self), border, title: None }
+    }
+
+    /// Sets the box title, boxing the panel with the default border first
+    /// if it isn't boxed yet
+    pub fn titled(self, title: impl Into<String>) -> Self {
+        match self {
+            Panel::Boxed { inner, border, .. } => Panel::Boxed { inner, border, title: Some(title.into()) },
+            panel => Panel::Boxed { inner: Box::new(panel), border: BorderStyle::default(), title: Some(title.into()) },
+        }
+    }
+
+    fn render(&self, width: usize, ctx: &Ctx) -> Vec<String> {
+        let width = width.max(1);
+        match self {
+            Panel::Text(text) => text
+                .lines()
+                .flat_map(|line| {
+                    if visible_width(line) > width { wrap_ansi(line, width) } else { vec![line.to_string()] }
+                })
+                .collect(),
+            Panel::Table(rows) if rows.is_empty() => Vec::new(),
+            Panel::Table(rows) => {
+                let rows: Vec<Vec<&str>> = rows.iter().map(|row| row.iter().map(String::as_str).collect()).collect();
+                let rows: Vec<&[&str]> = rows.iter().map(Vec::as_slice).collect();
+                let header = ctx.log.theme.headers.spec();
+                // Narrow mode follows the terminal, not the panel: a table in
+                // half a wide terminal still gets columns
+                render_to_string(ctx.ansi, |buf| {
+                    if ctx.log.is_narrow() { write_stacked(buf, &rows, header, &[], width) } else { write_grid(buf, &rows, header, &[], &[], width) }
+                })
+                    .lines()
+                    .map(str::to_string)
+                    .collect()
+            }
+            Panel::KeyValue(pairs) => render_kv(pairs, width, ctx),
+            Panel::Boxed { inner, border, title } => render_boxed(inner, border, title.as_deref(), width, ctx),
+            // Stacked when narrow, or when there are more panels than columns
+            Panel::Row(panels) if width < NARROW_WIDTH || panels.len() < 2 || row_share(width, panels.len()) == 0 => {
+                panels.iter().flat_map(|panel| panel.render(width, ctx)).collect()
+            }
+            Panel::Row(panels) => render_row(panels, width, ctx),
+            Panel::Column(panels) => panels.iter().flat_map(|panel| panel.render(width, ctx)).collect(),
+        }
+    }
+}
+
+// What panels need from the logger while rendering
+struct Ctx<'a> {
+    log: &'a Stderr,
+    ansi: bool,
+}
+
+impl Ctx<'_> {
+    // A border piece in the theme's box style
+    fn paint(&self, piece: &str) -> String {
+        self.paint_in(&self.log.theme.boxes, piece)
+    }
+
+    fn paint_in(&self, style: &Style, piece: &str) -> String {
+        if self.ansi {
+            format!("{}{}{}", Style::to_ansi(style.spec()), piece, Style::ANSI_RESET)
+        } else {
+            piece.to_string()
+        }
+    }
+}
+
+fn render_kv(pairs: &[(String, String)], width: usize, ctx: &Ctx) -> Vec<String> {
+    let key_width = pairs.iter().map(|(key, _)| visible_width(key)).max().unwrap_or(0);
+    let value_width = width.saturating_sub(key_width + GAP).max(1);
+    let gap = " ".repeat(GAP);
+    let mut lines = Vec::new();
+    for (key, value) in pairs {
+        let key = ctx.paint_in(&ctx.log.theme.headers, &Align::Right.pad(key, visible_width(key), key_width));
+        let mut values: Vec<String> = value
+            .lines()
+            .flat_map(|line| {
+                if visible_width(line) > value_width { wrap_ansi(line, value_width) } else { vec![line.to_string()] }
+            })
+            .collect();
+        if values.is_empty() { values.push(String::new()); }
+        for (i, value) in values.iter().enumerate() {
+            let lead = if i == 0 { key.clone() } else { " ".repeat(key_width) };
+            lines.push(format!("{}{}{}", lead, gap, value).trim_end().to_string());
+        }
+    }
+    lines
+}
+
+fn render_boxed(inner: &Panel, border: &BorderStyle, title: Option<&str>, width: usize, ctx: &Ctx) -> Vec<String> {
+    let chars = BoxChars::from_style(&ctx.log.border(*border));
+    let span = width.max(5) - 2;
+    let inner_width = span - 2;
+
+    let edge = match title {
+        Some(title) => {
+            let title = truncate_ansi(&format!(" {} ", title), span.saturating_sub(1));
+            let rest = span - 1 - visible_width(&title);
+            format!("{}{}{}", chars.horizontal, title, chars.horizontal.repeat(rest))
+        }
+        None => chars.horizontal.repeat(span),
+    };
+    let mut lines = vec![ctx.paint(&format!("{}{}{}", chars.top_left, edge, chars.top_right))];
+    let side = ctx.paint(chars.vertical);
+    for line in inner.render(inner_width, ctx) {
+        lines.push(format!("{} {} {}", side, fit(&line, inner_width), side));
+    }
+    lines.push(ctx.paint(&format!("{}{}{}", chars.bottom_left, chars.horizontal.repeat(span), chars.bottom_right)));
+    lines
+}
+
+// Columns each of `count` panels in a row gets
+fn row_share(width: usize, count: usize) -> usize {
+    width.saturating_sub(GAP * count.saturating_sub(1)) / count.max(1)
+}
+
+fn render_row(panels: &[Panel], width: usize, ctx: &Ctx) -> Vec<String> {
+    let count = panels.len();
+    let share = row_share(width, count);
+    let widths: Vec<usize> = (0..count)
+        .map(|i| if i + 1 == count { width.saturating_sub((share + GAP) * (count - 1)) } else { share })
+        .collect();
+    let columns: Vec<Vec<String>> = panels.iter().zip(&widths).map(|(panel, &w)| panel.render(w, ctx)).collect();
+    let height = columns.iter().map(Vec::len).max().unwrap_or(0);
+    let gap = " ".repeat(GAP);
+
+    (0..height)
+        .map(|row| {
+            let cells: Vec<String> = columns
+                .iter()
+                .zip(&widths)
+                .map(|(lines, &w)| fit(lines.get(row).map(String::as_str).unwrap_or(""), w))
+                .collect();
+            cells.join(&gap).trim_end().to_string()
+        })
+        .collect()
+}
+
+// Pads (or cuts) a line to exactly `width` visible columns
+pub(crate) fn fit(line: &str, width: usize) -> String {
+    let visible = visible_width(line);
+    if visible > width {
+        truncate_ansi(line, width)
+    } else {
+        format!("{}{}", line, " ".repeat(width - visible))
+    }
+}
+
+impl Stderr {
+    /// Draws a [`Panel`] layout at the layout width
+    pub fn panel(&mut self, panel: &Panel) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let ansi = self.writer.supports_color() && !self.is_plain();
+        let lines = self.panel_lines(panel, ansi);
+        self.write_lines("panel", &lines)
+    }
+
+    /// Prints key/value pairs as a status panel: keys right-aligned in the
+    /// table header style, values beside them
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.kv(&[("Version", "1.2"), ("Context", "@myapp"), ("Keys", "42")]).unwrap();
+    /// //  Version  1.2
+    /// //  Context  @myapp
+    /// //     Keys  42
+    /// ```
+    pub fn kv(&mut self, pairs: &[(&str, &str)]) -> io::Result<()> {
+        self.panel(&Panel::kv(pairs.iter().copied()))
+    }
+
+    /// Like [`kv`](Self::kv), inside a border
+    pub fn kv_boxed(&mut self, pairs: &[(&str, &str)], border: BorderStyle) -> io::Result<()> {
+        self.panel(&Panel::kv(pairs.iter().copied()).boxed(border))
+    }
+
+    /// Renders a [`Panel`] layout to a `String` instead of the stream
+    pub fn render_panel(&self, panel: &Panel, ansi: bool) -> String {
+        let ansi = ansi && !self.is_plain();
+        self.panel_lines(panel, ansi).iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn panel_lines(&self, panel: &Panel, ansi: bool) -> Vec<String> {
+        let ctx = Ctx { log: self, ansi };
+        panel.render(self.width.layout(), &ctx)
+    }
+}
@@ -0,0 +1,114 @@
+//! OpenTelemetry export: log records through a `LoggerProvider`, trace
+//! scopes as spans through the global tracer provider
+//!
+//! Nothing here installs an SDK; the application sets up its providers and
+//! exporters as usual and hands them to the logger.
+
+use opentelemetry::logs::{AnyValue, LogRecord, Logger, LoggerProvider, Severity};
+#[cfg(feature = "trace")]
+use opentelemetry::trace::{TraceContextExt, Tracer};
+#[cfg(feature = "trace")]
+use opentelemetry::{global, Context, ContextGuard, KeyValue};
+
+use super::sinks::{Record, Sink};
+use super::stderr::LogLevel;
+
+const SCOPE_NAME: &str = "rdx-stderr";
+
+/// Exports every message as an OpenTelemetry log record.
+///
+/// Records carry `stderr.level`, `stderr.label` and `stderr.context`
+/// attributes, and pick up the active trace context, so messages logged
+/// inside a `trace_scope` are correlated with its span.
+///
+/// ```
+/// use opentelemetry::logs::NoopLoggerProvider;
+/// use stderr::{OtelSink, Stderr};
+///
+/// // normally an `opentelemetry_sdk::logs::SdkLoggerProvider` with an exporter
+/// let provider = NoopLoggerProvider::new();
+///
+/// let mut log = Stderr::new();
+/// log.add_otel(OtelSink::new(&provider));
+/// log.info("exported as an INFO log record");
+/// ```
+pub struct OtelSink {
+    emit: Box<dyn Fn(&Record) + Send>,
+}
+
+impl OtelSink {
+    pub fn new<P>(provider: &P) -> Self
+    where
+        P: LoggerProvider,
+        P::Logger: Send + 'static,
+    {
+        let logger = provider.logger(SCOPE_NAME);
+        Self {
+            emit: Box::new(move |record| {
+                let mut otel = logger.create_log_record();
+                let (severity, text) = severity(record.level);
+                otel.set_timestamp(std::time::SystemTime::now());
+                otel.set_severity_number(severity);
+                otel.set_severity_text(text);
+                otel.set_body(AnyValue::from(crate::utils::text::strip_ansi(record.msg)));
+                otel.add_attribute("stderr.level", record.level.to_string());
+                if let Some(label) = record.label {
+                    otel.add_attribute("stderr.label", label.to_string());
+                }
+                if let Some(context) = record.context {
+                    otel.add_attribute("stderr.context", context.to_string());
+                }
+                logger.emit(otel);
+            }),
+        }
+    }
+
+}
+
+impl Sink for OtelSink {
+    fn emit(&mut self, record: &Record) {
+        (self.emit)(record);
+    }
+}
+
+fn severity(level: LogLevel) -> (Severity, &'static str) {
+    match level {
+        LogLevel::Error => (Severity::Error, "ERROR"),
+        LogLevel::Warn => (Severity::Warn, "WARN"),
+        LogLevel::Okay | LogLevel::Info | LogLevel::Note => (Severity::Info, "INFO"),
+        LogLevel::Debug | LogLevel::DevLog => (Severity::Debug, "DEBUG"),
+        LogLevel::Trace | LogLevel::Magic | LogLevel::Silly => (Severity::Trace, "TRACE"),
+    }
+}
+
+/// The span behind a trace scope. It becomes the current context while the
+/// scope lives, so nested scopes on the same thread are its children.
+#[cfg(feature = "trace")]
+pub(crate) struct ScopeSpan {
+    cx: Context,
+    _guard: ContextGuard,
+}
+
+#[cfg(feature = "trace")]
+impl ScopeSpan {
+    pub(crate) fn start(name: &str) -> Self {
+        let span = global::tracer(SCOPE_NAME).start(name.to_string());
+        let cx = Context::current_with_span(span);
+        let guard = cx.clone().attach();
+        Self { cx, _guard: guard }
+    }
+
+    /// Records a scope step as a span event, with a `step_debug` value's
+    /// `{:?}` as its `stderr.value` attribute
+    pub(crate) fn event(&self, msg: &str, value: Option<String>) {
+        let attributes = value.map(|value| KeyValue::new("stderr.value", value)).into_iter().collect();
+        self.cx.span().add_event(msg.to_string(), attributes);
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for ScopeSpan {
+    fn drop(&mut self) {
+        self.cx.span().end();
+    }
+}
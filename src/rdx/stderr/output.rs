@@ -0,0 +1,323 @@
+//! Buffered output stream behind every `Stderr` write
+//!
+//! All writes land in a `termcolor::Buffer` first and are handed to the
+//! terminal in one call according to the `FlushPolicy`, so big tables no
+//! longer turn into hundreds of tiny unbuffered writes.
+//!
+//! Automatic flushes only ever happen at a line boundary, with colors
+//! reset, and each one is a single write under the stderr lock. Lines from
+//! different loggers or threads therefore never interleave mid-line.
+//!
+//! The destination is normally the process's stderr, but any boxed
+//! `WriteColor` can take its place (see `StderrBuilder::target` and
+//! `Stderr::with_writer`).
+
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::sync::{Arc, Mutex, PoisonError};
+use termcolor::{Buffer, BufferWriter, ColorChoice, ColorSpec, WriteColor};
+use crate::esc::colors::Color;
+
+/// When buffered output is handed to the terminal
+///
+/// The global `logger` is never dropped, so with `Bytes` or `Manual` its
+/// last lines only reach the terminal through `logger.flush()` or a
+/// `logger.flush_guard()` held in `main`. `fatal` flushes before exiting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FlushPolicy {
+    /// Flush once a complete line (including its color reset) is buffered
+    #[default]
+    Line,
+    /// Flush once at least this many bytes are buffered, at the next line end
+    Bytes(usize),
+    /// Only flush on `Stderr::flush()` (or when the logger is dropped)
+    Manual,
+}
+
+// Where buffered output ends up
+enum Target {
+    Stderr(BufferWriter),
+    // Receives the buffer's bytes as-is: ANSI escapes when colored
+    Custom(Box<dyn WriteColor + Send>),
+}
+
+/// The buffered writer used by `Stderr`
+pub(crate) struct Output {
+    sink: Target,
+    buffer: Buffer,
+    policy: FlushPolicy,
+    colored: bool,
+    // RGB colors pass through; otherwise they become their nearest 256-color entry
+    truecolor: bool,
+    // Open `Stderr::batch` calls; while non-zero nothing is handed over
+    held: u32,
+    // Whether the last byte written ended a line
+    line_start: bool,
+    // Set while a JSON-mode block is being collected as plain text
+    capture: Option<Vec<u8>>,
+    // Spaces put in front of every line written (see `set_indent`)
+    indent: usize,
+}
+
+impl Output {
+    /// Creates a buffered writer targeting the process's stderr.
+    pub(crate) fn stderr(choice: ColorChoice) -> Self {
+        let sink = BufferWriter::stderr(stderr_choice(choice));
+        let buffer = sink.buffer();
+        Self {
+            sink: Target::Stderr(sink),
+            buffer,
+            policy: FlushPolicy::default(),
+            colored: false,
+            truecolor: Color::supports_truecolor(),
+            held: 0,
+            line_start: true,
+            capture: None,
+            indent: 0,
+        }
+    }
+
+    /// Creates a buffered writer targeting `writer`. Colors are written as
+    /// ANSI escapes when `choice` allows it and the writer supports color.
+    pub(crate) fn custom(writer: Box<dyn WriteColor + Send>, choice: ColorChoice) -> Self {
+        Self {
+            buffer: custom_buffer(writer.as_ref(), choice),
+            sink: Target::Custom(writer),
+            policy: FlushPolicy::default(),
+            colored: false,
+            truecolor: Color::supports_truecolor(),
+            held: 0,
+            line_start: true,
+            capture: None,
+            indent: 0,
+        }
+    }
+
+    /// Switches color handling, flushing anything buffered under the old choice first.
+    pub(crate) fn set_color_choice(&mut self, choice: ColorChoice) {
+        let _ = self.write_out();
+        self.buffer = match &mut self.sink {
+            Target::Stderr(sink) => {
+                *sink = BufferWriter::stderr(stderr_choice(choice));
+                sink.buffer()
+            }
+            Target::Custom(writer) => custom_buffer(writer.as_ref(), choice),
+        };
+    }
+
+    /// Whether output goes to the process's stderr and that is a terminal
+    pub(crate) fn is_terminal(&self) -> bool {
+        matches!(self.sink, Target::Stderr(_)) && io::stderr().is_terminal()
+    }
+
+    pub(crate) fn set_truecolor(&mut self, on: bool) {
+        self.truecolor = on;
+    }
+
+    pub(crate) fn truecolor(&self) -> bool {
+        self.truecolor
+    }
+
+    /// Whether nothing has been written on the current line yet
+    pub(crate) fn at_line_start(&self) -> bool {
+        self.line_start
+    }
+
+    /// Records that the cursor went back to the start of a line (e.g.
+    /// after `\r` and clearing it)
+    pub(crate) fn mark_line_start(&mut self) {
+        self.line_start = true;
+    }
+
+    /// Indents every line started from now on by `columns` spaces; blank
+    /// lines stay empty
+    pub(crate) fn set_indent(&mut self, columns: usize) {
+        self.indent = columns;
+    }
+
+    /// Starts collecting writes as plain text instead of sending them on
+    pub(crate) fn start_capture(&mut self) {
+        self.capture = Some(Vec::new());
+    }
+
+    /// Stops collecting and returns what was written since `start_capture`
+    pub(crate) fn end_capture(&mut self) -> String {
+        self.capture.take().map(|bytes| String::from_utf8_lossy(&bytes).into_owned()).unwrap_or_default()
+    }
+
+    pub(crate) fn policy(&self) -> FlushPolicy {
+        self.policy
+    }
+
+    pub(crate) fn set_policy(&mut self, policy: FlushPolicy) {
+        self.policy = policy;
+    }
+
+    /// Starts holding all output, explicit flushes included
+    pub(crate) fn hold(&mut self) {
+        self.held += 1;
+    }
+
+    /// Ends one `hold`; the outermost one writes everything in one call
+    pub(crate) fn release(&mut self) -> io::Result<()> {
+        self.held = self.held.saturating_sub(1);
+        self.flush()
+    }
+
+    fn write_out(&mut self) -> io::Result<()> {
+        if !self.buffer.is_empty() {
+            match &mut self.sink {
+                Target::Stderr(sink) => sink.print(&self.buffer)?,
+                Target::Custom(writer) => {
+                    writer.write_all(self.buffer.as_slice())?;
+                    writer.flush()?;
+                }
+            }
+            self.buffer.clear();
+        }
+        Ok(())
+    }
+
+    fn flush_if_due(&mut self) -> io::Result<()> {
+        // Hold a colored line until its reset, and never hand over half a
+        // line, so the whole line goes out in one write
+        let at_line_end = !self.colored && self.buffer.as_slice().ends_with(b"\n");
+        let due = match self.policy {
+            FlushPolicy::Line => at_line_end,
+            FlushPolicy::Bytes(limit) => at_line_end && self.buffer.len() >= limit,
+            FlushPolicy::Manual => false,
+        };
+        if due { self.flush() } else { Ok(()) }
+    }
+}
+
+impl Write for Output {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        if let Some(capture) = &mut self.capture {
+            capture.extend_from_slice(buf);
+            return Ok(buf.len());
+        }
+        let written = if self.indent == 0 {
+            self.buffer.write(buf)?
+        } else {
+            for line in buf.split_inclusive(|&b| b == b'\n') {
+                if self.line_start && line != b"\n" {
+                    write!(self.buffer, "{:1$}", "", self.indent)?;
+                }
+                self.buffer.write_all(line)?;
+                self.line_start = line.ends_with(b"\n");
+            }
+            buf.len()
+        };
+        if let Some(&last) = buf[..written].last() {
+            self.line_start = last == b'\n';
+        }
+        self.flush_if_due()?;
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        if self.held > 0 { return Ok(()); }
+        self.write_out()
+    }
+}
+
+impl WriteColor for Output {
+    fn supports_color(&self) -> bool {
+        self.capture.is_none() && self.buffer.supports_color()
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> io::Result<()> {
+        if self.capture.is_some() { return Ok(()); }
+        self.colored = !spec.is_none();
+        if self.truecolor || !has_rgb(spec) {
+            return self.buffer.set_color(spec);
+        }
+        let mut spec = spec.clone();
+        spec.set_fg(spec.fg().map(|&c| Color::downgrade(c, false)));
+        spec.set_bg(spec.bg().map(|&c| Color::downgrade(c, false)));
+        self.buffer.set_color(&spec)
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        if self.capture.is_some() { return Ok(()); }
+        self.colored = false;
+        self.buffer.reset()?;
+        self.flush_if_due()
+    }
+}
+
+/// A custom destination held by a `StderrBuilder`; clones of the builder
+/// (and every logger they build) write to the same place
+#[derive(Clone)]
+pub(crate) struct SharedTarget(Arc<Mutex<Box<dyn WriteColor + Send>>>);
+
+impl SharedTarget {
+    pub(crate) fn new(writer: impl WriteColor + Send + 'static) -> Self {
+        Self(Arc::new(Mutex::new(Box::new(writer))))
+    }
+
+    fn with<R>(&self, f: impl FnOnce(&mut Box<dyn WriteColor + Send>) -> R) -> R {
+        f(&mut self.0.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+}
+
+impl fmt::Debug for SharedTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("SharedTarget")
+    }
+}
+
+impl Write for SharedTarget {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.with(|w| w.write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.with(|w| w.flush())
+    }
+}
+
+impl WriteColor for SharedTarget {
+    fn supports_color(&self) -> bool {
+        self.with(|w| w.supports_color())
+    }
+
+    fn set_color(&mut self, spec: &ColorSpec) -> io::Result<()> {
+        self.with(|w| w.set_color(spec))
+    }
+
+    fn reset(&mut self) -> io::Result<()> {
+        self.with(|w| w.reset())
+    }
+}
+
+// termcolor's `Auto` only looks at `TERM` and `NO_COLOR`; also require a
+// terminal, so piped output stays free of escape codes
+fn stderr_choice(choice: ColorChoice) -> ColorChoice {
+    if choice == ColorChoice::Auto && !io::stderr().is_terminal() {
+        ColorChoice::Never
+    } else {
+        choice
+    }
+}
+
+fn has_rgb(spec: &ColorSpec) -> bool {
+    let rgb = |c: Option<&termcolor::Color>| matches!(c, Some(termcolor::Color::Rgb(..)));
+    rgb(spec.fg()) || rgb(spec.bg())
+}
+
+fn custom_buffer(writer: &(dyn WriteColor + Send), choice: ColorChoice) -> Buffer {
+    let colored = match choice {
+        ColorChoice::Never => false,
+        ColorChoice::Always | ColorChoice::AlwaysAnsi => true,
+        ColorChoice::Auto => writer.supports_color(),
+    };
+    if colored { Buffer::ansi() } else { Buffer::no_color() }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        let _ = self.write_out(); // even inside a batch that panicked
+    }
+}
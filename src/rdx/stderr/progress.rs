@@ -0,0 +1,216 @@
+//! `MultiProgress`: several progress bars and spinners drawn as one block
+//! in the logger's status region
+//!
+//! Because the block lives in the status region, messages logged through
+//! the same logger (the global `logger` by default) are printed above it
+//! and the block is redrawn underneath, so the live region never tears.
+
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+use super::handle::StderrHandle;
+use super::static_logger::LOGGER;
+use super::stderr::Stderr;
+use crate::utils::ansi::visible_width;
+use crate::utils::helpers::repeat_char;
+
+const SPINNER: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+// Plain output and non-Unicode terminals
+const ASCII_SPINNER: [&str; 4] = ["|", "/", "-", "\\"];
+const BAR_WIDTH: usize = 30;
+
+/// Coordinates a set of bars and spinners, redrawn together on every update.
+///
+/// Clones share the same block, and each [`ProgressBar`] handle can be
+/// moved into its own thread.
+///
+/// ```
+/// use stderr::{logger, MultiProgress};
+///
+/// let multi = MultiProgress::new();
+/// let download = multi.add_bar("download", 100);
+/// let index = multi.add_spinner("index");
+///
+/// for _ in 0..10 {
+///     download.inc(10);
+///     index.tick();
+/// }
+/// logger.info("printed above the bars");
+/// download.finish("done");
+/// index.finish("42 files");
+/// ```
+#[derive(Clone)]
+pub struct MultiProgress {
+    inner: Arc<Mutex<State>>,
+}
+
+struct State {
+    target: Target,
+    // Slots stay put so `ProgressBar` indices remain valid; finished bars are `None`
+    bars: Vec<Option<Bar>>,
+}
+
+enum Target {
+    Global,
+    Shared(StderrHandle),
+}
+
+impl Target {
+    fn with<R>(&self, f: impl FnOnce(&mut Stderr) -> R) -> R {
+        match self {
+            Target::Global => f(&mut LOGGER.raw()),
+            Target::Shared(handle) => handle.with(f),
+        }
+    }
+}
+
+struct Bar {
+    name: String,
+    // `None` for spinners
+    len: Option<u64>,
+    pos: u64,
+    msg: String,
+    frame: usize,
+}
+
+impl Bar {
+    fn render(&self, name_width: usize, ascii: bool) -> String {
+        let pad = " ".repeat(name_width.saturating_sub(visible_width(&self.name)));
+        let mut line = match self.len {
+            Some(len) => {
+                let filled = (self.pos.min(len) * BAR_WIDTH as u64).checked_div(len).map_or(BAR_WIDTH, |n| n as usize);
+                let (done, todo) = if ascii { ('#', '-') } else { ('█', '░') };
+                format!(
+                    "{}{} [{}{}] {}/{}",
+                    self.name,
+                    pad,
+                    repeat_char(done, filled),
+                    repeat_char(todo, BAR_WIDTH - filled),
+                    self.pos.min(len),
+                    len,
+                )
+            }
+            None => {
+                let frames: &[&str] = if ascii { &ASCII_SPINNER } else { &SPINNER };
+                format!("{}{} {}", self.name, pad, frames[self.frame % frames.len()])
+            }
+        };
+        if !self.msg.is_empty() {
+            line.push(' ');
+            line.push_str(&self.msg);
+        }
+        line
+    }
+}
+
+impl Default for MultiProgress {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MultiProgress {
+    /// A block drawn through the global logger
+    pub fn new() -> Self {
+        Self::with_target(Target::Global)
+    }
+
+    /// A block drawn through a shared logger instead of the global one
+    pub fn with_handle(handle: StderrHandle) -> Self {
+        Self::with_target(Target::Shared(handle))
+    }
+
+    fn with_target(target: Target) -> Self {
+        Self { inner: Arc::new(Mutex::new(State { target, bars: Vec::new() })) }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.inner.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// Adds a bar counting up to `len`
+    pub fn add_bar(&self, name: impl Into<String>, len: u64) -> ProgressBar {
+        self.add(Bar { name: name.into(), len: Some(len), pos: 0, msg: String::new(), frame: 0 })
+    }
+
+    /// Adds a spinner, advanced with [`ProgressBar::tick`]
+    pub fn add_spinner(&self, name: impl Into<String>) -> ProgressBar {
+        self.add(Bar { name: name.into(), len: None, pos: 0, msg: String::new(), frame: 0 })
+    }
+
+    fn add(&self, bar: Bar) -> ProgressBar {
+        let mut state = self.lock();
+        state.bars.push(Some(bar));
+        let index = state.bars.len() - 1;
+        redraw(&state, None);
+        ProgressBar { multi: self.clone(), index }
+    }
+
+    /// Removes every bar and the block with them
+    pub fn clear(&self) {
+        let mut state = self.lock();
+        state.bars.clear();
+        state.target.with(Stderr::clear_status);
+    }
+
+    fn update(&self, index: usize, f: impl FnOnce(&mut Bar)) {
+        let mut state = self.lock();
+        let Some(Some(bar)) = state.bars.get_mut(index) else { return };
+        f(bar);
+        redraw(&state, None);
+    }
+}
+
+// Draws the block, printing `finished` above it first
+fn redraw(state: &State, finished: Option<&str>) {
+    // Bars line up after the longest name
+    let name_width = state.bars.iter().flatten().map(|bar| visible_width(&bar.name)).max().unwrap_or(0);
+    state.target.with(|log| {
+        let ascii = log.ascii_only();
+        let block: Vec<String> = state.bars.iter().flatten().map(|bar| bar.render(name_width, ascii)).collect();
+        if let Some(msg) = finished {
+            // Dropping the old block first keeps the finished bar from flashing back
+            log.clear_status();
+            log.okay(msg);
+        }
+        if block.is_empty() {
+            log.clear_status();
+        } else {
+            log.set_status(&block.join("\n"));
+        }
+    });
+}
+
+/// One bar or spinner in a [`MultiProgress`]; cheap to clone and `Send`
+#[derive(Clone)]
+pub struct ProgressBar {
+    multi: MultiProgress,
+    index: usize,
+}
+
+impl ProgressBar {
+    pub fn set_position(&self, pos: u64) {
+        self.multi.update(self.index, |bar| bar.pos = pos);
+    }
+
+    pub fn inc(&self, delta: u64) {
+        self.multi.update(self.index, |bar| bar.pos = bar.pos.saturating_add(delta));
+    }
+
+    /// Text shown after the bar
+    pub fn set_message(&self, msg: impl Into<String>) {
+        let msg = msg.into();
+        self.multi.update(self.index, |bar| bar.msg = msg);
+    }
+
+    /// Advances a spinner by one frame
+    pub fn tick(&self) {
+        self.multi.update(self.index, |bar| bar.frame = bar.frame.wrapping_add(1));
+    }
+
+    /// Removes the bar and logs `name msg` as an `okay` line above the block
+    pub fn finish(&self, msg: impl std::fmt::Display) {
+        let mut state = self.multi.lock();
+        let Some(bar) = state.bars.get_mut(self.index).and_then(Option::take) else { return };
+        redraw(&state, Some(&format!("{} {}", bar.name, msg)));
+    }
+}
@@ -0,0 +1,198 @@
+//! The `Sink` trait and the built-in sinks (syslog, journald)
+//!
+//! Sinks get the plain message, never the glyphs or prefix; the built-in
+//! ones strip ANSI codes, map the level to a syslog severity and carry the
+//! label / context along.
+
+use std::io;
+#[cfg(all(unix, any(feature = "syslog", feature = "journald")))]
+use std::os::unix::net::UnixDatagram;
+
+use super::stderr::{LevelMask, LogLevel};
+
+/// A message as handed to a [`Sink`]
+#[derive(Debug, Clone, Copy)]
+pub struct Record<'a> {
+    pub level: LogLevel,
+    /// The message text, as passed to the logger (may contain ANSI codes)
+    pub msg: &'a str,
+    pub label: Option<&'a str>,
+    pub context: Option<&'a str>,
+}
+
+/// A destination that receives messages alongside the terminal.
+///
+/// Register sinks with `Stderr::add_sink` (same levels as the terminal) or
+/// `Stderr::add_sink_filtered` (its own `LevelMask`, so e.g. a file can get
+/// debug output that the terminal hides).
+///
+/// ```
+/// use stderr::{LevelMask, LogLevel, Record, Sink, Stderr};
+///
+/// struct Collect(std::sync::Arc<std::sync::Mutex<Vec<String>>>);
+///
+/// impl Sink for Collect {
+///     fn emit(&mut self, record: &Record) {
+///         self.0.lock().unwrap().push(format!("{}: {}", record.level, record.msg));
+///     }
+/// }
+///
+/// let lines = std::sync::Arc::default();
+/// let mut log = Stderr::builder().quiet(true).build();
+/// log.add_sink_filtered(Collect(std::sync::Arc::clone(&lines)), LevelMask::ALL);
+/// log.warn("quiet terminal, but the sink still gets it");
+/// assert_eq!(lines.lock().unwrap()[0], "warn: quiet terminal, but the sink still gets it");
+/// ```
+pub trait Sink: Send {
+    fn emit(&mut self, record: &Record);
+
+    /// Called by `Stderr::flush`
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// A registered sink and its level filter (`None` follows the logger's own levels)
+pub(crate) struct SinkEntry {
+    pub sink: Box<dyn Sink>,
+    pub levels: Option<LevelMask>,
+}
+
+impl SinkEntry {
+    pub(crate) fn wants(&self, level: LogLevel, logger_levels: LevelMask) -> bool {
+        self.levels.unwrap_or(logger_levels).contains(level)
+    }
+}
+
+/// Syslog severity for a level (RFC 5424 numbering)
+pub fn syslog_severity(level: LogLevel) -> u8 {
+    match level {
+        LogLevel::Error => 3,
+        LogLevel::Warn => 4,
+        LogLevel::Okay | LogLevel::Note => 5,
+        LogLevel::Info => 6,
+        LogLevel::Debug | LogLevel::DevLog | LogLevel::Trace | LogLevel::Magic | LogLevel::Silly => 7,
+    }
+}
+
+/// Forwards messages to the local syslog daemon over `/dev/log`.
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use stderr::{Stderr, SyslogSink};
+///
+/// let mut log = Stderr::new();
+/// log.add_syslog(SyslogSink::new("myapp")?);
+/// log.warn("disk almost full"); // terminal + syslog (severity 4)
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(all(unix, feature = "syslog"))]
+pub struct SyslogSink {
+    socket: UnixDatagram,
+    ident: String,
+    facility: u8,
+}
+
+#[cfg(all(unix, feature = "syslog"))]
+impl SyslogSink {
+    /// Connects to the system log socket with the `user` facility
+    pub fn new(ident: impl Into<String>) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect("/dev/log").or_else(|_| socket.connect("/var/run/syslog"))?;
+        Ok(Self { socket, ident: ident.into(), facility: 1 })
+    }
+
+    /// Sets the syslog facility code (e.g. 3 = daemon, 16..=23 = local0..local7)
+    pub fn with_facility(mut self, facility: u8) -> Self {
+        self.facility = facility;
+        self
+    }
+
+    fn send(&self, record: &Record) -> io::Result<()> {
+        let pri = u32::from(self.facility) * 8 + u32::from(syslog_severity(record.level));
+        let msg = crate::utils::text::strip_ansi(record.msg);
+        let mut line = format!("<{}>{}[{}]: ", pri, self.ident, std::process::id());
+        if let Some(label) = record.label {
+            line.push_str(&format!("[{}] ", label));
+        }
+        if let Some(context) = record.context {
+            line.push_str(&format!("({}) ", context));
+        }
+        line.push_str(&msg);
+        self.socket.send(line.as_bytes()).map(|_| ())
+    }
+}
+
+/// Forwards messages to systemd-journald using its native protocol, with
+/// structured fields (`PRIORITY`, `SYSLOG_IDENTIFIER`, `STDERR_LEVEL`,
+/// `STDERR_LABEL`, `STDERR_CONTEXT`).
+///
+/// ```no_run
+/// # fn main() -> std::io::Result<()> {
+/// use stderr::{Stderr, JournaldSink};
+///
+/// let mut log = Stderr::new().with_label("worker-3");
+/// log.add_journald(JournaldSink::new("myapp")?);
+/// log.error("job failed"); // journalctl -t myapp STDERR_LABEL=worker-3
+/// # Ok(())
+/// # }
+/// ```
+#[cfg(all(unix, feature = "journald"))]
+pub struct JournaldSink {
+    socket: UnixDatagram,
+    identifier: String,
+}
+
+#[cfg(all(unix, feature = "journald"))]
+impl JournaldSink {
+    pub fn new(identifier: impl Into<String>) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        socket.connect("/run/systemd/journal/socket")?;
+        Ok(Self { socket, identifier: identifier.into() })
+    }
+
+    fn send(&self, record: &Record) -> io::Result<()> {
+        let mut payload = Vec::new();
+        let msg = crate::utils::text::strip_ansi(record.msg);
+        journal_field(&mut payload, "MESSAGE", &msg);
+        journal_field(&mut payload, "PRIORITY", &syslog_severity(record.level).to_string());
+        journal_field(&mut payload, "SYSLOG_IDENTIFIER", &self.identifier);
+        journal_field(&mut payload, "STDERR_LEVEL", &record.level.to_string());
+        if let Some(label) = record.label {
+            journal_field(&mut payload, "STDERR_LABEL", label);
+        }
+        if let Some(context) = record.context {
+            journal_field(&mut payload, "STDERR_CONTEXT", context);
+        }
+        self.socket.send(&payload).map(|_| ())
+    }
+}
+
+#[cfg(all(unix, feature = "syslog"))]
+impl Sink for SyslogSink {
+    fn emit(&mut self, record: &Record) {
+        let _ = self.send(record); // a missing syslog daemon must not break logging
+    }
+}
+
+#[cfg(all(unix, feature = "journald"))]
+impl Sink for JournaldSink {
+    fn emit(&mut self, record: &Record) {
+        let _ = self.send(record);
+    }
+}
+
+// Multi-line values use the length-prefixed binary form of the protocol
+#[cfg(all(unix, feature = "journald"))]
+fn journal_field(out: &mut Vec<u8>, key: &str, value: &str) {
+    out.extend_from_slice(key.as_bytes());
+    if value.contains('\n') {
+        out.push(b'\n');
+        out.extend_from_slice(&(value.len() as u64).to_le_bytes());
+    } else {
+        out.push(b'=');
+    }
+    out.extend_from_slice(value.as_bytes());
+    out.push(b'\n');
+}
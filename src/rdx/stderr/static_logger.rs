@@ -1,32 +1,514 @@
-use std::sync::{Mutex, MutexGuard};
+//! The global `logger` and its locking rules
+//!
+//! Locking guarantees (relied on by the `q*` macros):
+//!
+//! - Every `StaticLogger` method and macro holds the lock for one call only;
+//!   message arguments are evaluated *before* the lock is taken.
+//! - A thread that logs while it already holds the lock (a `Drop` or `Debug`
+//!   impl firing inside a `logger.raw()` block) does not deadlock: the nested
+//!   call writes through a temporary logger with a snapshot of the config.
+//! - A panic while logging does not poison the logger for everyone else.
+
+use std::cell::RefCell;
+use std::fmt::Display;
+use std::ops::{Deref, DerefMut};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
 use once_cell::sync::Lazy;
 
-use crate::Stderr; 
+use crate::{LogLevel, Stderr, StderrConfig};
 
 static LOGGER_: Lazy<Mutex<Stderr>> = Lazy::new(|| Mutex::new(Stderr::new()));
 
+// Bitmask of enabled levels (bit = `LogLevel as u32`), refreshed each time the
+// lock is released so `logger.enabled()` never has to lock.
+static ENABLED: AtomicU32 = AtomicU32::new(0);
+const ENABLED_READY: u32 = 1 << 31;
+
+fn enabled_mask(log: &Stderr) -> u32 {
+    LogLevel::ALL.iter()
+        .filter(|&&level| log.is_enabled(level))
+        .fold(ENABLED_READY, |mask, &level| mask | (1 << level as u32))
+}
+
+thread_local! {
+    // Per-thread label layered over the shared logger (see `StaticLogger::set_scope`)
+    static THREAD_SCOPE: RefCell<Option<String>> = const { RefCell::new(None) };
+
+    // Config snapshot while this thread holds the lock; `Some` marks it as held
+    static HELD_CONFIG: RefCell<Option<StderrConfig>> = const { RefCell::new(None) };
+}
+
+/// Applies `config` to the global logger.
+///
+/// Call this early in `main` (e.g. right after parsing CLI flags) so the
+/// static logger and the `q*` macros use the app's settings instead of the
+/// env-var defaults. Calling it again simply replaces the configuration.
+pub fn init(config: StderrConfig) {
+    LOGGER.raw().set_config(config);
+}
+
 pub struct StaticLogger;
 
 impl StaticLogger {
-    pub fn info(&self, msg: &str) {
-        LOGGER_.lock().unwrap().info(msg);
+    /// Same as the free-standing [`init`].
+    pub fn init(&self, config: StderrConfig) {
+        init(config);
+    }
+
+    /// Adjusts the global logger's configuration in place.
+    ///
+    /// ```
+    /// stderr::logger.configure(|cfg| {
+    ///     cfg.enable(stderr::LogLevel::Trace);
+    ///     cfg.quiet = false;
+    /// });
+    /// ```
+    pub fn configure<F>(&self, f: F)
+    where
+        F: FnOnce(&mut StderrConfig),
+    {
+        let mut log = self.raw();
+        let mut config = log.config.clone();
+        f(&mut config);
+        log.set_config(config);
+    }
+
+    /// Shows every level up to `max` in verbosity; see [`Stderr::set_level`]
+    pub fn set_level(&self, max: LogLevel) {
+        self.raw().set_level(max);
+    }
+
+    /// Sets a label for the calling thread only.
+    ///
+    /// While set, every message this thread logs through the static logger
+    /// uses the scope as its label; other threads keep their own scope (or
+    /// the shared label).
+    pub fn set_scope(&self, scope: impl Into<String>) {
+        let scope = scope.into();
+        THREAD_SCOPE.with(|s| *s.borrow_mut() = Some(scope));
+    }
+
+    /// Removes the calling thread's scope.
+    pub fn clear_scope(&self) {
+        THREAD_SCOPE.with(|s| *s.borrow_mut() = None);
+    }
+
+    /// Returns the calling thread's scope, if any.
+    pub fn scope(&self) -> Option<String> {
+        THREAD_SCOPE.with(|s| s.borrow().clone())
+    }
+
+    /// Whether a message at `level` would currently be printed.
+    ///
+    /// This is a single atomic load, so macros use it to skip formatting
+    /// disabled messages entirely.
+    pub fn enabled(&self, level: LogLevel) -> bool {
+        let mut mask = ENABLED.load(Ordering::Relaxed);
+        if mask & ENABLED_READY == 0 {
+            drop(self.raw()); // first use: build the logger, which publishes the mask
+            mask = ENABLED.load(Ordering::Relaxed);
+        }
+        mask & (1 << level as u32) != 0
+    }
+
+    pub fn info(&self, msg: impl Display) {
+        self.raw().info(msg);
+    }
+
+    #[track_caller]
+    pub fn warn(&self, msg: impl Display) {
+        self.raw().warn(msg);
+    }
+
+    #[track_caller]
+    pub fn error(&self, msg: impl Display) {
+        self.raw().error(msg);
+    }
+
+    pub fn okay(&self, msg: impl Display) {
+        self.raw().okay(msg);
+    }
+
+    pub fn note(&self, msg: impl Display) {
+        self.raw().note(msg);
+    }
+
+    /// Logs a message with inline markup; see [`Stderr::log_md`]
+    pub fn log_md(&self, level: LogLevel, markup: &str) {
+        self.raw().log_md(level, markup);
     }
 
-    pub fn warn(&self, msg: &str) {
-        LOGGER_.lock().unwrap().warn(msg);
+    pub fn info_md(&self, markup: &str) {
+        self.raw().info_md(markup);
     }
 
-    pub fn error(&self, msg: &str) {
-        LOGGER_.lock().unwrap().error(msg);
+    pub fn warn_md(&self, markup: &str) {
+        self.raw().warn_md(markup);
     }
 
-    pub fn okay(&self, msg: &str) {
-        LOGGER_.lock().unwrap().okay(msg);
+    pub fn error_md(&self, markup: &str) {
+        self.raw().error_md(markup);
     }
 
-    pub fn raw(&self) -> MutexGuard<'static, Stderr> {
-        LOGGER_.lock().unwrap()
+    pub fn okay_md(&self, markup: &str) {
+        self.raw().okay_md(markup);
+    }
+
+    pub fn note_md(&self, markup: &str) {
+        self.raw().note_md(markup);
+    }
+
+    pub fn debug(&self, msg: impl Display) {
+        self.raw().debug(msg);
+    }
+
+    pub fn devlog(&self, msg: impl Display) {
+        self.raw().devlog(msg);
+    }
+
+    pub fn trace(&self, msg: impl Display) {
+        self.raw().trace(msg);
+    }
+
+    pub fn magic(&self, msg: impl Display) {
+        self.raw().magic(msg);
+    }
+
+    pub fn silly(&self, msg: impl Display) {
+        self.raw().silly(msg);
+    }
+
+    /// An `io::Write` that logs each line at `level` through the global
+    /// logger, locking once per line. Unlike `Stderr::writer_for` it can be
+    /// moved to another thread.
+    pub fn writer_for(&self, level: LogLevel) -> crate::stderr::adapters::LevelWriter<'static> {
+        crate::stderr::adapters::LevelWriter::global(level)
+    }
+
+    /// `Stderr::batch` on the global logger, holding the lock throughout.
+    ///
+    /// Log through the closure's argument, not `logger`: nested `logger`
+    /// calls from the same thread bypass the batch.
+    pub fn batch<R>(&self, f: impl FnOnce(&mut Stderr) -> R) -> R {
+        self.raw().batch(f)
+    }
+
+    pub fn set_status(&self, msg: &str) {
+        self.raw().set_status(msg);
+    }
+
+    pub fn clear_status(&self) {
+        self.raw().clear_status();
+    }
+
+    /// Like `Stderr::suspend`, but the lock is released while `f` runs, so
+    /// `f` may log (its messages are printed without the status line).
+    pub fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        let status = {
+            let mut log = self.raw();
+            log.hide_status();
+            let _ = std::io::Write::flush(&mut log.writer);
+            log.status.take()
+        };
+        let result = f();
+        let mut log = self.raw();
+        log.status = status;
+        log.show_status();
+        result
+    }
+
+    pub fn progress(&self, msg: &str, percent: Option<u8>) {
+        self.raw().progress(msg, percent);
+    }
+
+    pub fn log(&self, level: LogLevel, msg: impl Display) {
+        self.raw().log(level, msg);
+    }
+
+    pub fn try_log(&self, level: LogLevel, msg: impl Display) -> std::io::Result<()> {
+        self.raw().try_log(level, msg)
+    }
+
+    /// Logs an error and exits the process with status 1
+    pub fn fatal(&self, msg: impl Display) -> ! {
+        self.raw().fatal(msg)
+    }
+
+    /// Hands buffered output to the terminal (and flushes sinks). The
+    /// global logger is never dropped, so with `FlushPolicy::Manual` or
+    /// `Bytes` call this (or hold a [`flush_guard`](Self::flush_guard))
+    /// before `main` returns.
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.raw().flush()
+    }
+
+    /// Flushes the global logger when the returned guard drops; create it
+    /// at the top of `main`
+    ///
+    /// ```
+    /// use stderr::{logger, FlushPolicy};
+    ///
+    /// let _flush = logger.flush_guard();
+    /// logger.raw().set_flush_policy(FlushPolicy::Manual);
+    /// logger.info("written when main returns");
+    /// ```
+    pub fn flush_guard(&self) -> FlushGuard {
+        FlushGuard
+    }
+
+    /// Confirmation through the global logger.
+    ///
+    /// The lock is only held while the prompt is drawn, not while waiting
+    /// for input, so other threads can keep logging. End of input (Ctrl-D,
+    /// a closed pipe) answers `None`.
+    #[cfg(feature = "interactive")]
+    pub fn confirm(&self, prompt: &str) -> std::io::Result<Option<bool>> {
+        self.ask_confirm(prompt, None)
+    }
+
+    /// Like [`confirm`](Self::confirm), answering through `policy` when
+    /// nobody can be asked
+    #[cfg(feature = "interactive")]
+    pub fn confirm_with_policy(&self, prompt: &str, policy: crate::ConfirmPolicy) -> std::io::Result<Option<bool>> {
+        self.ask_confirm(prompt, Some(policy))
+    }
+
+    #[cfg(feature = "interactive")]
+    fn ask_confirm(&self, prompt: &str, policy: Option<crate::ConfirmPolicy>) -> std::io::Result<Option<bool>> {
+        if let Some(answer) = self.raw().confirm_preflight(policy)? { return Ok(answer); }
+
+        loop {
+            self.raw().write_confirm_prompt(prompt, None, false, None)?;
+
+            let input = crate::utils::helpers::readline()?;
+            // End of input: nobody is left to answer, so fail closed
+            if input.is_empty() { return Ok(None); }
+            self.raw().prompt_answered();
+
+            match crate::stderr::interactive::parse_confirm(&input, None) {
+                Some(answer) => return Ok(answer),
+                None => self.warn("Invalid input. Please try again."),
+            }
+        }
+    }
+
+    /// Numbered selection through the global logger, with the same locking
+    /// behavior as [`confirm`](Self::confirm).
+    #[cfg(feature = "interactive")]
+    pub fn select(&self, prompt: &str, items: &[&str]) -> std::io::Result<Option<usize>> {
+        {
+            let mut log = self.raw();
+            if items.is_empty() || log.check_flag(crate::OptionFlag::Quiet) { return Ok(None); }
+            log.require_terminal()?;
+            log.write_select_menu(prompt, items)?;
+        }
+
+        loop {
+            self.raw().write_select_prompt(items.len())?;
+
+            let input = crate::utils::helpers::readline()?;
+            if input.is_empty() { return Ok(None); }
+            self.raw().prompt_answered();
+
+            match crate::stderr::interactive::parse_select(&input, items.len()) {
+                Some(choice) => return Ok(choice),
+                None => self.warn("Invalid input. Please try again."),
+            }
+        }
+    }
+
+    /// Async confirmation through the global logger.
+    ///
+    /// The lock is only held while the prompt is drawn, never across the
+    /// `.await`, so the returned future is `Send` and other tasks can keep
+    /// logging while the user thinks.
+    #[cfg(feature = "async")]
+    pub async fn confirm_async(&self, prompt: &str) -> std::io::Result<Option<bool>> {
+        if let Some(answer) = self.raw().confirm_preflight(None)? { return Ok(answer); }
+
+        loop {
+            self.raw().write_confirm_prompt(prompt, None, false, None)?;
+
+            let input = crate::utils::helpers::readline_async().await?;
+            if input.is_empty() { return Ok(None); }
+            self.raw().prompt_answered();
+
+            match crate::stderr::interactive::parse_confirm(&input, None) {
+                Some(answer) => return Ok(answer),
+                None => self.warn("Invalid input. Please try again."),
+            }
+        }
+    }
+
+    /// Locks the global logger, applying the calling thread's scope for as
+    /// long as the guard is held.
+    ///
+    /// Keep the guard short-lived: other threads block until it is dropped.
+    /// Logging again from the same thread while it is held is safe (see the
+    /// module docs) but changes made through that nested guard are not kept.
+    pub fn raw(&self) -> LoggerGuard {
+        let nested = HELD_CONFIG.with(|held| held.borrow().clone());
+        if let Some(config) = nested {
+            let mut fallback = Stderr::with_config(config);
+            fallback.label = self.scope();
+            return LoggerGuard { inner: Inner::Nested(Box::new(fallback)), saved_label: None };
+        }
+
+        let mut guard = LOGGER_.lock().unwrap_or_else(PoisonError::into_inner);
+        HELD_CONFIG.with(|held| *held.borrow_mut() = Some(guard.config.clone()));
+        let saved_label = self.scope().map(|scope| guard.label.replace(scope));
+        LoggerGuard { inner: Inner::Shared(guard), saved_label }
+    }
+
+    pub fn push_context(&self, context: &str) {
+        self.raw().push_context(context);
+    }
+
+    pub fn pop_context(&self) -> Option<String> {
+        self.raw().pop_context()
+    }
+
+    /// Pushes a context on the global logger and restores the outer one
+    /// when the returned guard drops. Unlike `Stderr::context_scope` the
+    /// lock is not held in between, so the scope's body can log freely.
+    pub fn context_scope(&self, context: &str) -> StaticContextScope {
+        let mut log = self.raw();
+        log.push_context(context);
+        StaticContextScope { depth: log.context_depth() - 1 }
+    }
+
+    /// Creates a trace scope on the global logger that logs entry now and
+    /// exit on drop, locking only for each individual line.
+    #[cfg(feature = "trace")]
+    pub fn trace_scope(&self, func_name: &str) -> StaticTraceScope {
+        self.raw().trace_fn(func_name, "entering");
+        StaticTraceScope {
+            func_name: func_name.to_string(),
+            #[cfg(feature = "otel")]
+            span: self.enabled(LogLevel::Trace).then(|| crate::stderr::otel::ScopeSpan::start(func_name)),
+        }
+    }
+}
+
+/// Exclusive access to the global logger, returned by `logger.raw()`.
+///
+/// If the thread has a scope, it replaces the shared label until the guard
+/// is dropped; label changes made through a scoped guard are discarded.
+pub struct LoggerGuard {
+    inner: Inner,
+    saved_label: Option<Option<String>>,
+}
+
+enum Inner {
+    Shared(MutexGuard<'static, Stderr>),
+    Nested(Box<Stderr>),
+}
+
+impl Deref for LoggerGuard {
+    type Target = Stderr;
+
+    fn deref(&self) -> &Stderr {
+        match &self.inner {
+            Inner::Shared(guard) => guard,
+            Inner::Nested(fallback) => fallback,
+        }
+    }
+}
+
+impl DerefMut for LoggerGuard {
+    fn deref_mut(&mut self) -> &mut Stderr {
+        match &mut self.inner {
+            Inner::Shared(guard) => guard,
+            Inner::Nested(fallback) => fallback,
+        }
+    }
+}
+
+impl Drop for LoggerGuard {
+    fn drop(&mut self) {
+        if let Inner::Shared(guard) = &mut self.inner {
+            if let Some(label) = self.saved_label.take() {
+                guard.label = label;
+            }
+            ENABLED.store(enabled_mask(guard), Ordering::Relaxed);
+            HELD_CONFIG.with(|held| *held.borrow_mut() = None);
+        }
+    }
+}
+
+/// RAII trace scope over the global logger, created by `logger.trace_scope()`
+/// and the `qtrace_scope!` macro. Unlike `TraceScope` it does not hold the
+/// lock between lines.
+#[cfg(feature = "trace")]
+pub struct StaticTraceScope {
+    func_name: String,
+    // Only started when trace output is enabled
+    #[cfg(feature = "otel")]
+    span: Option<crate::stderr::otel::ScopeSpan>,
+}
+
+#[cfg(feature = "trace")]
+impl StaticTraceScope {
+    /// Add a step within this function scope
+    pub fn step(&self, msg: &str) {
+        #[cfg(feature = "otel")]
+        if let Some(span) = &self.span {
+            span.event(msg, None);
+        }
+        LOGGER.raw().trace_fn(&self.func_name, msg);
+    }
+
+    /// Add a step with debug information
+    pub fn step_debug<T: std::fmt::Debug>(&self, msg: &str, value: &T) {
+        #[cfg(feature = "otel")]
+        if let Some(span) = &self.span {
+            span.event(msg, Some(format!("{:?}", value)));
+        }
+        if LOGGER.enabled(LogLevel::Trace) {
+            LOGGER.raw().trace_fn(&self.func_name, &format!("{}: {:#?}", msg, value));
+        }
+    }
+}
+
+#[cfg(feature = "trace")]
+impl Drop for StaticTraceScope {
+    fn drop(&mut self) {
+        LOGGER.raw().trace_fn(&self.func_name, "exiting");
+    }
+}
+
+/// Flushes the global logger on drop, from `logger.flush_guard()`
+#[must_use = "the logger is flushed when the guard is dropped"]
+pub struct FlushGuard;
+
+impl Drop for FlushGuard {
+    fn drop(&mut self) {
+        let _ = LOGGER.flush();
+    }
+}
+
+/// RAII context over the global logger, created by `logger.context_scope()`
+/// and the `qwith_context!` macro
+#[must_use = "the context is popped as soon as the guard is dropped"]
+pub struct StaticContextScope {
+    depth: usize,
+}
+
+impl Drop for StaticContextScope {
+    fn drop(&mut self) {
+        LOGGER.raw().restore_context(self.depth);
     }
 }
 
 pub static LOGGER: StaticLogger = StaticLogger;
+
+// The logger and its async prompt must stay `Send` so they can cross await
+// points and thread boundaries in tokio-based CLIs.
+const _: fn() = || {
+    fn is_send<T: Send + 'static>(_: &T) {}
+    is_send(&Stderr::new());
+    is_send(&&LOGGER);
+    #[cfg(feature = "async")]
+    is_send(&LOGGER.confirm_async("?"));
+};
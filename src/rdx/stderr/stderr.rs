@@ -1,26 +1,244 @@
 //! Core stderr functionality - basic logging without extensions
 
 use std::fmt::{Display, Debug};
+#[cfg(feature = "serde")]
+use std::collections::BTreeMap;
 use std::io::{self, Write};
-use termcolor::{Color, ColorChoice, ColorSpec, StandardStream, WriteColor};
+use std::ops::{Deref, DerefMut};
+use std::panic::Location;
+use termcolor::{Color, ColorChoice, ColorSpec, WriteColor};
 use crate::esc::colors::Color as ESC;
+use crate::esc::markup;
+use crate::esc::style::{Style, StyledString};
 
-use crate::utils::helpers::{term_width, env};
+use super::output::{FlushPolicy, Output, SharedTarget};
+use super::ci::{self, CiVendor};
+use super::sinks::{Record, Sink, SinkEntry};
+
+use super::width::{SoftWrap, Width, WidthPolicy};
+use crate::utils::ansi::{truncate_ansi, visible_width, wrap_ansi};
+use crate::utils::helpers::env;
+use crate::utils::text::{truncate, Ellipsis};
 
 /// Logging levels for the core logger
+///
+/// Levels are ordered from most severe to most verbose (like `log::Level`),
+/// so `level <= LogLevel::Debug` reads as "at most debug-verbose". They print
+/// and parse as lowercase names (`"warn"`, `"devlog"`), case-insensitively.
+///
+/// ```
+/// use stderr::LogLevel;
+///
+/// let level: LogLevel = "Trace".parse().unwrap();
+/// assert!(level > LogLevel::Debug);
+/// assert_eq!(LogLevel::Warn.to_string(), "warn");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, strum_macros::Display, strum_macros::EnumString)]
+#[strum(serialize_all = "lowercase", ascii_case_insensitive)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
 pub enum LogLevel {
+    Error,
+    Warn,
     Okay,
     Info,
     Note,
-    Warn,
-    Error,
     Debug,
+    #[strum(to_string = "devlog", serialize = "dev")]
+    DevLog,
     Trace,
     Magic,
     Silly,
-    DevLog,
 }
 
+impl LogLevel {
+    /// Every level, from most severe to most verbose
+    pub const ALL: [LogLevel; 10] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Okay,
+        LogLevel::Info,
+        LogLevel::Note,
+        LogLevel::Debug,
+        LogLevel::DevLog,
+        LogLevel::Trace,
+        LogLevel::Magic,
+        LogLevel::Silly,
+    ];
+
+    /// Whether this level survives the `max-level-*` / `release-max-level-*`
+    /// cargo features. Stripped levels compile down to no-ops in the level
+    /// methods and the `q*` macros.
+    ///
+    /// - `max-level-info` strips `Debug`, `DevLog`, `Trace`, `Magic`, `Silly`
+    /// - `max-level-debug` strips `Trace`, `Magic`, `Silly`
+    pub const fn is_compiled_in(self) -> bool {
+        match self {
+            LogLevel::Debug | LogLevel::DevLog => !STRIP_DEBUG,
+            LogLevel::Trace | LogLevel::Magic | LogLevel::Silly => !STRIP_TRACE,
+            _ => true,
+        }
+    }
+}
+
+/// Set of enabled log levels, one bit per `LogLevel`.
+///
+/// ```
+/// use stderr::{LevelMask, LogLevel};
+///
+/// // trace output without the debug chatter
+/// let mask = LevelMask::DEFAULT.with(LogLevel::Trace);
+/// assert!(mask.contains(LogLevel::Trace));
+/// assert!(!mask.contains(LogLevel::Debug));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(into = "Vec<LogLevel>", from = "Vec<LogLevel>"))]
+pub struct LevelMask(u32);
+
+impl LevelMask {
+    pub const NONE: LevelMask = LevelMask(0);
+
+    /// Error, Warn, Okay, Info and Note: what prints without any verbose flags
+    pub const DEFAULT: LevelMask = LevelMask::NONE
+        .with(LogLevel::Error)
+        .with(LogLevel::Warn)
+        .with(LogLevel::Okay)
+        .with(LogLevel::Info)
+        .with(LogLevel::Note);
+
+    /// Every level up to and including `max` in verbosity
+    pub fn up_to(max: LogLevel) -> Self {
+        LogLevel::ALL.into_iter().filter(|&level| level <= max).collect()
+    }
+
+    pub const ALL: LevelMask = LevelMask((1 << LogLevel::ALL.len()) - 1);
+
+    const fn bit(level: LogLevel) -> u32 {
+        1 << level as u32
+    }
+
+    /// Returns a copy with `level` enabled
+    pub const fn with(self, level: LogLevel) -> Self {
+        LevelMask(self.0 | Self::bit(level))
+    }
+
+    /// Returns a copy with `level` disabled
+    pub const fn without(self, level: LogLevel) -> Self {
+        LevelMask(self.0 & !Self::bit(level))
+    }
+
+    pub const fn contains(self, level: LogLevel) -> bool {
+        self.0 & Self::bit(level) != 0
+    }
+
+    pub fn insert(&mut self, level: LogLevel) {
+        *self = self.with(level);
+    }
+
+    pub fn remove(&mut self, level: LogLevel) {
+        *self = self.without(level);
+    }
+
+    /// Enables or disables `level`
+    pub fn set(&mut self, level: LogLevel, on: bool) {
+        if on { self.insert(level) } else { self.remove(level) }
+    }
+
+    /// The raw bitset (bit `n` = `LogLevel` discriminant `n`)
+    pub const fn bits(self) -> u32 {
+        self.0
+    }
+
+    /// Enabled levels, in `LogLevel::ALL` order
+    pub fn levels(self) -> impl Iterator<Item = LogLevel> {
+        LogLevel::ALL.into_iter().filter(move |&level| self.contains(level))
+    }
+
+    /// Levels enabled in either mask
+    pub const fn union(self, other: LevelMask) -> Self {
+        LevelMask(self.0 | other.0)
+    }
+}
+
+/// Where messages at one level go; see [`StderrConfig::set_route`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum Route {
+    /// Terminal and sinks
+    Show,
+    /// Nowhere (sinks with their own level filter still decide for themselves)
+    Hide,
+    /// Sinks only, e.g. keep `debug` in the log file but off the screen
+    SinkOnly,
+}
+
+impl Default for LevelMask {
+    fn default() -> Self {
+        LevelMask::DEFAULT
+    }
+}
+
+impl FromIterator<LogLevel> for LevelMask {
+    fn from_iter<I: IntoIterator<Item = LogLevel>>(iter: I) -> Self {
+        iter.into_iter().fold(LevelMask::NONE, LevelMask::with)
+    }
+}
+
+impl From<Vec<LogLevel>> for LevelMask {
+    fn from(levels: Vec<LogLevel>) -> Self {
+        levels.into_iter().collect()
+    }
+}
+
+impl From<LevelMask> for Vec<LogLevel> {
+    fn from(mask: LevelMask) -> Self {
+        mask.levels().collect()
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<log::Level> for LogLevel {
+    fn from(level: log::Level) -> Self {
+        match level {
+            log::Level::Error => LogLevel::Error,
+            log::Level::Warn => LogLevel::Warn,
+            log::Level::Info => LogLevel::Info,
+            log::Level::Debug => LogLevel::Debug,
+            log::Level::Trace => LogLevel::Trace,
+        }
+    }
+}
+
+#[cfg(feature = "log")]
+impl From<LogLevel> for log::Level {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Error => log::Level::Error,
+            LogLevel::Warn => log::Level::Warn,
+            LogLevel::Okay | LogLevel::Info | LogLevel::Note => log::Level::Info,
+            LogLevel::Debug | LogLevel::DevLog => log::Level::Debug,
+            LogLevel::Trace | LogLevel::Magic | LogLevel::Silly => log::Level::Trace,
+        }
+    }
+}
+
+// Verbose levels removed at compile time by the `max-level-*` features
+const STRIP_DEBUG: bool = cfg!(feature = "max-level-info")
+    || (cfg!(feature = "release-max-level-info") && !cfg!(debug_assertions));
+const STRIP_TRACE: bool = STRIP_DEBUG
+    || cfg!(feature = "max-level-debug")
+    || (cfg!(feature = "release-max-level-debug") && !cfg!(debug_assertions));
+
+// Longest context shown in the inline tag before it is cut with `…`
+const CONTEXT_TAG_WIDTH: usize = 24;
+// Fill characters kept on each side of a context banner's title
+const BANNER_MIN_FILL: usize = 3;
+
+// Columns each open section scope or indent guard indents output by
+const INDENT_WIDTH: usize = 2;
+
 /// Configuration flags
 pub enum OptionFlag {
     Quiet,
@@ -30,25 +248,339 @@ pub enum OptionFlag {
     Silly,
 }
 
+/// When to emit ANSI colors
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ColorMode {
+    /// Color when stderr is a terminal (honors `NO_COLOR` and `TERM=dumb`)
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorMode {
+    /// The mode asked for by the environment, by the usual conventions:
+    /// a non-empty `NO_COLOR` turns colors off, then `CLICOLOR_FORCE` (not
+    /// `0`) turns them on even when piped, then `CLICOLOR=0` turns them off.
+    /// Anything else is `Auto`.
+    pub fn from_env() -> Self {
+        let set = |var: &str| env(var).is_ok_and(|v| !v.is_empty());
+        if set("NO_COLOR") {
+            ColorMode::Never
+        } else if env("CLICOLOR_FORCE").is_ok_and(|v| !v.is_empty() && v != "0") {
+            ColorMode::Always
+        } else if env("CLICOLOR").is_ok_and(|v| v == "0") {
+            ColorMode::Never
+        } else {
+            ColorMode::Auto
+        }
+    }
+}
+
+impl From<ColorChoice> for ColorMode {
+    fn from(choice: ColorChoice) -> Self {
+        match choice {
+            ColorChoice::Always | ColorChoice::AlwaysAnsi => ColorMode::Always,
+            ColorChoice::Auto => ColorMode::Auto,
+            ColorChoice::Never => ColorMode::Never,
+        }
+    }
+}
+
+/// How log lines are written
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum OutputFormat {
+    /// Styled text with glyphs, for people
+    #[default]
+    Pretty,
+    /// One JSON object per message (`timestamp`, `level`, `message`, and
+    /// `label` / `context` when set), for log shippers
+    Json,
+}
+
+impl From<ColorMode> for ColorChoice {
+    fn from(mode: ColorMode) -> Self {
+        match mode {
+            ColorMode::Auto => ColorChoice::Auto,
+            ColorMode::Always => ColorChoice::Always,
+            ColorMode::Never => ColorChoice::Never,
+        }
+    }
+}
+
+/// What logging methods do when writing to the terminal fails (e.g. a
+/// broken pipe after `| head`)
+#[derive(Debug, Clone, Copy, Default)]
+pub enum ErrorPolicy {
+    /// Drop the error, so logging never interrupts the program
+    #[default]
+    Ignore,
+    /// Panic with the error
+    Panic,
+    /// Hand the error to a function, e.g. to exit quietly on `BrokenPipe`
+    Callback(fn(&io::Error)),
+}
+
+/// How confirmations answer when nobody can be asked: in quiet mode, in
+/// the plain-CI profile, or when stdin is not a terminal
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "kebab-case"))]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum ConfirmPolicy {
+    /// Answer `None`, as if the user quit, so nothing destructive runs
+    FailClosed,
+    /// Answer yes
+    AssumeYes,
+    /// Answer no
+    AssumeNo,
+    /// Return an `Unsupported` error
+    Error,
+}
+
 /// Core configuration for stderr
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 pub struct StderrConfig {
+    /// Mutes the terminal below `quiet_floor`, regardless of `enabled`
+    /// (sinks with their own level filter still receive messages)
     pub quiet: bool,
-    pub dev: bool,
-    pub debug: bool,
-    pub trace: bool,
-    pub silly: bool,
+    /// Most verbose level that still prints in quiet mode (default `Error`)
+    pub quiet_floor: LogLevel,
+    /// Mutes the terminal completely, errors included
+    pub silent: bool,
+    /// Levels that print when not quiet
+    pub enabled: LevelMask,
+    /// Levels that skip the terminal but still reach sinks following the
+    /// logger's levels (`Route::SinkOnly`)
+    pub sink_only: LevelMask,
+    /// Print a banner when the context changes (default on)
+    pub context_banners: bool,
+    /// Tag every line with a dim, abbreviated `(context)` after its prefix
+    pub show_context_inline: bool,
+    pub color: ColorMode,
+    /// No colors and ASCII-only glyphs, borders and trace trees, e.g. for
+    /// output saved to files
+    pub plain: bool,
+    /// Styled text or JSON lines (`LOG_FORMAT=json`)
+    pub format: OutputFormat,
+    /// Headless answer for confirmations; `None` fails closed in quiet
+    /// mode and is an error without a terminal
+    pub confirm_policy: Option<ConfirmPolicy>,
+    /// Applied to terminal write errors; `try_log` returns them instead
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub on_write_error: ErrorPolicy,
+}
+
+impl Default for StderrConfig {
+    fn default() -> Self {
+        Self {
+            quiet: false,
+            quiet_floor: LogLevel::Error,
+            silent: false,
+            enabled: LevelMask::DEFAULT,
+            sink_only: LevelMask::NONE,
+            context_banners: true,
+            show_context_inline: false,
+            color: ColorMode::Auto,
+            plain: false,
+            format: OutputFormat::Pretty,
+            confirm_policy: None,
+            on_write_error: ErrorPolicy::Ignore,
+        }
+    }
 }
 
 impl StderrConfig {
     /// Creates configuration from environment variables
     pub fn from_env() -> Self {
-        Self {
+        let mut config = Self {
             quiet: env("QUIET_MODE").is_ok(),
-            debug: env("DEBUG_MODE").is_ok(),
-            dev: env("DEV_MODE").is_ok(),
-            trace: env("TRACE_MODE").is_ok(),
-            silly: env("SILLY_MODE").is_ok(),
+            quiet_floor: LogLevel::Error,
+            silent: env("SILENT_MODE").is_ok(),
+            enabled: LevelMask::DEFAULT,
+            sink_only: LevelMask::NONE,
+            context_banners: true,
+            show_context_inline: false,
+            color: ColorMode::from_env(),
+            plain: false,
+            format: if env("LOG_FORMAT").is_ok_and(|v| v.eq_ignore_ascii_case("json")) {
+                OutputFormat::Json
+            } else {
+                OutputFormat::Pretty
+            },
+            confirm_policy: None,
+            on_write_error: ErrorPolicy::Ignore,
+        };
+        // LOG_LEVEL sets the threshold; the older *_MODE switches add to it
+        if let Some(level) = env("LOG_LEVEL").ok().and_then(|v| v.trim().parse().ok()) {
+            config.set_level(level);
+        }
+        let switches = [
+            ("DEBUG_MODE", OptionFlag::Debug),
+            ("DEV_MODE", OptionFlag::Dev),
+            ("TRACE_MODE", OptionFlag::Trace),
+            ("SILLY_MODE", OptionFlag::Silly),
+        ];
+        for (var, flag) in switches {
+            if env(var).is_ok() { config.set_flag(flag, true); }
+        }
+        config
+    }
+
+    /// Enables exactly the levels up to `max` in verbosity, replacing the
+    /// current set (the `debug`/`trace`/`silly` switches included)
+    ///
+    /// ```
+    /// use stderr::{LogLevel, StderrConfig};
+    ///
+    /// let mut config = StderrConfig::default();
+    /// config.set_level(LogLevel::Debug);
+    /// assert!(config.shows(LogLevel::Warn) && config.shows(LogLevel::Debug));
+    /// assert!(!config.shows(LogLevel::Trace));
+    /// assert_eq!(config.level(), Some(LogLevel::Debug));
+    /// ```
+    pub fn set_level(&mut self, max: LogLevel) {
+        self.enabled = LevelMask::up_to(max);
+    }
+
+    /// The most verbose enabled level, or `None` when every level is off
+    pub fn level(&self) -> Option<LogLevel> {
+        self.enabled.levels().last()
+    }
+
+    pub fn enable(&mut self, level: LogLevel) {
+        self.enabled.insert(level);
+    }
+
+    pub fn disable(&mut self, level: LogLevel) {
+        self.enabled.remove(level);
+    }
+
+    /// Where messages at `level` currently go
+    pub fn route(&self, level: LogLevel) -> Route {
+        if self.enabled.contains(level) {
+            Route::Show
+        } else if self.sink_only.contains(level) {
+            Route::SinkOnly
+        } else {
+            Route::Hide
+        }
+    }
+
+    /// Routes one level: show it, hide it, or send it to sinks only.
+    ///
+    /// ```
+    /// use stderr::{LogLevel, Route, StderrConfig};
+    ///
+    /// // warnings on screen, info off it, debug only in the log file
+    /// let mut config = StderrConfig::default();
+    /// config.set_route(LogLevel::Info, Route::Hide);
+    /// config.set_route(LogLevel::Debug, Route::SinkOnly);
+    /// assert_eq!(config.route(LogLevel::Warn), Route::Show);
+    /// assert!(!config.shows(LogLevel::Debug));
+    /// assert!(config.sink_levels().contains(LogLevel::Debug));
+    /// ```
+    pub fn set_route(&mut self, level: LogLevel, route: Route) {
+        self.enabled.set(level, route == Route::Show);
+        self.sink_only.set(level, route == Route::SinkOnly);
+    }
+
+    /// The color choice for the writer: `Never` in plain mode, else `color`
+    pub fn color_choice(&self) -> ColorChoice {
+        if self.plain { ColorChoice::Never } else { self.color.into() }
+    }
+
+    /// Every level with its route, from most severe to most verbose
+    pub fn routes(&self) -> impl Iterator<Item = (LogLevel, Route)> + '_ {
+        LogLevel::ALL.into_iter().map(move |level| (level, self.route(level)))
+    }
+
+    /// Levels that sinks without their own filter receive
+    pub fn sink_levels(&self) -> LevelMask {
+        self.enabled.union(self.sink_only)
+    }
+
+    /// Whether `level` prints on the terminal under the current quiet,
+    /// silent and level settings
+    ///
+    /// ```
+    /// use stderr::{LogLevel, StderrConfig};
+    ///
+    /// let mut config = StderrConfig { quiet: true, ..StderrConfig::default() };
+    /// assert!(config.shows(LogLevel::Error));
+    /// assert!(!config.shows(LogLevel::Warn));
+    ///
+    /// config.quiet_floor = LogLevel::Warn;
+    /// assert!(config.shows(LogLevel::Warn));
+    ///
+    /// config.silent = true;
+    /// assert!(!config.shows(LogLevel::Error));
+    /// ```
+    #[inline]
+    pub fn shows(&self, level: LogLevel) -> bool {
+        !self.silent
+            && (!self.quiet || level <= self.quiet_floor)
+            && self.enabled.contains(level)
+    }
+
+    /// Reads one of the classic on/off switches; `Silly` covers both
+    /// `Silly` and `Magic`, and `Quiet` is also on in silent mode.
+    pub fn flag(&self, flag: OptionFlag) -> bool {
+        match flag {
+            OptionFlag::Quiet => self.quiet || self.silent,
+            OptionFlag::Dev => self.enabled.contains(LogLevel::DevLog),
+            OptionFlag::Debug => self.enabled.contains(LogLevel::Debug),
+            OptionFlag::Trace => self.enabled.contains(LogLevel::Trace),
+            OptionFlag::Silly => self.enabled.contains(LogLevel::Silly),
+        }
+    }
+
+    /// Flips one of the classic on/off switches in the level mask
+    pub fn set_flag(&mut self, flag: OptionFlag, on: bool) {
+        match flag {
+            OptionFlag::Quiet => self.quiet = on,
+            OptionFlag::Dev => self.enabled.set(LogLevel::DevLog, on),
+            OptionFlag::Debug => self.enabled.set(LogLevel::Debug, on),
+            OptionFlag::Trace => self.enabled.set(LogLevel::Trace, on),
+            OptionFlag::Silly => {
+                self.enabled.set(LogLevel::Silly, on);
+                self.enabled.set(LogLevel::Magic, on);
+            }
+        }
+    }
+
+    /// Loads configuration from a TOML or JSON file, chosen by extension
+    /// (anything other than `.json` is read as TOML). Missing keys keep
+    /// their defaults.
+    ///
+    /// ```no_run
+    /// # fn main() -> std::io::Result<()> {
+    /// use stderr::{Stderr, StderrConfig};
+    ///
+    /// // stderr.toml:
+    /// //   enabled = ["error", "warn", "okay", "info", "note", "debug", "trace"]
+    /// let config = StderrConfig::from_file("stderr.toml")?;
+    /// let mut log = Stderr::with_config(config);
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn from_file(path: impl AsRef<std::path::Path>) -> io::Result<Self> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path)?;
+        let is_json = path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+        if is_json {
+            serde_json::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+        } else {
+            toml::from_str(&text).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
         }
     }
 }
@@ -100,18 +632,105 @@ impl<'a> DebugPrinter<'a> {
     }
 }
 
+/// RAII guard from [`Stderr::context_scope`]; derefs to the logger and pops
+/// the context when dropped
+#[must_use = "the context is popped as soon as the guard is dropped"]
+pub struct ContextScope<'a> {
+    stderr: &'a mut Stderr,
+    depth: usize,
+}
+
+impl Deref for ContextScope<'_> {
+    type Target = Stderr;
+
+    fn deref(&self) -> &Stderr {
+        self.stderr
+    }
+}
+
+impl DerefMut for ContextScope<'_> {
+    fn deref_mut(&mut self) -> &mut Stderr {
+        self.stderr
+    }
+}
+
+impl Drop for ContextScope<'_> {
+    fn drop(&mut self) {
+        self.stderr.restore_context(self.depth);
+    }
+}
+
+/// RAII guard from [`Stderr::indent`]; derefs to the logger and restores
+/// the previous indentation when dropped
+#[must_use = "the indentation is undone as soon as the guard is dropped"]
+pub struct IndentScope<'a> {
+    stderr: &'a mut Stderr,
+    depth: usize,
+}
+
+impl Deref for IndentScope<'_> {
+    type Target = Stderr;
+
+    fn deref(&self) -> &Stderr {
+        self.stderr
+    }
+}
+
+impl DerefMut for IndentScope<'_> {
+    fn deref_mut(&mut self) -> &mut Stderr {
+        self.stderr
+    }
+}
+
+impl Drop for IndentScope<'_> {
+    fn drop(&mut self) {
+        self.stderr.set_indent(self.depth);
+    }
+}
+
 /// Core stderr struct with basic logging functionality
 pub struct Stderr {
     pub(crate) config: StderrConfig,
-    pub(crate) writer: StandardStream,
-    pub(crate) width: usize,
+    pub(crate) writer: Output,
+    pub(crate) width: Width,
+    pub(crate) soft_wrap: SoftWrap,
     pub(crate) label: Option<String>,
     
-    // Context tracking for banner display
-    pub(crate) current_context: Option<String>,
+    // Context stack for banner display; the innermost context is last
+    pub(crate) contexts: Vec<String>,
+    // Open section scopes and indent guards; each indents output one more level
+    pub(crate) indent: usize,
     
-    // Glyph customization
-    pub(crate) glyphs: GlyphSet,
+    // Level colors, glyphs and prefix shape, plus box/banner/header styles
+    pub(crate) theme: Theme,
+    pub(crate) context_style: ContextBannerStyle,
+    // Whether box borders and fills may use Unicode line drawing
+    pub(crate) unicode: bool,
+
+    // CI log markup (detected from the environment) and the open section title
+    pub(crate) ci: Option<CiVendor>,
+    pub(crate) ci_profile: bool,
+    // GitHub Actions annotations for warnings and errors (opt-in), and the
+    // call site of the `warn`/`error` being emitted
+    pub(crate) ci_annotations: bool,
+    pub(crate) caller: Option<&'static Location<'static>>,
+
+    // Reused buffers for formatting `impl Display` messages and CI prefixes
+    pub(crate) scratch: String,
+    pub(crate) prefix_buf: Vec<u8>,
+
+    // Extra destinations (files, syslog, otel, ...) with their level filters
+    pub(crate) sinks: Vec<SinkEntry>,
+
+    // Transient bottom line, redrawn under each message (terminals only)
+    pub(crate) status: Option<String>,
+    // Whether the status is on screen right now; it stays off while a line
+    // written piece by piece is unfinished
+    pub(crate) status_drawn: bool,
+    // Open `write_block` calls; the status is redrawn when the outermost ends
+    pub(crate) block_depth: u32,
+    #[cfg(feature = "formatting")]
+    pub(crate) open_section: Option<String>,
     
     // Feature-specific state (only compiled in when features are enabled)
     #[cfg(feature = "trace")]
@@ -119,7 +738,16 @@ pub struct Stderr {
 }
 
 /// Customizable glyph set for different logging functions
+///
+/// Loggers start from [`GlyphSet::detect`], so terminals without UTF-8 get
+/// the ASCII set instead of mojibake.
+///
+/// With the `serde` feature a set is a table of level names (and `banner`)
+/// to glyphs; entries left out keep their default glyph, and the strings read are
+/// interned like [`Glyph::register`](crate::Glyph::register)'s.
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(into = "BTreeMap<String, String>"))]
 pub struct GlyphSet {
     pub info: &'static str,
     pub warn: &'static str,
@@ -128,6 +756,230 @@ pub struct GlyphSet {
     pub trace: &'static str,
     pub debug: &'static str,
     pub magic: &'static str,
+    pub note: &'static str,
+    pub silly: &'static str,
+    pub devlog: &'static str,
+    /// Set before banner titles, e.g. `==== ◆ Deploy ====`; empty (the
+    /// default) for none
+    pub banner: &'static str,
+}
+
+impl GlyphSet {
+    /// ASCII-only glyphs, used in plain mode
+    pub const ASCII: GlyphSet = GlyphSet {
+        info: "i",
+        warn: "!",
+        error: "x",
+        okay: "+",
+        trace: ".",
+        debug: "#",
+        magic: "*",
+        note: ">",
+        silly: "~",
+        devlog: "#",
+        banner: "",
+    };
+
+    /// Starts a builder from the default set
+    ///
+    /// ```
+    /// use stderr::{GlyphSet, LogLevel, Stderr};
+    ///
+    /// let glyphs = GlyphSet::builder().note("»").silly("☺").build();
+    /// assert_eq!(glyphs.get(LogLevel::Note), "»");
+    /// let log = Stderr::new().with_glyphs(glyphs);
+    /// ```
+    pub fn builder() -> GlyphSetBuilder {
+        GlyphSetBuilder { glyphs: Self::default() }
+    }
+
+    pub fn get(&self, level: LogLevel) -> &'static str {
+        match level {
+            LogLevel::Okay => self.okay,
+            LogLevel::Warn => self.warn,
+            LogLevel::Error => self.error,
+            LogLevel::Info => self.info,
+            LogLevel::Note => self.note,
+            LogLevel::Debug => self.debug,
+            LogLevel::Trace => self.trace,
+            LogLevel::Magic => self.magic,
+            LogLevel::Silly => self.silly,
+            LogLevel::DevLog => self.devlog,
+        }
+    }
+
+    pub fn set(&mut self, level: LogLevel, glyph: &'static str) {
+        let slot = match level {
+            LogLevel::Okay => &mut self.okay,
+            LogLevel::Warn => &mut self.warn,
+            LogLevel::Error => &mut self.error,
+            LogLevel::Info => &mut self.info,
+            LogLevel::Note => &mut self.note,
+            LogLevel::Debug => &mut self.debug,
+            LogLevel::Trace => &mut self.trace,
+            LogLevel::Magic => &mut self.magic,
+            LogLevel::Silly => &mut self.silly,
+            LogLevel::DevLog => &mut self.devlog,
+        };
+        *slot = glyph;
+    }
+
+    /// The glyph set before banner titles (empty for none)
+    pub fn banner_glyph(&self) -> &'static str {
+        self.banner
+    }
+
+    pub fn set_banner_glyph(&mut self, glyph: &'static str) {
+        self.banner = glyph;
+    }
+
+    /// ASCII-only glyphs, safe on any terminal: `[i]`, `[!]`, `[x]`, ...
+    pub fn ascii() -> Self {
+        Self::ASCII
+    }
+
+    /// Emoji glyphs; most render two columns wide
+    pub fn emoji() -> Self {
+        Self {
+            info: "\u{2139}\u{FE0F}",   // ℹ️
+            warn: "\u{26A0}\u{FE0F}",   // ⚠️
+            error: "\u{274C}",          // ❌
+            okay: "\u{2705}",           // ✅
+            trace: "\u{1F50D}",         // 🔍
+            debug: "\u{1F41B}",         // 🐛
+            magic: "\u{2728}",          // ✨
+            note: "\u{1F4DD}",          // 📝
+            silly: "\u{1F92A}",         // 🤪
+            devlog: "\u{1F527}",        // 🔧
+            banner: "",
+        }
+    }
+
+    /// Font Awesome icons from a patched Nerd Font
+    pub fn nerd_font() -> Self {
+        Self {
+            info: "\u{F05A}",   // nf-fa-info_circle
+            warn: "\u{F071}",   // nf-fa-warning
+            error: "\u{F057}",  // nf-fa-times_circle
+            okay: "\u{F058}",   // nf-fa-check_circle
+            trace: "\u{F002}",  // nf-fa-search
+            debug: "\u{F188}",  // nf-fa-bug
+            magic: "\u{F0D0}",  // nf-fa-magic
+            note: "\u{F0EB}",   // nf-fa-lightbulb_o
+            silly: "\u{F118}",  // nf-fa-smile_o
+            devlog: "\u{F121}", // nf-fa-code
+            banner: "",
+        }
+    }
+
+    /// A preset by name, ignoring case: `unicode` (or `default`), `ascii`,
+    /// `emoji` and `nerd` (or `nerd-font`)
+    pub fn named(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "unicode" | "default" => Some(Self::default()),
+            "ascii" => Some(Self::ascii()),
+            "emoji" => Some(Self::emoji()),
+            "nerd" | "nerd-font" => Some(Self::nerd_font()),
+            _ => None,
+        }
+    }
+
+    /// The set for this terminal: the preset named by `STDERR_GLYPHS` if
+    /// set, else ASCII when the terminal can't be trusted with Unicode
+    /// (see [`supports_unicode`]), else the default Unicode set.
+    pub fn detect() -> Self {
+        if let Some(glyphs) = env("STDERR_GLYPHS").ok().and_then(|name| Self::named(&name)) {
+            return glyphs;
+        }
+        if supports_unicode() { Self::default() } else { Self::ascii() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<GlyphSet> for BTreeMap<String, String> {
+    fn from(glyphs: GlyphSet) -> Self {
+        let mut table: BTreeMap<String, String> = LogLevel::ALL.into_iter()
+            .map(|level| (level.to_string(), glyphs.get(level).to_string()))
+            .collect();
+        table.insert("banner".to_string(), glyphs.banner.to_string());
+        table
+    }
+}
+
+// Written by hand: a derived impl would borrow the `&'static str` fields
+// and only accept `'static` input
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GlyphSet {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let table = BTreeMap::<String, String>::deserialize(deserializer)?;
+        let mut glyphs = GlyphSet::default();
+        for (name, glyph) in table {
+            let glyph = crate::esc::glyphs::intern(&glyph);
+            if name == "banner" {
+                glyphs.set_banner_glyph(glyph);
+                continue;
+            }
+            let level = name.parse::<LogLevel>()
+                .map_err(|_| serde::de::Error::custom(format!("unknown glyph `{}`", name)))?;
+            glyphs.set(level, glyph);
+        }
+        Ok(glyphs)
+    }
+}
+
+/// Fluent builder for [`GlyphSet`], created via `GlyphSet::builder()`
+#[derive(Debug, Clone)]
+pub struct GlyphSetBuilder {
+    glyphs: GlyphSet,
+}
+
+impl GlyphSetBuilder {
+    /// Sets the glyph for one level
+    pub fn level(mut self, level: LogLevel, glyph: &'static str) -> Self {
+        self.glyphs.set(level, glyph);
+        self
+    }
+
+    pub fn okay(self, glyph: &'static str) -> Self { self.level(LogLevel::Okay, glyph) }
+    pub fn warn(self, glyph: &'static str) -> Self { self.level(LogLevel::Warn, glyph) }
+    pub fn error(self, glyph: &'static str) -> Self { self.level(LogLevel::Error, glyph) }
+    pub fn info(self, glyph: &'static str) -> Self { self.level(LogLevel::Info, glyph) }
+    pub fn note(self, glyph: &'static str) -> Self { self.level(LogLevel::Note, glyph) }
+    pub fn debug(self, glyph: &'static str) -> Self { self.level(LogLevel::Debug, glyph) }
+    pub fn trace(self, glyph: &'static str) -> Self { self.level(LogLevel::Trace, glyph) }
+    pub fn magic(self, glyph: &'static str) -> Self { self.level(LogLevel::Magic, glyph) }
+    pub fn silly(self, glyph: &'static str) -> Self { self.level(LogLevel::Silly, glyph) }
+    pub fn devlog(self, glyph: &'static str) -> Self { self.level(LogLevel::DevLog, glyph) }
+
+    /// Sets the glyph set before banner titles
+    pub fn banner(mut self, glyph: &'static str) -> Self {
+        self.glyphs.set_banner_glyph(glyph);
+        self
+    }
+
+    pub fn build(self) -> GlyphSet {
+        self.glyphs
+    }
+}
+
+/// Whether the terminal is likely to render Unicode glyphs.
+///
+/// The first locale variable set (`LC_ALL`, `LC_CTYPE`, `LANG`) decides:
+/// a UTF-8 locale can, anything else (`C`, `POSIX`, Latin-1, ...) can't.
+/// Without a locale, the Linux console and dumb terminals can't and
+/// everything else is assumed to.
+pub fn supports_unicode() -> bool {
+    if cfg!(windows) { return true; }
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .into_iter()
+        .find_map(|var| env(var).ok().filter(|v| !v.is_empty()));
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_ascii_lowercase();
+            locale.contains("utf-8") || locale.contains("utf8")
+        }
+        None => !matches!(env("TERM").as_deref(), Ok("linux") | Ok("dumb")),
+    }
 }
 
 impl Default for GlyphSet {
@@ -140,63 +992,819 @@ impl Default for GlyphSet {
             trace: "\u{2026}",     // …
             debug: "\u{232C}",     // ⌬
             magic: "\u{21AF}",     // ↯
+            note: "\u{2192}",      // →
+            silly: "\u{03C6}",     // φ
+            devlog: "\u{232C}",    // ⌬
+            banner: "",
         }
     }
-}
+}
+
+/// Per-level prefix styles (colors plus bold/dim/underline/...), used by both
+/// the text and `_debug` paths
+///
+/// ```
+/// use stderr::{Color, LogLevel, Stderr, Style, StyleSet};
+///
+/// let styles = StyleSet::default()
+///     .with(LogLevel::Error, Style::new().fg(Color::RED).bold())
+///     .with(LogLevel::Trace, Style::new().fg(Color::GREY).dimmed())
+///     .with(LogLevel::DevLog, Style::new().fg(Color::RED2).italic());
+/// let log = Stderr::builder().styles(styles).build();
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct StyleSet {
+    pub okay: Style,
+    pub warn: Style,
+    pub error: Style,
+    pub info: Style,
+    pub note: Style,
+    pub debug: Style,
+    pub trace: Style,
+    pub magic: Style,
+    pub silly: Style,
+    pub devlog: Style,
+}
+
+impl Default for StyleSet {
+    fn default() -> Self {
+        Self {
+            okay: ESC::GREEN.into(),
+            warn: ESC::ORANGE.into(),
+            error: ESC::RED.into(),
+            info: ESC::BLUE.into(),
+            note: ESC::BLUE.into(),
+            debug: ESC::CYAN.into(),
+            trace: ESC::GREY.into(),
+            magic: ESC::PURPLE.into(),
+            silly: ESC::MAGENTA.into(),
+            devlog: ESC::RED2.into(),
+        }
+    }
+}
+
+impl StyleSet {
+    pub fn get(&self, level: LogLevel) -> &Style {
+        match level {
+            LogLevel::Okay => &self.okay,
+            LogLevel::Warn => &self.warn,
+            LogLevel::Error => &self.error,
+            LogLevel::Info => &self.info,
+            LogLevel::Note => &self.note,
+            LogLevel::Debug => &self.debug,
+            LogLevel::Trace => &self.trace,
+            LogLevel::Magic => &self.magic,
+            LogLevel::Silly => &self.silly,
+            LogLevel::DevLog => &self.devlog,
+        }
+    }
+
+    pub fn set(&mut self, level: LogLevel, style: impl Into<Style>) {
+        let slot = match level {
+            LogLevel::Okay => &mut self.okay,
+            LogLevel::Warn => &mut self.warn,
+            LogLevel::Error => &mut self.error,
+            LogLevel::Info => &mut self.info,
+            LogLevel::Note => &mut self.note,
+            LogLevel::Debug => &mut self.debug,
+            LogLevel::Trace => &mut self.trace,
+            LogLevel::Magic => &mut self.magic,
+            LogLevel::Silly => &mut self.silly,
+            LogLevel::DevLog => &mut self.devlog,
+        };
+        *slot = style.into();
+    }
+
+    pub fn with(mut self, level: LogLevel, style: impl Into<Style>) -> Self {
+        self.set(level, style);
+        self
+    }
+}
+
+/// How a level's glyph is written in front of each message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]
+pub enum PrefixStyle {
+    /// `[λ] message`
+    #[default]
+    Brackets,
+    /// `λ message`
+    Bare,
+}
+
+impl PrefixStyle {
+    pub(crate) fn brackets(self) -> bool {
+        self == PrefixStyle::Brackets
+    }
+}
+
+/// Everything that decides how the logger looks: per-level styles and
+/// glyphs, the prefix shape, and the styles of boxes, banners and table
+/// headers. Set one with [`Stderr::with_theme`] or the builder's `theme`.
+///
+/// Loggers start from the preset named by `STDERR_THEME`, if any:
+///
+/// ```
+/// use stderr::{Stderr, Theme};
+///
+/// let mut log = Stderr::new();
+/// log.set_theme(Theme::solarized());
+/// log.info("solarized");
+/// assert!(Theme::named("High-Contrast").is_some());
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Theme {
+    pub styles: StyleSet,
+    pub glyphs: GlyphSet,
+    pub prefix: PrefixStyle,
+    /// Box borders and the text inside them
+    pub boxes: Style,
+    /// Banner titles; its foreground also colors the context banner
+    pub banner: Style,
+    /// Table header rows
+    pub headers: Style,
+    /// Context banner look; without one the banner keeps its own fill and
+    /// template and takes its color from `banner`
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub context: Option<ContextBannerStyle>,
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self {
+            styles: StyleSet::default(),
+            glyphs: GlyphSet::detect(),
+            prefix: PrefixStyle::Brackets,
+            boxes: ESC::WHITE.into(),
+            banner: Style::new().fg(ESC::BLUE).bold(),
+            headers: Style::new().fg(ESC::BLUE).bold(),
+            context: None,
+        }
+    }
+}
+
+/// Parses a preset name; see [`Theme::named`]
+impl std::str::FromStr for Theme {
+    type Err = strum::ParseError;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Theme::named(name).ok_or(strum::ParseError::VariantNotFound)
+    }
+}
+
+impl Theme {
+    /// Solarized accent colors
+    pub fn solarized() -> Self {
+        let c = Color::Ansi256;
+        Self {
+            styles: StyleSet {
+                okay: c(64).into(),     // green
+                warn: c(136).into(),    // yellow
+                error: c(160).into(),   // red
+                info: c(33).into(),     // blue
+                note: c(37).into(),     // cyan
+                debug: c(61).into(),    // violet
+                trace: c(240).into(),   // base01
+                magic: c(125).into(),   // magenta
+                silly: c(166).into(),   // orange
+                devlog: c(160).into(),
+            },
+            boxes: c(245).into(),       // base1
+            banner: Style::new().fg(c(33)).bold(),
+            headers: Style::new().fg(c(37)).bold(),
+            ..Self::default()
+        }
+    }
+
+    /// Bold, bright colors for low-vision users and washed-out terminals
+    pub fn high_contrast() -> Self {
+        let bright = |color: Color| Style::new().fg(color).intense().bold();
+        Self {
+            styles: StyleSet {
+                okay: bright(Color::Green),
+                warn: bright(Color::Yellow),
+                error: bright(Color::Red).underline(),
+                info: bright(Color::Cyan),
+                note: bright(Color::Cyan),
+                debug: bright(Color::Magenta),
+                trace: bright(Color::White),
+                magic: bright(Color::Magenta),
+                silly: bright(Color::Magenta),
+                devlog: bright(Color::Red),
+            },
+            boxes: bright(Color::White),
+            banner: bright(Color::Yellow),
+            headers: bright(Color::White).underline(),
+            ..Self::default()
+        }
+    }
+
+    /// No colors at all; severity is carried by weight instead
+    pub fn monochrome() -> Self {
+        Self {
+            styles: StyleSet {
+                okay: Style::new(),
+                warn: Style::new().bold(),
+                error: Style::new().bold().underline(),
+                info: Style::new(),
+                note: Style::new().italic(),
+                debug: Style::new().dimmed(),
+                trace: Style::new().dimmed(),
+                magic: Style::new().italic(),
+                silly: Style::new().dimmed().italic(),
+                devlog: Style::new().bold(),
+            },
+            boxes: Style::new(),
+            banner: Style::new().bold(),
+            headers: Style::new().bold().underline(),
+            ..Self::default()
+        }
+    }
+
+    /// A preset by name, ignoring case: `default`, `solarized`,
+    /// `high-contrast` (or `high_contrast`) and `monochrome`
+    pub fn named(name: &str) -> Option<Self> {
+        match name.trim().to_ascii_lowercase().replace('_', "-").as_str() {
+            "default" => Some(Self::default()),
+            "solarized" => Some(Self::solarized()),
+            "high-contrast" => Some(Self::high_contrast()),
+            "monochrome" | "mono" => Some(Self::monochrome()),
+            _ => None,
+        }
+    }
+
+    /// The preset named by `STDERR_THEME`, if it names one
+    pub fn from_env() -> Option<Self> {
+        env("STDERR_THEME").ok().and_then(|name| Self::named(&name))
+    }
+
+    // A theme's own context banner replaces the logger's; otherwise the
+    // banner keeps its fill and template, but follows the theme's banner
+    // color (plain white when that has none)
+    pub(crate) fn apply_banner(&self, context: &mut ContextBannerStyle) {
+        match &self.context {
+            Some(style) => *context = style.clone(),
+            None => context.color = self.banner.spec().fg().copied().unwrap_or(Color::White),
+        }
+    }
+}
+
+/// Look of the banner printed when the context changes. Set it on its own
+/// as below, or as a [`Theme`]'s `context` (which also loads from theme
+/// files with the `serde` feature)
+///
+/// ```
+/// use stderr::{Color, ContextBannerStyle, Stderr};
+///
+/// let mut log = Stderr::builder()
+///     .context_banner(ContextBannerStyle {
+///         fill: '=',
+///         color: Color::PURPLE,
+///         max_width: Some(60),
+///         template: " [{}] ".into(),
+///     })
+///     .build();
+/// log.set_context("@myapp.VAR.config"); // ========= [@myapp.VAR.config] =========
+/// ```
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ContextBannerStyle {
+    pub fill: char,
+    #[cfg_attr(feature = "serde", serde(with = "crate::esc::style::serde_color"))]
+    pub color: Color,
+    /// Never wider than this (60 by default); `None` follows the layout width
+    pub max_width: Option<usize>,
+    /// Title text; `{}` is replaced by the context
+    pub template: String,
+}
+
+impl Default for ContextBannerStyle {
+    fn default() -> Self {
+        Self {
+            fill: '-',
+            color: ESC::BLUE,
+            max_width: Some(60),
+            template: " Context: {} ".to_string(),
+        }
+    }
+}
+
+impl Default for Stderr {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Fluent builder for `Stderr`, created via `Stderr::builder()`.
+///
+/// Starts from the environment configuration (`QUIET_MODE`, `DEBUG_MODE`, ...),
+/// so each call only overrides what the application cares about.
+///
+/// ```
+/// use stderr::Stderr;
+///
+/// let mut log = Stderr::builder()
+///     .quiet(false)
+///     .debug(true)
+///     .label("myapp")
+///     .build();
+/// log.debug("visible because debug was enabled");
+/// ```
+#[derive(Debug, Clone)]
+pub struct StderrBuilder {
+    config: StderrConfig,
+    label: Option<String>,
+    theme: Theme,
+    context_style: ContextBannerStyle,
+    flush_policy: FlushPolicy,
+    width: Option<usize>,
+    width_policy: WidthPolicy,
+    #[cfg(feature = "formatting")]
+    box_max_width: Option<usize>,
+    soft_wrap: SoftWrap,
+    unicode: bool,
+    ci: Option<CiVendor>,
+    ci_profile: Option<bool>,
+    ci_annotations: bool,
+    target: Option<SharedTarget>,
+}
+
+impl Default for StderrBuilder {
+    fn default() -> Self {
+        let builder = Self {
+            config: StderrConfig::from_env(),
+            label: None,
+            theme: Theme::default(),
+            context_style: ContextBannerStyle::default(),
+            flush_policy: FlushPolicy::default(),
+            width: None,
+            width_policy: WidthPolicy::default(),
+            #[cfg(feature = "formatting")]
+            box_max_width: None,
+            soft_wrap: SoftWrap::default(),
+            unicode: supports_unicode(),
+            ci: CiVendor::detect(),
+            ci_profile: None,
+            ci_annotations: false,
+            target: None,
+        };
+        match Theme::from_env() {
+            Some(theme) => builder.theme(theme),
+            None => builder,
+        }
+    }
+}
+
+impl StderrBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Replaces the whole configuration
+    pub fn config(mut self, config: StderrConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    pub fn quiet(mut self, quiet: bool) -> Self {
+        self.config.set_flag(OptionFlag::Quiet, quiet);
+        self
+    }
+
+    /// Most verbose level that still prints in quiet mode, e.g. `Warn` to
+    /// keep warnings as well as errors
+    pub fn quiet_floor(mut self, level: LogLevel) -> Self {
+        self.config.quiet_floor = level;
+        self
+    }
+
+    /// Mutes the terminal completely, errors included
+    pub fn silent(mut self, silent: bool) -> Self {
+        self.config.silent = silent;
+        self
+    }
+
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.config.set_flag(OptionFlag::Debug, debug);
+        self
+    }
+
+    pub fn trace(mut self, trace: bool) -> Self {
+        self.config.set_flag(OptionFlag::Trace, trace);
+        self
+    }
+
+    pub fn silly(mut self, silly: bool) -> Self {
+        self.config.set_flag(OptionFlag::Silly, silly);
+        self
+    }
+
+    pub fn dev(mut self, dev: bool) -> Self {
+        self.config.set_flag(OptionFlag::Dev, dev);
+        self
+    }
+
+    /// Enables every level up to `max` in verbosity; see [`StderrConfig::set_level`]
+    pub fn level(mut self, max: LogLevel) -> Self {
+        self.config.set_level(max);
+        self
+    }
+
+    /// Replaces the set of enabled levels
+    pub fn levels(mut self, enabled: LevelMask) -> Self {
+        self.config.enabled = enabled;
+        self
+    }
+
+    pub fn enable(mut self, level: LogLevel) -> Self {
+        self.config.enable(level);
+        self
+    }
+
+    pub fn disable(mut self, level: LogLevel) -> Self {
+        self.config.disable(level);
+        self
+    }
+
+    /// Routes one level; see [`StderrConfig::set_route`]
+    pub fn route(mut self, level: LogLevel, route: Route) -> Self {
+        self.config.set_route(level, route);
+        self
+    }
+
+    pub fn color(mut self, color: ColorMode) -> Self {
+        self.config.color = color;
+        self
+    }
+
+    pub fn label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// Replaces the whole theme; see [`Theme`]
+    pub fn theme(mut self, theme: Theme) -> Self {
+        theme.apply_banner(&mut self.context_style);
+        self.theme = theme;
+        self
+    }
+
+    pub fn glyphs(mut self, glyphs: GlyphSet) -> Self {
+        self.theme.glyphs = glyphs;
+        self
+    }
+
+    /// Replaces all per-level prefix styles
+    pub fn styles(mut self, styles: StyleSet) -> Self {
+        self.theme.styles = styles;
+        self
+    }
+
+    /// Overrides the prefix style for one level
+    pub fn level_style(mut self, level: LogLevel, style: impl Into<Style>) -> Self {
+        self.theme.styles.set(level, style);
+        self
+    }
+
+    pub fn context_banner(mut self, style: ContextBannerStyle) -> Self {
+        self.context_style = style;
+        self
+    }
+
+    /// Whether context changes print a banner (default on)
+    pub fn context_banners(mut self, on: bool) -> Self {
+        self.config.context_banners = on;
+        self
+    }
+
+    /// Writes every message as a JSON line instead of styled text
+    ///
+    /// ```
+    /// use stderr::{OutputFormat, Stderr};
+    ///
+    /// let mut log = Stderr::builder().format(OutputFormat::Json).label("api").build();
+    /// log.info("listening on :8080");
+    /// // {"timestamp":"…","level":"info","message":"listening on :8080","label":"api"}
+    /// ```
+    pub fn format(mut self, format: OutputFormat) -> Self {
+        self.config.format = format;
+        self
+    }
+
+    /// How confirmations answer when nobody can be asked
+    ///
+    /// ```
+    /// use stderr::{ConfirmPolicy, Stderr};
+    ///
+    /// let mut log = Stderr::builder().quiet(true).confirm_policy(ConfirmPolicy::FailClosed).build();
+    /// assert_eq!(log.confirm("Drop the database?").unwrap(), None);
+    /// ```
+    pub fn confirm_policy(mut self, policy: ConfirmPolicy) -> Self {
+        self.config.confirm_policy = Some(policy);
+        self
+    }
+
+    /// Tags every line with a dim `(context)` after its prefix
+    pub fn show_context_inline(mut self, on: bool) -> Self {
+        self.config.show_context_inline = on;
+        self
+    }
+
+    pub fn flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.flush_policy = policy;
+        self
+    }
+
+    /// Lays out against a fixed width instead of the terminal's
+    pub fn width(mut self, columns: usize) -> Self {
+        self.width = Some(columns);
+        self
+    }
+
+    pub fn width_policy(mut self, policy: WidthPolicy) -> Self {
+        self.width_policy = policy;
+        self
+    }
+
+    /// Caps how wide boxes get; content is word-wrapped to fit. Boxes use
+    /// the layout width by default.
+    #[cfg(feature = "formatting")]
+    pub fn box_max_width(mut self, columns: usize) -> Self {
+        self.box_max_width = Some(columns);
+        self
+    }
+
+    /// Wraps long messages under their prefix (off by default)
+    pub fn soft_wrap(mut self, wrap: SoftWrap) -> Self {
+        self.soft_wrap = wrap;
+        self
+    }
+
+    /// Allows or forbids Unicode box drawing; detected with
+    /// [`supports_unicode`] by default. Without it boxes, help and flag
+    /// tables use [`BorderStyle::Ascii`](crate::BorderStyle::Ascii).
+    pub fn unicode(mut self, on: bool) -> Self {
+        self.unicode = on;
+        self
+    }
+
+    /// Overrides CI detection; `None` disables CI log markup
+    pub fn ci(mut self, ci: Option<CiVendor>) -> Self {
+        self.ci = ci;
+        self
+    }
+
+    /// Forces the plain-CI profile on or off (see [`Stderr::is_ci_profile`]).
+    /// By default it is on when a CI environment is detected that has no
+    /// dedicated adapter.
+    pub fn ci_profile(mut self, on: bool) -> Self {
+        self.ci_profile = Some(on);
+        self
+    }
+
+    /// Adds a GitHub Actions annotation (`::warning file=..,line=..::msg`)
+    /// after each warning and error line, so they show up inline on pull
+    /// requests. Off by default; only applies under GitHub Actions.
+    pub fn ci_annotations(mut self, on: bool) -> Self {
+        self.ci_annotations = on;
+        self
+    }
+
+    /// Sends output to `writer` instead of the process's stderr, as
+    /// [`Stderr::with_writer`] does on a built logger. Colors are written as
+    /// ANSI escapes, so wrap plain writers in `termcolor::Ansi` or
+    /// `termcolor::NoColor`.
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    /// use termcolor::NoColor;
+    ///
+    /// let mut log = Stderr::builder().target(NoColor::new(std::io::stdout())).build();
+    /// log.info("on stdout");
+    /// ```
+    pub fn target(mut self, writer: impl WriteColor + Send + 'static) -> Self {
+        self.target = Some(SharedTarget::new(writer));
+        self
+    }
+
+    pub fn build(self) -> Stderr {
+        let mut writer = match self.target {
+            Some(target) => Output::custom(Box::new(target), self.config.color_choice()),
+            None => Output::stderr(self.config.color_choice()),
+        };
+        writer.set_policy(self.flush_policy);
+        let width = Width::new(self.width, self.width_policy);
+        #[cfg(feature = "formatting")]
+        let width = width.with_box_max(self.box_max_width);
+        Stderr {
+            config: self.config,
+            writer,
+            width,
+            soft_wrap: self.soft_wrap,
+            label: self.label,
+            contexts: Vec::new(),
+            indent: 0,
+            theme: self.theme,
+            context_style: self.context_style,
+            unicode: self.unicode,
+            ci_profile: self.ci_profile.unwrap_or_else(|| self.ci.is_none() && ci::is_ci()),
+            ci: self.ci,
+            ci_annotations: self.ci_annotations,
+            caller: None,
+            scratch: String::new(),
+            prefix_buf: Vec::new(),
+            sinks: Vec::new(),
+            status: None,
+            status_drawn: false,
+            block_depth: 0,
+            #[cfg(feature = "formatting")]
+            open_section: None,
+            #[cfg(feature = "trace")]
+            last_trace_func: None,
+        }
+    }
+}
+
+impl Stderr {
+    /// Creates a new logger with environment-based configuration
+    pub fn new() -> Self {
+        Self::builder().build()
+    }
+
+    /// Creates logger with custom configuration
+    pub fn with_config(config: StderrConfig) -> Self {
+        Self::builder().config(config).build()
+    }
+
+    /// Starts a fluent builder; see [`StderrBuilder`]
+    pub fn builder() -> StderrBuilder {
+        StderrBuilder::new()
+    }
+
+    /// Sets how colors are chosen, overriding `NO_COLOR` / `CLICOLOR*`
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    /// use termcolor::ColorChoice;
+    ///
+    /// let mut log = Stderr::new().with_color_choice(ColorChoice::Never);
+    /// log.info("no escape codes, even on a terminal");
+    /// ```
+    pub fn with_color_choice(mut self, choice: ColorChoice) -> Self {
+        self.set_color_mode(choice.into());
+        self
+    }
+
+    /// Plain output: no escape codes, and ASCII in place of glyphs, box
+    /// borders and trace trees, so output piped into files stays plain text
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new().force_plain();
+    /// log.okay("saved");   // [+] saved
+    /// ```
+    pub fn force_plain(mut self) -> Self {
+        self.set_plain(true);
+        self
+    }
 
-impl Default for Stderr {
-    fn default() -> Self {
-        Self::new()
+    /// Turns plain mode on or off on a live logger
+    pub fn set_plain(&mut self, plain: bool) {
+        self.config.plain = plain;
+        self.writer.set_color_choice(self.config.color_choice());
     }
-}
 
-impl Stderr {
-    /// Creates a new logger with environment-based configuration
-    pub fn new() -> Self {
-        Self {
-            config: StderrConfig::from_env(),
-            writer: StandardStream::stderr(ColorChoice::Auto),
-            width: term_width(),
-            label: None,
-            current_context: None,
-            glyphs: GlyphSet::default(),
-            #[cfg(feature = "trace")]
-            last_trace_func: None,
-        }
+    pub fn is_plain(&self) -> bool {
+        self.config.plain
     }
 
-    /// Creates logger with custom configuration
-    pub fn with_config(config: StderrConfig) -> Self {
-        Self {
-            config,
-            writer: StandardStream::stderr(ColorChoice::Auto),
-            width: term_width(),
-            label: None,
-            current_context: None,
-            glyphs: GlyphSet::default(),
-            #[cfg(feature = "trace")]
-            last_trace_func: None,
-        }
+    /// Allows or forbids Unicode box drawing on a live logger, e.g. for a
+    /// serial console the locale doesn't describe
+    ///
+    /// ```
+    /// use stderr::{BorderStyle, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_unicode(false);
+    /// assert!(log.render_boxed("ok", BorderStyle::Rounded, false).starts_with('+'));
+    /// ```
+    pub fn set_unicode(&mut self, on: bool) {
+        self.unicode = on;
+    }
+
+    // Plain output and non-Unicode terminals get ASCII box drawing
+    pub(crate) fn ascii_only(&self) -> bool {
+        self.config.plain || !self.unicode
+    }
+
+    // Plain mode swaps non-ASCII fill characters for `-`
+    pub(crate) fn fill(&self, fill: char) -> char {
+        if self.ascii_only() && !fill.is_ascii() { '-' } else { fill }
+    }
+
+    /// Replaces the theme: level colors and glyphs, the prefix shape, and
+    /// the styles of boxes, banners and table headers
+    ///
+    /// ```
+    /// use stderr::{Color, LogLevel, PrefixStyle, Stderr, Style, Theme};
+    ///
+    /// let mut theme = Theme::default();
+    /// theme.prefix = PrefixStyle::Bare;
+    /// theme.styles.set(LogLevel::Info, Style::new().fg(Color::PURPLE));
+    /// theme.boxes = Style::new().fg(Color::GREY);
+    ///
+    /// let mut log = Stderr::new().with_theme(theme);
+    /// log.info("themed");   // λ themed
+    /// ```
+    pub fn with_theme(mut self, theme: Theme) -> Self {
+        self.set_theme(theme);
+        self
+    }
+
+    /// Switches the theme on a live logger
+    pub fn set_theme(&mut self, theme: Theme) {
+        theme.apply_banner(&mut self.context_style);
+        self.theme = theme;
+    }
+
+    pub fn theme(&self) -> &Theme {
+        &self.theme
     }
 
     /// Customize the glyph set for this logger
     pub fn with_glyphs(mut self, glyphs: GlyphSet) -> Self {
-        self.glyphs = glyphs;
+        self.theme.glyphs = glyphs;
         self
     }
 
     /// Set individual glyphs
     pub fn set_glyph(&mut self, level: LogLevel, glyph: &'static str) {
-        match level {
-            LogLevel::Info => self.glyphs.info = glyph,
-            LogLevel::Warn => self.glyphs.warn = glyph,
-            LogLevel::Error => self.glyphs.error = glyph,
-            LogLevel::Okay => self.glyphs.okay = glyph,
-            LogLevel::Trace => self.glyphs.trace = glyph,
-            LogLevel::Debug => self.glyphs.debug = glyph,
-            LogLevel::Magic => self.glyphs.magic = glyph,
-            _ => {} // Others don't have configurable glyphs yet
-        }
+        self.theme.glyphs.set(level, glyph);
+    }
+
+    /// Sets the glyph printed before banner titles; `""` for none
+    pub fn set_banner_glyph(&mut self, glyph: &'static str) {
+        self.theme.glyphs.set_banner_glyph(glyph);
+    }
+
+    /// Sends output to `writer` instead of the process's stderr: a file, stdout,
+    /// or a buffer in tests. Colors are written as ANSI escapes, so wrap plain
+    /// writers in `termcolor::Ansi` (colored) or `termcolor::NoColor`.
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    /// use termcolor::Ansi;
+    ///
+    /// let mut log = Stderr::new().with_writer(Box::new(Ansi::new(std::io::stdout())));
+    /// log.info("on stdout");
+    /// ```
+    pub fn with_writer(mut self, writer: Box<dyn WriteColor + Send>) -> Self {
+        self.set_writer(writer);
+        self
+    }
+
+    /// Replaces the output destination on a live logger, flushing what was
+    /// buffered for the old one
+    pub fn set_writer(&mut self, writer: Box<dyn WriteColor + Send>) {
+        let policy = self.writer.policy();
+        let truecolor = self.writer.truecolor();
+        let _ = self.writer.flush();
+        self.writer = Output::custom(writer, self.config.color_choice());
+        self.writer.set_policy(policy);
+        self.writer.set_truecolor(truecolor);
+    }
+
+    /// Whether RGB colors (e.g. from [`Color::from_hex`](crate::Color::from_hex))
+    /// are written as-is or mapped to the nearest 256-color entry. Defaults
+    /// to what `COLORTERM` advertises.
+    ///
+    /// ```
+    /// use stderr::{Color, Stderr, Style};
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_truecolor(false);
+    /// log.print_with_prefix(Style::new().fg(Color::from_hex("#ff8800").unwrap()), "~", "orange-ish").unwrap();
+    /// ```
+    pub fn set_truecolor(&mut self, on: bool) {
+        self.writer.set_truecolor(on);
+    }
+
+    /// Customize the per-level prefix styles for this logger
+    pub fn with_styles(mut self, styles: StyleSet) -> Self {
+        self.theme.styles = styles;
+        self
+    }
+
+    /// Set one level's prefix style
+    pub fn set_level_style(&mut self, level: LogLevel, style: impl Into<Style>) {
+        self.theme.styles.set(level, style);
     }
 
     // --- Label Management ---
@@ -216,32 +1824,196 @@ impl Stderr {
 
     // --- Context Management ---
     
-    /// Set context and show banner if it changed
+    /// Replaces the innermost context and shows a banner if it changed
     pub fn set_context(&mut self, context: &str) {
-        let context_str = context.to_string();
-        
         // Only show banner if context actually changed
-        if self.current_context.as_ref() != Some(&context_str) {
-            self.context_banner(&context_str);
-            self.current_context = Some(context_str);
+        if self.current_context() != Some(context) {
+            self.context_banner(context);
+        }
+        match self.contexts.last_mut() {
+            Some(top) => *top = context.to_string(),
+            None => self.contexts.push(context.to_string()),
+        }
+    }
+
+    /// Enters a nested context, showing its banner if it differs from the
+    /// current one
+    pub fn push_context(&mut self, context: &str) {
+        if self.current_context() != Some(context) {
+            self.context_banner(context);
+        }
+        self.contexts.push(context.to_string());
+    }
+
+    /// Leaves the innermost context and shows the outer one's banner again
+    pub fn pop_context(&mut self) -> Option<String> {
+        let depth = self.contexts.len().checked_sub(1)?;
+        let popped = self.contexts[depth].clone();
+        self.restore_context(depth);
+        Some(popped)
+    }
+
+    /// Pushes `context` until the returned guard is dropped, which restores
+    /// the outer context (and its banner), even if inner code pushed more
+    /// without popping.
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_context("deploy");
+    /// {
+    ///     let mut scope = log.context_scope("migrate");
+    ///     scope.info("running migrations");
+    ///     assert_eq!(scope.current_context(), Some("migrate"));
+    /// }
+    /// assert_eq!(log.current_context(), Some("deploy"));
+    /// ```
+    pub fn context_scope(&mut self, context: &str) -> ContextScope<'_> {
+        self.push_context(context);
+        let depth = self.contexts.len() - 1;
+        ContextScope { stderr: self, depth }
+    }
+
+    /// Indents prefixed lines one more level until the guard drops; guards
+    /// nest
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.info("deploying");           // [i] deploying
+    /// {
+    ///     let mut step = log.indent();
+    ///     step.info("uploading");      //   [i] uploading
+    ///     let mut sub = step.indent();
+    ///     sub.okay("3 files");         //     [✔] 3 files
+    ///     assert_eq!(sub.indent_level(), 2);
+    /// }
+    /// assert_eq!(log.indent_level(), 0);
+    /// ```
+    pub fn indent(&mut self) -> IndentScope<'_> {
+        let depth = self.indent;
+        self.set_indent(depth + 1);
+        IndentScope { stderr: self, depth }
+    }
+
+    /// Current indentation depth (open [`indent`](Self::indent) guards and
+    /// section scopes)
+    pub fn indent_level(&self) -> usize {
+        self.indent
+    }
+
+    /// Unwinds the stack to `depth` entries, re-announcing the context that
+    /// becomes current
+    pub(crate) fn restore_context(&mut self, depth: usize) {
+        if depth >= self.contexts.len() { return; }
+        let left = self.contexts.split_off(depth);
+        if let Some(outer) = self.contexts.last().cloned() {
+            if left.first() != Some(&outer) {
+                self.context_banner(&outer);
+            }
         }
     }
 
-    /// Clear current context
+    /// Clears the whole context stack
     pub fn clear_context(&mut self) {
-        self.current_context = None;
+        self.contexts.clear();
+    }
+
+    /// Number of contexts on the stack
+    pub fn context_depth(&self) -> usize {
+        self.contexts.len()
+    }
+
+    /// The CI system whose log markup is emitted, if any
+    pub fn ci(&self) -> Option<CiVendor> {
+        self.ci
+    }
+
+    /// Whether the plain-CI profile is active: every line is timestamped and
+    /// its prefix padded, and interactive prompts fail immediately instead of
+    /// waiting on input that will never come.
+    pub fn is_ci_profile(&self) -> bool {
+        self.ci_profile
+    }
+
+    pub fn set_ci_profile(&mut self, on: bool) {
+        self.ci_profile = on;
+    }
+
+    /// Turns GitHub Actions annotations for warnings and errors on or off
+    /// (see [`StderrBuilder::ci_annotations`])
+    pub fn set_ci_annotations(&mut self, on: bool) {
+        self.ci_annotations = on;
+    }
+
+    /// Get the current (innermost) context, if any
+    pub fn current_context(&self) -> Option<&str> {
+        self.contexts.last().map(String::as_str)
+    }
+
+    /// Changes how context banners look
+    pub fn set_context_banner(&mut self, style: ContextBannerStyle) {
+        self.context_style = style;
+    }
+
+    pub fn set_context_banners(&mut self, on: bool) {
+        self.config.context_banners = on;
+    }
+
+    pub fn set_show_context_inline(&mut self, on: bool) {
+        self.config.show_context_inline = on;
+    }
+
+    /// Sets how confirmations answer when nobody can be asked
+    pub fn set_confirm_policy(&mut self, policy: ConfirmPolicy) {
+        self.config.confirm_policy = Some(policy);
+    }
+
+    /// Switches between styled text and JSON lines
+    pub fn set_format(&mut self, format: OutputFormat) {
+        self.config.format = format;
+    }
+
+    pub(crate) fn is_json(&self) -> bool {
+        self.config.format == OutputFormat::Json
+    }
+
+    // One message as a JSON line
+    pub(crate) fn write_json(&mut self, level: LogLevel, msg: &str) -> io::Result<()> {
+        let context = self.contexts.last().map(String::as_str);
+        let line = super::json::json_line(level, msg, self.label.as_deref(), context);
+        writeln!(&mut self.writer, "{}", line)
+    }
+
+    // Block output (a table, a box, a banner...) as a JSON line; blank
+    // blocks are dropped
+    pub(crate) fn write_json_block(&mut self, kind: &str, text: &str) -> io::Result<()> {
+        if text.trim().is_empty() { return Ok(()); }
+        let context = self.contexts.last().map(String::as_str);
+        let line = super::json::json_block(kind, text, self.label.as_deref(), context);
+        writeln!(&mut self.writer, "{}", line)
     }
 
     /// Display context change banner
     fn context_banner(&mut self, context: &str) {
-        if self.check_flag(OptionFlag::Quiet) { return; }
+        if self.check_flag(OptionFlag::Quiet) || !self.config.context_banners || self.is_json() { return; }
         
-        let msg = format!(" Context: {} ", context);
-        let width = self.width.min(60); // Cap banner width
-        let msg_len = msg.chars().count();
+        let style = &self.context_style;
+        let fill = self.fill(style.fill);
+        let width = style.max_width.map_or(self.width.layout(), |max| self.width.layout().min(max));
+        // A long context loses its middle so the banner keeps a few fill
+        // characters on each side
+        let frame = visible_width(&style.template.replace("{}", ""));
+        let room = width.saturating_sub(frame + 2 * BANNER_MIN_FILL);
+        let context = if visible_width(context) > room && room > 0 { truncate(context, room, Ellipsis::Middle) } else { context.to_string() };
+        let msg = style.template.replace("{}", &context);
+        let msg_len = visible_width(&msg);
         
         if msg_len >= width {
-            let _ = writeln!(&mut self.writer, "--- {} ---", context);
+            let result = self.write_block("context", |log| writeln!(&mut log.writer, "{0}{0}{0} {1} {0}{0}{0}", fill, context));
+            self.handle_write_error(result);
             return;
         }
         
@@ -249,44 +2021,232 @@ impl Stderr {
         let left_fill = total_fill / 2;
         let right_fill = total_fill - left_fill;
         
-        let left_bar = "-".repeat(left_fill);
-        let right_bar = "-".repeat(right_fill);
+        let left_bar = fill.to_string().repeat(left_fill);
+        let right_bar = fill.to_string().repeat(right_fill);
         
-        let _ = self.set_fg(ESC::BLUE);
-        let _ = writeln!(&mut self.writer, "{}{}{}", left_bar, msg, right_bar);
-        let _ = self.reset();
+        let color = style.color;
+        let result = self.write_block("context", |log| {
+            log.set_fg(color)?;
+            writeln!(&mut log.writer, "{}{}{}", left_bar, msg, right_bar)?;
+            log.writer.reset()
+        });
+        self.handle_write_error(result);
     }
 
     // --- Configuration ---
     
     pub fn set_quiet(&mut self, quiet: bool) {
-        self.config.quiet = quiet;
+        self.config.set_flag(OptionFlag::Quiet, quiet);
+    }
+
+    pub fn set_quiet_floor(&mut self, level: LogLevel) {
+        self.config.quiet_floor = level;
+    }
+
+    pub fn set_silent(&mut self, silent: bool) {
+        self.config.silent = silent;
+    }
+
+    /// Shows every level up to `max` in verbosity and hides the rest
+    pub fn set_level(&mut self, max: LogLevel) {
+        self.config.set_level(max);
+    }
+
+    /// The most verbose level currently shown, if any
+    pub fn level(&self) -> Option<LogLevel> {
+        self.config.level()
     }
 
     pub fn set_debug(&mut self, debug: bool) {
-        self.config.debug = debug;
+        self.config.set_flag(OptionFlag::Debug, debug);
     }
 
     pub fn set_trace(&mut self, trace: bool) {
-        self.config.trace = trace;
+        self.config.set_flag(OptionFlag::Trace, trace);
     }
 
     pub fn set_silly(&mut self, silly: bool) {
-        self.config.silly = silly;
+        self.config.set_flag(OptionFlag::Silly, silly);
+    }
+
+    /// Switches color handling on a live logger
+    pub fn set_color_mode(&mut self, color: ColorMode) {
+        self.config.color = color;
+        self.writer.set_color_choice(self.config.color_choice());
+    }
+
+    /// Replaces the whole configuration, including its color mode
+    pub fn set_config(&mut self, config: StderrConfig) {
+        if config.color_choice() != self.config.color_choice() {
+            self.writer.set_color_choice(config.color_choice());
+        }
+        self.config = config;
     }
 
     pub fn set_dev(&mut self, dev: bool) {
-        self.config.dev = dev;
+        self.config.set_flag(OptionFlag::Dev, dev);
     }
 
     pub fn check_flag(&self, flag: OptionFlag) -> bool {
-        match flag {
-            OptionFlag::Quiet => self.config.quiet,
-            OptionFlag::Dev => self.config.dev,
-            OptionFlag::Debug => self.config.debug,
-            OptionFlag::Trace => self.config.trace,
-            OptionFlag::Silly => self.config.silly,
+        self.config.flag(flag)
+    }
+
+    /// Turns a single level on, e.g. trace without debug
+    pub fn enable(&mut self, level: LogLevel) {
+        self.config.enable(level);
+    }
+
+    /// Turns a single level off, e.g. hide `note` chatter
+    pub fn disable(&mut self, level: LogLevel) {
+        self.config.disable(level);
+    }
+
+    /// Shows, hides or sends one level to sinks only, at runtime
+    pub fn set_route(&mut self, level: LogLevel, route: Route) {
+        self.config.set_route(level, route);
+    }
+
+    pub fn route(&self, level: LogLevel) -> Route {
+        self.config.route(level)
+    }
+
+    // --- Sinks ---
+
+    /// Sends messages to `sink` as well, at the same levels the terminal
+    /// shows (following later `enable`/`disable`/`set_debug` calls).
+    pub fn add_sink(&mut self, sink: impl Sink + 'static) {
+        self.sinks.push(SinkEntry { sink: Box::new(sink), levels: None });
+    }
+
+    /// Sends messages at `levels` to `sink`, independent of what the
+    /// terminal shows (and of quiet mode).
+    pub fn add_sink_filtered(&mut self, sink: impl Sink + 'static, levels: LevelMask) {
+        self.sinks.push(SinkEntry { sink: Box::new(sink), levels: Some(levels) });
+    }
+
+    /// Removes all sinks
+    pub fn clear_sinks(&mut self) {
+        self.sinks.clear();
+    }
+
+    /// Also sends every message to syslog (level-mapped, ANSI-stripped)
+    #[cfg(all(unix, feature = "syslog"))]
+    pub fn add_syslog(&mut self, sink: super::sinks::SyslogSink) {
+        self.add_sink(sink);
+    }
+
+    /// Also sends every message to systemd-journald with structured fields
+    #[cfg(all(unix, feature = "journald"))]
+    pub fn add_journald(&mut self, sink: super::sinks::JournaldSink) {
+        self.add_sink(sink);
+    }
+
+    /// Also exports every message as an OpenTelemetry log record
+    #[cfg(feature = "otel")]
+    pub fn add_otel(&mut self, sink: super::otel::OtelSink) {
+        self.add_sink(sink);
+    }
+
+    // --- Buffering ---
+
+    /// Sets when buffered output is written to the terminal
+    pub fn with_flush_policy(mut self, policy: FlushPolicy) -> Self {
+        self.writer.set_policy(policy);
+        self
+    }
+
+    pub fn set_flush_policy(&mut self, policy: FlushPolicy) {
+        self.writer.set_policy(policy);
+    }
+
+    pub fn flush_policy(&self) -> FlushPolicy {
+        self.writer.policy()
+    }
+
+    /// Renders everything `f` prints into one buffer and writes it in a
+    /// single call at the end, so composite output (banner + table +
+    /// footer) is never interleaved with other threads' lines.
+    ///
+    /// Sinks still receive each message as it is logged. Don't prompt for
+    /// input inside a batch; the prompt would only appear afterwards.
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.batch(|b| {
+    ///     b.info("3 services");
+    ///     b.okay("api");
+    ///     b.okay("worker");
+    ///     b.warn("cache (degraded)");
+    /// });
+    /// ```
+    pub fn batch<R>(&mut self, f: impl FnOnce(&mut Stderr) -> R) -> R {
+        self.writer.hold();
+        let result = f(self);
+        let written = self.writer.release();
+        self.handle_write_error(written);
+        result
+    }
+
+    /// Width that banners, boxes and tables are laid out against
+    pub fn width(&self) -> usize {
+        self.width.layout()
+    }
+
+    /// Whether layout is in narrow mode (under [`NARROW_WIDTH`](super::width::NARROW_WIDTH) columns)
+    pub fn is_narrow(&self) -> bool {
+        self.width.layout() < super::width::NARROW_WIDTH
+    }
+
+    /// Fixes the layout width, e.g. for output that is captured or piped
+    pub fn set_width(&mut self, columns: usize) {
+        self.width.set_fixed(Some(columns));
+    }
+
+    /// Goes back to following the terminal width
+    pub fn clear_width(&mut self) {
+        self.width.set_fixed(None);
+    }
+
+    /// `Fill` uses the whole width, `Cap(n)` at most `n` columns
+    pub fn set_width_policy(&mut self, policy: WidthPolicy) {
+        self.width.policy = policy;
+    }
+
+    /// Turns soft wrapping of long messages on or off
+    pub fn set_soft_wrap(&mut self, wrap: SoftWrap) {
+        self.soft_wrap = wrap;
+    }
+
+    /// Looks the terminal width up again on next use (e.g. after a resize)
+    pub fn refresh_width(&self) {
+        self.width.refresh();
+    }
+
+    /// Writes any buffered output to the terminal now, and flushes sinks
+    pub fn flush(&mut self) -> io::Result<()> {
+        for entry in &mut self.sinks {
+            entry.sink.flush()?;
         }
+        self.writer.flush()
+    }
+
+    /// Whether a message at `level` would currently go anywhere: the
+    /// terminal or at least one sink
+    #[inline]
+    pub fn is_enabled(&self, level: LogLevel) -> bool {
+        level.is_compiled_in() && (self.terminal_enabled(level) || self.sink_wants(level))
+    }
+
+    #[inline]
+    pub(crate) fn terminal_enabled(&self, level: LogLevel) -> bool {
+        self.config.shows(level)
+    }
+
+    fn sink_wants(&self, level: LogLevel) -> bool {
+        let logger_levels = self.config.sink_levels();
+        self.sinks.iter().any(|entry| entry.wants(level, logger_levels))
     }
 
     // --- Low-Level Output Methods ---
@@ -310,7 +2270,30 @@ impl Stderr {
 
     pub fn write(&mut self, msg: impl Display) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        write!(&mut self.writer, "{}", msg)
+        self.write_block("text", |log| write!(&mut log.writer, "{}", msg))
+    }
+
+    /// Writes `text` in `style` and resets, without a newline, for building
+    /// mixed-color lines piece by piece
+    ///
+    /// ```
+    /// use stderr::{Color, Stderr, Style};
+    ///
+    /// let mut log = Stderr::new();
+    /// log.write_styled(Style::new().fg(Color::RED).bold(), "fatal")?;
+    /// log.write(" in ")?;
+    /// Style::new().fg(Color::CYAN).apply_to(&mut log, "src/main.rs")?;
+    /// log.newline()?;
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn write_styled(&mut self, style: impl Into<Style>, text: impl Display) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let style = style.into();
+        self.write_block("text", |log| {
+            log.writer.set_color(style.spec())?;
+            write!(&mut log.writer, "{}", text)?;
+            log.writer.reset()
+        })
     }
 
     pub fn reset(&mut self) -> io::Result<()> {
@@ -320,80 +2303,378 @@ impl Stderr {
 
     pub fn newline(&mut self) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        writeln!(&mut self.writer)?;
-        Ok(())
+        self.write_block("text", |log| writeln!(&mut log.writer))
     }
 
     pub fn print(&mut self, msg: &str) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        writeln!(&mut self.writer, "{}", msg)?;
-        self.writer.reset()
+        self.write_block("text", |log| {
+            writeln!(&mut log.writer, "{}", msg)?;
+            log.writer.reset()
+        })
     }
 
-    pub fn print_with_prefix(&mut self, color: Color, prefix: impl Display, msg: &str) -> io::Result<()> {
+    /// A line with the given prefix, styled by `style` (a [`Style`] or a plain color)
+    pub fn print_with_prefix(&mut self, style: impl Into<Style>, prefix: impl Display, msg: &str) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
-        
-        self.set_fg(color)?;
-        let formatted_prefix = match &self.label {
-            Some(label) => format!("[{}][{}]", label, prefix),
-            None => format!("[{}]", prefix),
-        };
+        let style = style.into();
+        self.write_line(style.spec(), prefix, msg)
+    }
 
-        write!(&mut self.writer, "{} ", formatted_prefix)?;
-        writeln!(&mut self.writer, "{}", msg)?;
-        self.writer.reset()
+    // A prefixed line, for messages that already passed the level filter
+    // (which is what lets errors through in quiet mode)
+    fn write_line(&mut self, spec: &ColorSpec, prefix: impl Display, msg: &str) -> io::Result<()> {
+        self.write_block("log", |log| {
+            log.writer.set_color(spec)?;
+            let marker = if log.config.plain { log.soft_wrap.ascii_marker() } else { log.soft_wrap.marker() };
+            let brackets = log.theme.prefix.brackets();
+            if marker.is_some() || msg.contains('\n') {
+                log.write_hanging(spec, prefix, brackets, msg, marker)?;
+            } else {
+                log.write_prefix(prefix, brackets)?;
+                log.write_context_tag(spec)?;
+                write!(&mut log.writer, " {}", msg)?;
+            }
+            // Reset before the newline so the next line (possibly a CI
+            // service message) starts clean
+            log.writer.reset()?;
+            writeln!(&mut log.writer)
+        })
     }
 
     // --- Core Logging Methods ---
     
-    pub fn fatal(&mut self, msg: &str) -> ! {
-        let _ = self.error(msg);
+    pub fn fatal(&mut self, msg: impl Display) -> ! {
+        self.error(msg);
+        let _ = self.flush(); // exit skips destructors, so don't leave anything buffered
         std::process::exit(1);
     }
 
-    pub fn error(&mut self, msg: &str) {
-        let _ = self.print_with_prefix(ESC::RED, self.glyphs.error, msg);
+    #[track_caller]
+    pub fn error(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Error) { return; }
+        self.caller = Some(Location::caller());
+        self.emit(LogLevel::Error, msg);
+    }
+
+    #[track_caller]
+    pub fn warn(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Warn) { return; }
+        self.caller = Some(Location::caller());
+        self.emit(LogLevel::Warn, msg);
+    }
+
+    pub fn info(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Info) { return; }
+        self.emit(LogLevel::Info, msg);
+    }
+
+    pub fn okay(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Okay) { return; }
+        self.emit(LogLevel::Okay, msg);
+    }
+
+    pub fn note(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Note) { return; }
+        self.emit(LogLevel::Note, msg);
+    }
+
+    #[inline]
+    pub fn debug(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Debug) { return; }
+        self.emit(LogLevel::Debug, msg);
+    }
+
+    #[inline]
+    pub fn devlog(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::DevLog) { return; }
+        self.emit(LogLevel::DevLog, msg);
+    }
+
+    #[inline]
+    pub fn trace(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Trace) { return; }
+        self.emit(LogLevel::Trace, msg);
+    }
+
+    #[inline]
+    pub fn magic(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Magic) { return; }
+        self.emit(LogLevel::Magic, msg);
+    }
+
+    #[inline]
+    pub fn silly(&mut self, msg: impl Display) {
+        if !self.is_enabled(LogLevel::Silly) { return; }
+        self.emit(LogLevel::Silly, msg);
+    }
+
+    /// Reports progress on a long-running step.
+    ///
+    /// Under TeamCity this becomes a `progressMessage`, and under Azure DevOps
+    /// (when `percent` is given) a `task.setprogress` update; elsewhere it is
+    /// printed like `info` with the percentage appended.
+    pub fn progress(&mut self, msg: &str, percent: Option<u8>) {
+        if !self.is_enabled(LogLevel::Info) { return; }
+        if let Some(line) = self.ci.filter(|_| !self.is_json()).and_then(|vendor| ci::progress_message(vendor, msg, percent)) {
+            let result = self.write_block("progress", |log| writeln!(&mut log.writer, "{}", line));
+            self.handle_write_error(result);
+            return;
+        }
+        match percent {
+            Some(pct) => self.info(format_args!("{} ({}%)", msg, pct.min(100))),
+            None => self.info(msg),
+        }
+    }
+
+    /// Shows a transient status line at the bottom of the terminal.
+    ///
+    /// Messages are printed above it and it is redrawn underneath them. A
+    /// multi-line status (see [`MultiProgress`](crate::MultiProgress)) is
+    /// drawn as one block. The status is ignored when stderr is not a
+    /// terminal, in quiet mode and in the plain-CI profile.
+    pub fn set_status(&mut self, msg: &str) {
+        if self.check_flag(OptionFlag::Quiet) || self.ci_profile || self.is_json() || self.config.plain
+            || !self.writer.is_terminal() { return; }
+        self.hide_status();
+        let width = self.width.available().saturating_sub(1);
+        let lines: Vec<String> = msg.lines().map(|line| truncate_ansi(line, width)).collect();
+        self.status = Some(lines.join("\n"));
+        self.show_status();
+    }
+
+    /// Removes the status line
+    pub fn clear_status(&mut self) {
+        self.hide_status();
+        let _ = self.writer.flush();
+        self.status = None;
+    }
+
+    /// Clears the status line, runs `f`, then redraws it.
+    ///
+    /// Use this around anything that draws on the terminal by itself, e.g.
+    /// an `indicatif` progress bar or a child process inheriting stderr:
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// log.set_status("compiling 3/10");
+    /// log.suspend(|| eprintln!("drawn by someone else"));
+    /// log.clear_status();
+    /// ```
+    pub fn suspend<R>(&mut self, f: impl FnOnce() -> R) -> R {
+        self.hide_status();
+        let _ = self.writer.flush();
+        let result = f();
+        self.show_status();
+        result
+    }
+
+    pub(crate) fn hide_status(&mut self) {
+        if !self.status_drawn { return; }
+        self.status_drawn = false;
+        if let Some(status) = &self.status {
+            let _ = write!(&mut self.writer, "\r\x1b[2K");
+            for _ in 1..status.lines().count() {
+                let _ = write!(&mut self.writer, "\x1b[1A\x1b[2K");
+            }
+            self.writer.mark_line_start();
+        }
+    }
+
+    // Redraws the status, unless a block is still being written or the
+    // cursor is in the middle of a line
+    pub(crate) fn show_status(&mut self) {
+        if self.status_drawn || self.block_depth > 0 || !self.writer.at_line_start() { return; }
+        if let Some(status) = &self.status {
+            let _ = write!(&mut self.writer, "{}", status);
+            let _ = self.writer.flush();
+            self.status_drawn = true;
+        }
+    }
+
+    /// Called once a prompt's answer line has been read: the user's Enter
+    /// ended the line behind the writer's back, so the status line (lifted
+    /// off while the prompt was drawn) can come back.
+    #[cfg(feature = "interactive")]
+    pub(crate) fn prompt_answered(&mut self) {
+        self.writer.mark_line_start();
+        self.show_status();
+    }
+
+    /// Writes pre-rendered lines as one block
+    #[cfg(feature = "formatting")]
+    pub(crate) fn write_lines(&mut self, kind: &'static str, lines: &[String]) -> io::Result<()> {
+        self.write_block(kind, |log| {
+            for line in lines {
+                writeln!(&mut log.writer, "{}", line)?;
+            }
+            Ok(())
+        })
+    }
+
+    /// Runs `write` as one block of output: everything on the stream that
+    /// isn't a log line (banners, tables, boxes, raw prints, ...) goes
+    /// through here, so the status line is lifted off first and redrawn
+    /// once the block ends a line.
+    ///
+    /// In JSON mode the block's text is collected instead and written as
+    /// one `{"kind":…,"text":…}` record, so the stream stays valid JSON
+    /// lines.
+    pub(crate) fn write_block(&mut self, kind: &'static str, write: impl FnOnce(&mut Self) -> io::Result<()>) -> io::Result<()> {
+        if self.is_json() {
+            if self.block_depth > 0 { return write(self); }
+            self.block_depth += 1;
+            self.writer.start_capture();
+            let result = write(self);
+            let text = self.writer.end_capture();
+            self.block_depth -= 1;
+            result?;
+            return self.write_json_block(kind, &text);
+        }
+        self.hide_status();
+        // Log lines place their own indent (after the CI clock); everything
+        // else is indented line by line as it is written
+        if self.block_depth == 0 && kind != "log" {
+            self.writer.set_indent(self.indent_width());
+        }
+        self.block_depth += 1;
+        let result = write(self);
+        self.block_depth -= 1;
+        if self.block_depth == 0 {
+            self.writer.set_indent(0);
+        }
+        self.show_status();
+        result
     }
 
-    pub fn warn(&mut self, msg: &str) {
-        let _ = self.print_with_prefix(ESC::ORANGE, self.glyphs.warn, msg);
+    // Writes `[label][prefix]` (`[label]prefix` when `bracket` is false)
+    // straight to the stream, without building a String per line.
+    //
+    // The plain-CI profile timestamps it and pads it to the widest level
+    // prefix under the current label, so messages line up; custom prefixes
+    // wider than that go unpadded. There it returns the columns taken,
+    // padding included (0 otherwise); the width is needed up front, so the
+    // prefix is rendered into the reused `prefix_buf`.
+    fn write_prefix(&mut self, prefix: impl Display, bracket: bool) -> io::Result<usize> {
+        let indent = self.indent_width();
+        if !self.ci_profile {
+            write!(&mut self.writer, "{:indent$}", "")?;
+            return write_prefix_to(&mut self.writer, self.label.as_deref(), prefix, bracket).map(|_| 0);
+        }
+        let mut buf = std::mem::take(&mut self.prefix_buf);
+        buf.clear();
+        write_prefix_to(&mut buf, self.label.as_deref(), prefix, bracket)?;
+        let width = visible_width(&String::from_utf8_lossy(&buf));
+        let padded = width.max(self.level_prefix_width(bracket));
+
+        let result = write!(&mut self.writer, "{} {:indent$}", ci::clock(), "")
+            .and_then(|_| self.writer.write_all(&buf))
+            .and_then(|_| write!(&mut self.writer, "{:pad$}", "", pad = padded - width));
+        self.prefix_buf = buf;
+        result.map(|_| padded)
     }
 
-    pub fn info(&mut self, msg: &str) {
-        let _ = self.print_with_prefix(ESC::BLUE, self.glyphs.info, msg);
+    // Width of the widest level prefix (`[label][glyph]`) with the current
+    // label and glyphs
+    fn level_prefix_width(&self, bracket: bool) -> usize {
+        let glyph = LogLevel::ALL.iter().map(|&level| visible_width(self.style(level).1)).max().unwrap_or(0);
+        let label = self.label.as_deref().map_or(0, |label| visible_width(label) + 2);
+        label + glyph + if bracket { 2 } else { 0 }
     }
 
-    pub fn okay(&mut self, msg: &str) {
-        let _ = self.print_with_prefix(ESC::GREEN, self.glyphs.okay, msg);
+    // Columns of indentation from open section scopes
+    pub(crate) fn indent_width(&self) -> usize {
+        self.indent * INDENT_WIDTH
     }
 
-    pub fn note(&mut self, msg: &str) {
-        let _ = self.print_with_prefix(ESC::BLUE, "\u{2192}", msg); // →
+    // Changes the indentation depth; drawn elements shrink to fit beside it
+    pub(crate) fn set_indent(&mut self, depth: usize) {
+        self.indent = depth;
+        self.width.indent = self.indent_width();
     }
 
-    pub fn debug(&mut self, msg: &str) {
-        if !self.config.debug { return; }
-        let _ = self.print_with_prefix(ESC::CYAN, self.glyphs.debug, msg);
+    // The inline ` (context)` tag, dimmed; returns the columns it took
+    fn write_context_tag(&mut self, spec: &ColorSpec) -> io::Result<usize> {
+        if !self.config.show_context_inline { return Ok(0); }
+        let Some(context) = self.contexts.last() else { return Ok(0) };
+        let tag = truncate(context, CONTEXT_TAG_WIDTH, Ellipsis::Middle);
+        let width = visible_width(&tag) + 3;
+        self.writer.set_color(ColorSpec::new().set_dimmed(true))?;
+        write!(&mut self.writer, " ({})", tag)?;
+        self.writer.set_color(spec)?;
+        Ok(width)
     }
 
-    pub fn devlog(&mut self, msg: &str) {
-        if !self.config.dev { return; }
-        let _ = self.print_with_prefix(ESC::RED2, self.glyphs.debug, msg);
+    // A message with a hanging indent: lines after the first start under the
+    // message column. With a soft-wrap `marker`, lines wider than the
+    // terminal also wrap and every continuation gets the dim marker.
+    fn write_hanging(&mut self, spec: &ColorSpec, prefix: impl Display, brackets: bool, msg: &str, marker: Option<&str>) -> io::Result<()> {
+        let indent = if self.ci_profile {
+            let width = self.write_prefix(prefix, brackets)?;
+            ci::clock().len() + 1 + self.indent_width() + width
+        } else {
+            let mut buf = std::mem::take(&mut self.prefix_buf);
+            buf.clear();
+            buf.resize(self.indent_width(), b' ');
+            let result = write_prefix_to(&mut buf, self.label.as_deref(), prefix, brackets)
+                .and_then(|_| self.writer.write_all(&buf));
+            let width = visible_width(&String::from_utf8_lossy(&buf));
+            self.prefix_buf = buf;
+            result?;
+            width
+        };
+        let indent = indent + self.write_context_tag(spec)?;
+
+        let room = self.width.available().saturating_sub(indent + 1).max(20);
+        let mut first = true;
+        for line in msg.lines() {
+            let pieces = if marker.is_some() && visible_width(line) > room { wrap_ansi(line, room) } else { vec![line.to_string()] };
+            for piece in pieces {
+                if first {
+                    write!(&mut self.writer, " {}", piece)?;
+                    first = false;
+                    continue;
+                }
+                writeln!(&mut self.writer)?;
+                match marker {
+                    Some(marker) => {
+                        self.writer.set_color(ColorSpec::new().set_dimmed(true))?;
+                        write!(&mut self.writer, "{:pad$}{} ", "", marker, pad = indent.saturating_sub(1))?;
+                        self.writer.set_color(spec)?;
+                    }
+                    None => write!(&mut self.writer, "{:pad$}", "", pad = indent + 1)?,
+                }
+                write!(&mut self.writer, "{}", piece)?;
+            }
+        }
+        if first {
+            write!(&mut self.writer, " ")?;
+        }
+        Ok(())
     }
 
-    pub fn trace(&mut self, msg: &str) {
-        if !self.config.trace { return; }
-        let _ = self.print_with_prefix(ESC::GREY, self.glyphs.trace, msg);
+    // Applies `config.on_write_error` to the result of a terminal write
+    pub(crate) fn handle_write_error(&self, result: io::Result<()>) {
+        if let Err(err) = result {
+            match self.config.on_write_error {
+                ErrorPolicy::Ignore => {}
+                ErrorPolicy::Panic => panic!("failed to write log output: {}", err),
+                ErrorPolicy::Callback(callback) => callback(&err),
+            }
+        }
     }
 
-    pub fn magic(&mut self, msg: &str) {
-        if !self.config.silly { return; }
-        let _ = self.print_with_prefix(ESC::PURPLE, self.glyphs.magic, msg);
+    /// Moves the logger behind a cloneable, thread-safe [`StderrHandle`]
+    pub fn handle(self) -> super::handle::StderrHandle {
+        super::handle::StderrHandle::new(self)
     }
 
-    pub fn silly(&mut self, msg: &str) {
-        if !self.config.silly { return; }
-        let _ = self.print_with_prefix(ESC::MAGENTA, "\u{03C6}", msg); // φ
+    /// An `io::Write` that logs each line written to it at `level`,
+    /// e.g. for piping a child process's output through the logger.
+    pub fn writer_for(&mut self, level: LogLevel) -> super::adapters::LevelWriter<'_> {
+        super::adapters::LevelWriter::local(self, level)
     }
 
     /// Get access to the debug printer interface
@@ -401,85 +2682,253 @@ impl Stderr {
         DebugPrinter { inner: self }
     }
 
-    pub fn log(&mut self, level: LogLevel, msg: &str) {
-        let (color, symbol) = match level {
-            LogLevel::Okay => (ESC::GREEN, self.glyphs.okay),
-            LogLevel::Warn => (ESC::ORANGE, self.glyphs.warn),
-            LogLevel::Error => (ESC::RED, self.glyphs.error),
-            LogLevel::Info => (ESC::BLUE, self.glyphs.info),
-            LogLevel::Note => (ESC::BLUE, "\u{2192}"), // →
-            LogLevel::Debug => (ESC::CYAN, self.glyphs.debug),
-            LogLevel::Trace => (ESC::GREY, self.glyphs.trace),
-            LogLevel::Magic => (ESC::PURPLE, self.glyphs.magic),
-            LogLevel::Silly => (ESC::MAGENTA, "\u{03C6}"), // φ
-            LogLevel::DevLog => (ESC::MAGENTA, self.glyphs.debug),
+    pub fn log(&mut self, level: LogLevel, msg: impl Display) {
+        if !self.is_enabled(level) { return; }
+        self.emit(level, msg);
+    }
+
+    /// Like `log`, but returns terminal write errors instead of applying
+    /// `config.on_write_error`.
+    ///
+    /// ```no_run
+    /// use std::io::ErrorKind;
+    /// use stderr::{LogLevel, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// for i in 0.. {
+    ///     match log.try_log(LogLevel::Info, &format!("line {}", i)) {
+    ///         Err(e) if e.kind() == ErrorKind::BrokenPipe => break,
+    ///         other => other.unwrap(),
+    ///     }
+    /// }
+    /// ```
+    pub fn try_log(&mut self, level: LogLevel, msg: impl Display) -> io::Result<()> {
+        if !self.is_enabled(level) { return Ok(()); }
+        self.try_emit(level, msg)
+    }
+
+    /// Logs a message with inline markup (see [`esc::markup`](crate::esc::markup)):
+    /// styled spans on the terminal, the bare text everywhere else (sinks,
+    /// JSON, uncolored output).
+    ///
+    /// ```
+    /// use stderr::{LogLevel, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// let path = "src/main.rs";
+    /// log.log_md(LogLevel::Error, &format!("[bold red]fatal[/] in [cyan]{}[/]", path));
+    /// log.info_md("[dim]cached:[/] [green]42[/] files");
+    /// ```
+    pub fn log_md(&mut self, level: LogLevel, markup: &str) {
+        if !self.is_enabled(level) { return; }
+        let spans = markup::parse_markup(markup);
+        let plain = markup::plain_text(&spans);
+        let result = self.try_emit_spans(level, &plain, Some(&spans));
+        self.handle_write_error(result);
+    }
+
+    pub fn info_md(&mut self, markup: &str) {
+        self.log_md(LogLevel::Info, markup);
+    }
+
+    pub fn warn_md(&mut self, markup: &str) {
+        self.log_md(LogLevel::Warn, markup);
+    }
+
+    pub fn error_md(&mut self, markup: &str) {
+        self.log_md(LogLevel::Error, markup);
+    }
+
+    pub fn okay_md(&mut self, markup: &str) {
+        self.log_md(LogLevel::Okay, markup);
+    }
+
+    pub fn note_md(&mut self, markup: &str) {
+        self.log_md(LogLevel::Note, markup);
+    }
+
+    // Style and glyph used for a level's prefix
+    fn style(&self, level: LogLevel) -> (ColorSpec, &'static str) {
+        let glyphs = if self.config.plain { &GlyphSet::ASCII } else { &self.theme.glyphs };
+        (self.theme.styles.get(level).spec().clone(), glyphs.get(level))
+    }
+
+    pub(crate) fn dispatch_sinks(&mut self, level: LogLevel, msg: &str) {
+        if self.sinks.is_empty() { return; }
+        let record = Record {
+            level,
+            msg,
+            label: self.label.as_deref(),
+            context: self.contexts.last().map(String::as_str),
         };
+        let logger_levels = self.config.sink_levels();
+        for entry in &mut self.sinks {
+            if entry.wants(level, logger_levels) {
+                entry.sink.emit(&record);
+            }
+        }
+    }
+
+    fn emit(&mut self, level: LogLevel, msg: impl Display) {
+        let result = self.try_emit(level, msg);
+        self.handle_write_error(result);
+    }
 
-        let _ = self.print_with_prefix(color, symbol, msg);
+    // Formats the message into the reused scratch buffer, so passing a
+    // number or an error doesn't cost an allocation per call
+    fn try_emit(&mut self, level: LogLevel, msg: impl Display) -> io::Result<()> {
+        use std::fmt::Write as _;
+        let mut text = std::mem::take(&mut self.scratch);
+        text.clear();
+        let _ = write!(text, "{}", msg);
+        let result = self.try_emit_str(level, &text);
+        self.scratch = text;
+        result
+    }
+
+    // Single dispatch point for a message that passed `is_enabled`: sinks,
+    // then CI service messages (errors/warnings on TeamCity and Azure) or
+    // the terminal line.
+    fn try_emit_str(&mut self, level: LogLevel, msg: &str) -> io::Result<()> {
+        self.try_emit_spans(level, msg, None)
+    }
+
+    // `msg` is the plain text; `spans` the same text with inline markup
+    // styles, used for the terminal line when it is colored
+    fn try_emit_spans(&mut self, level: LogLevel, msg: &str, spans: Option<&[StyledString]>) -> io::Result<()> {
+        let caller = self.caller.take();
+        self.dispatch_sinks(level, msg);
+        if !self.terminal_enabled(level) { return Ok(()); }
+        if self.is_json() {
+            return self.write_json(level, msg);
+        }
+        self.write_block("log", |log| {
+            if let Some(line) = log.ci.and_then(|vendor| ci::issue_message(vendor, level, msg)) {
+                return writeln!(&mut log.writer, "{}", line);
+            }
+            let (spec, symbol) = log.style(level);
+            match spans {
+                Some(spans) if log.writer.supports_color() => {
+                    let styled = markup::render_within(spans, &spec, log.writer.truecolor());
+                    log.write_line(&spec, symbol, &styled)?;
+                }
+                _ => log.write_line(&spec, symbol, msg)?,
+            }
+            if log.ci_annotations && log.ci == Some(CiVendor::GitHubActions) {
+                if let Some(line) = ci::annotation(level, msg, caller) {
+                    writeln!(&mut log.writer, "{}", line)?;
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn emit_debug<T: Debug>(&mut self, level: LogLevel, value: &T) {
+        let (sinks, terminal) = (self.sink_wants(level), self.terminal_enabled(level));
+        let json = self.is_json();
+        // Formatted once for the sinks and the styled terminal line
+        let pretty = (sinks || (terminal && !json)).then(|| format!("{:#?}", value));
+        if let (true, Some(pretty)) = (sinks, &pretty) {
+            self.dispatch_sinks(level, pretty);
+        }
+        if !terminal { return; }
+        let result = match pretty {
+            Some(pretty) if !json => {
+                let (spec, symbol) = self.style(level);
+                self.write_line_pretty(&spec, symbol, &pretty)
+            }
+            _ => self.write_json(level, &format!("{:?}", value)),
+        };
+        self.handle_write_error(result);
     }
 
     // --- Debug Pretty Printing ---
     
     pub fn print_with_prefix_debug<T: Debug>(
         &mut self,
-        color: Color,
+        style: impl Into<Style>,
         prefix: impl Display,
         value: &T,
     ) -> io::Result<()> {
         if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let style = style.into();
+        self.write_line_debug(style.spec(), prefix, value)
+    }
 
-        self.set_fg(color)?;
-        let formatted_prefix = match &self.label {
-            Some(label) => format!("[{}]{}", label, prefix),
-            None => format!("{}", prefix),
-        };
+    fn write_line_debug<T: Debug>(&mut self, spec: &ColorSpec, prefix: impl Display, value: &T) -> io::Result<()> {
+        self.write_line_pretty(spec, prefix, &format!("{:#?}", value))
+    }
 
-        writeln!(&mut self.writer, "{} {:#?}", formatted_prefix, value)?;
-        self.writer.reset()
+    // A `{:#?}` dump after the prefix
+    fn write_line_pretty(&mut self, spec: &ColorSpec, prefix: impl Display, pretty: &str) -> io::Result<()> {
+        self.write_block("log", |log| {
+            log.writer.set_color(spec)?;
+            // Pretty dumps span lines; keep them under the message column
+            log.write_hanging(spec, prefix, false, pretty, None)?;
+            log.writer.reset()?;
+            writeln!(&mut log.writer)
+        })
     }
 
     pub fn error_debug<T: Debug>(&mut self, value: &T) {
-        let _ = self.print_with_prefix_debug(ESC::RED, self.glyphs.error, value);
+        if !self.is_enabled(LogLevel::Error) { return; }
+        self.emit_debug(LogLevel::Error, value);
     }
 
     pub fn warn_debug<T: Debug>(&mut self, value: &T) {
-        let _ = self.print_with_prefix_debug(ESC::ORANGE, self.glyphs.warn, value);
+        if !self.is_enabled(LogLevel::Warn) { return; }
+        self.emit_debug(LogLevel::Warn, value);
     }
 
     pub fn info_debug<T: Debug>(&mut self, value: &T) {
-        let _ = self.print_with_prefix_debug(ESC::BLUE, self.glyphs.info, value);
+        if !self.is_enabled(LogLevel::Info) { return; }
+        self.emit_debug(LogLevel::Info, value);
     }
 
     pub fn okay_debug<T: Debug>(&mut self, value: &T) {
-        let _ = self.print_with_prefix_debug(ESC::GREEN, self.glyphs.okay, value);
+        if !self.is_enabled(LogLevel::Okay) { return; }
+        self.emit_debug(LogLevel::Okay, value);
     }
 
     pub fn note_debug<T: Debug>(&mut self, value: &T) {
-        let _ = self.print_with_prefix_debug(ESC::BLUE, "\u{2192}", value); // →
+        if !self.is_enabled(LogLevel::Note) { return; }
+        self.emit_debug(LogLevel::Note, value);
     }
 
+    #[inline]
     pub fn debug_debug<T: Debug>(&mut self, value: &T) {
-        if !self.config.debug { return; }
-        let _ = self.print_with_prefix_debug(ESC::CYAN, self.glyphs.debug, value);
+        if !self.is_enabled(LogLevel::Debug) { return; }
+        self.emit_debug(LogLevel::Debug, value);
     }
 
+    #[inline]
     pub fn devlog_debug<T: Debug>(&mut self, value: &T) {
-        if !self.config.dev { return; }
-        let _ = self.print_with_prefix_debug(ESC::RED2, self.glyphs.debug, value);
+        if !self.is_enabled(LogLevel::DevLog) { return; }
+        self.emit_debug(LogLevel::DevLog, value);
     }
 
+    #[inline]
     pub fn trace_debug<T: Debug>(&mut self, value: &T) {
-        if !self.config.trace { return; }
-        let _ = self.print_with_prefix_debug(ESC::GREY, self.glyphs.trace, value);
+        if !self.is_enabled(LogLevel::Trace) { return; }
+        self.emit_debug(LogLevel::Trace, value);
     }
 
+    #[inline]
     pub fn magic_debug<T: Debug>(&mut self, value: &T) {
-        if !self.config.silly { return; }
-        let _ = self.print_with_prefix_debug(ESC::PURPLE, self.glyphs.magic, value);
+        if !self.is_enabled(LogLevel::Magic) { return; }
+        self.emit_debug(LogLevel::Magic, value);
     }
 
+    #[inline]
     pub fn silly_debug<T: Debug>(&mut self, value: &T) {
-        if !self.config.silly { return; }
-        let _ = self.print_with_prefix_debug(ESC::MAGENTA, "\u{03C6}", value); // φ
+        if !self.is_enabled(LogLevel::Silly) { return; }
+        self.emit_debug(LogLevel::Silly, value);
+    }
+}
+
+fn write_prefix_to<W: Write>(w: &mut W, label: Option<&str>, prefix: impl Display, bracket: bool) -> io::Result<()> {
+    if let Some(label) = label {
+        write!(w, "[{}]", label)?;
     }
+    if bracket { write!(w, "[{}]", prefix) } else { write!(w, "{}", prefix) }
 }
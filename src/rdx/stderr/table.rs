@@ -0,0 +1,402 @@
+//! Column-aware tables: alignment, header style and column order
+
+use std::cmp::Ordering;
+use std::fmt::Display;
+use std::io;
+use termcolor::ColorSpec;
+
+use super::formatting::{write_delimited, write_table};
+use super::stderr::{Stderr, OptionFlag};
+use crate::esc::style::Style;
+use crate::utils::helpers::render_to_string;
+
+/// How a column's cells sit in its width
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Align {
+    #[default]
+    Left,
+    Right,
+    Center,
+}
+
+impl Align {
+    // `cell` padded to `width` visible columns (`visible` is its own width)
+    pub(crate) fn pad(self, cell: &str, visible: usize, width: usize) -> String {
+        let room = width.saturating_sub(visible);
+        let (left, right) = match self {
+            Align::Left => (0, room),
+            Align::Right => (room, 0),
+            Align::Center => (room / 2, room - room / 2),
+        };
+        format!("{:left$}{}{:right$}", "", cell, "")
+    }
+}
+
+/// Direction for [`TableBuilder::sort_by`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SortOrder {
+    #[default]
+    Ascending,
+    Descending,
+}
+
+/// What a column does with cells wider than its `max_width`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Overflow {
+    /// Cut the cell, ending it with `…`
+    #[default]
+    Truncate,
+    /// Cut the middle of the cell: `@myapp…config`
+    TruncateMiddle,
+    /// Cut the start of the cell, keeping the end: `…/config/secrets.env`
+    TruncateStart,
+    /// Word-wrap the cell onto more lines
+    Wrap,
+}
+
+// How one column is laid out
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ColumnFormat {
+    pub(crate) align: Align,
+    pub(crate) max_width: Option<usize>,
+    pub(crate) overflow: Overflow,
+}
+
+#[derive(Debug, Clone)]
+struct Column {
+    name: String,
+    format: ColumnFormat,
+}
+
+/// Fluent table builder, created via `log.table_builder()`
+///
+/// ```
+/// use stderr::{Align, Color, Stderr, Style};
+///
+/// let mut log = Stderr::new();
+/// log.table_builder()
+///     .column("Name")
+///     .column("Size").align(Align::Right)
+///     .column("Kind")
+///     .header_style(Style::new().fg(Color::CYAN).underline())
+///     .row(["README.md", "4.2K", "file"])
+///     .row(["src", "-", "dir"])
+///     .order(&["Kind", "Name", "Size"])
+///     .print()
+///     .unwrap();
+/// ```
+pub struct TableBuilder<'a> {
+    stderr: &'a mut Stderr,
+    columns: Vec<Column>,
+    rows: Vec<Vec<String>>,
+    header: Option<Style>,
+    order: Option<Vec<String>>,
+    striped: bool,
+    row_style: Option<RowStyle<'a>>,
+    sort: Option<(usize, CellOrder<'a>)>,
+}
+
+// Picks a style for a body row from its cells
+type RowStyle<'a> = Box<dyn Fn(&[&str]) -> Option<Style> + 'a>;
+
+// Compares two cells of the sort column
+type CellOrder<'a> = Box<dyn Fn(&str, &str) -> Ordering + 'a>;
+
+impl<'a> TableBuilder<'a> {
+    pub(crate) fn new(stderr: &'a mut Stderr) -> Self {
+        Self {
+            stderr,
+            columns: Vec::new(),
+            rows: Vec::new(),
+            header: None,
+            order: None,
+            striped: false,
+            row_style: None,
+            sort: None,
+        }
+    }
+
+    /// Adds a column; rows give their cells in the order columns are added
+    pub fn column(mut self, name: impl Into<String>) -> Self {
+        self.columns.push(Column { name: name.into(), format: ColumnFormat::default() });
+        self
+    }
+
+    /// Adds several left-aligned columns
+    pub fn columns<S: Into<String>>(mut self, names: impl IntoIterator<Item = S>) -> Self {
+        for name in names {
+            self = self.column(name);
+        }
+        self
+    }
+
+    /// Sets the alignment of the column added last
+    pub fn align(mut self, align: Align) -> Self {
+        if let Some(column) = self.columns.last_mut() {
+            column.format.align = align;
+        }
+        self
+    }
+
+    /// Caps the width of the column added last, in terminal columns; wider
+    /// cells are truncated or wrapped (see [`overflow`](Self::overflow))
+    ///
+    /// ```
+    /// use stderr::{Overflow, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// let text = log.table_builder()
+    ///     .column("Path").max_width(12)
+    ///     .column("Note").max_width(10).overflow(Overflow::Wrap)
+    ///     .row(["src/rdx/stderr/table.rs", "wraps onto two lines"])
+    ///     .render(false);
+    /// assert!(text.contains("src/rdx/std…"));
+    /// assert_eq!(text.lines().count(), 4);   // header, rule, two lines
+    /// ```
+    pub fn max_width(mut self, columns: usize) -> Self {
+        if let Some(column) = self.columns.last_mut() {
+            column.format.max_width = Some(columns.max(1));
+        }
+        self
+    }
+
+    /// Sets how the column added last handles cells wider than it
+    pub fn overflow(mut self, overflow: Overflow) -> Self {
+        if let Some(column) = self.columns.last_mut() {
+            column.format.overflow = overflow;
+        }
+        self
+    }
+
+    /// Styles the header row; the theme's header style by default
+    pub fn header_style(mut self, style: impl Into<Style>) -> Self {
+        self.header = Some(style.into());
+        self
+    }
+
+    pub fn row<T: Display>(mut self, cells: impl IntoIterator<Item = T>) -> Self {
+        self.rows.push(cells.into_iter().map(|cell| cell.to_string()).collect());
+        self
+    }
+
+    pub fn rows<R, T>(mut self, rows: impl IntoIterator<Item = R>) -> Self
+    where
+        R: IntoIterator<Item = T>,
+        T: Display,
+    {
+        for row in rows {
+            self = self.row(row);
+        }
+        self
+    }
+
+    /// Dims every other body row, starting with the second
+    pub fn striped(mut self, on: bool) -> Self {
+        self.striped = on;
+        self
+    }
+
+    /// Styles body rows by their content. The closure gets the row's cells
+    /// in the order the columns were added (whatever [`order`](Self::order)
+    /// shows) and returns a style, or `None` to leave the row alone; it
+    /// takes precedence over striping.
+    ///
+    /// ```
+    /// use stderr::{Color, Stderr, Style};
+    ///
+    /// let mut log = Stderr::new();
+    /// log.table_builder()
+    ///     .columns(["job", "status"])
+    ///     .row(["build", "ok"])
+    ///     .row(["test", "failed"])
+    ///     .row(["lint", "ok"])
+    ///     .striped(true)
+    ///     .row_style(|row| (row[1] == "failed").then(|| Style::new().fg(Color::RED)))
+    ///     .print()
+    ///     .unwrap();
+    /// ```
+    pub fn row_style<S: Into<Style>>(mut self, style: impl Fn(&[&str]) -> Option<S> + 'a) -> Self {
+        self.row_style = Some(Box::new(move |row| style(row).map(Into::into)));
+        self
+    }
+
+    /// Sorts body rows by a column (its index in the order columns were
+    /// added). Digit runs compare as numbers, so `file2` comes before
+    /// `file10` and `856` before `1200`; the sort is stable.
+    ///
+    /// ```
+    /// use stderr::{SortOrder, Stderr};
+    ///
+    /// let mut log = Stderr::new();
+    /// let text = log.table_builder()
+    ///     .columns(["file", "lines"])
+    ///     .row(["a.rs", "1200"])
+    ///     .row(["b.rs", "856"])
+    ///     .row(["c.rs", "97"])
+    ///     .sort_by(1, SortOrder::Descending)
+    ///     .render(false);
+    /// let files: Vec<&str> = text.lines().skip(2).map(|line| &line[..4]).collect();
+    /// assert_eq!(files, ["a.rs", "b.rs", "c.rs"]);
+    /// ```
+    pub fn sort_by(self, column: usize, order: SortOrder) -> Self {
+        match order {
+            SortOrder::Ascending => self.sort_with(column, natural_cmp),
+            SortOrder::Descending => self.sort_with(column, |a, b| natural_cmp(b, a)),
+        }
+    }
+
+    /// Sorts body rows by a column with a custom comparison, e.g. for sizes
+    /// like `1.2KB` and `856B`
+    pub fn sort_with(mut self, column: usize, compare: impl Fn(&str, &str) -> Ordering + 'a) -> Self {
+        self.sort = Some((column, Box::new(compare)));
+        self
+    }
+
+    /// Shows only these columns, in this order (by name; unknown names are
+    /// skipped)
+    pub fn order(mut self, names: &[&str]) -> Self {
+        self.order = Some(names.iter().map(|name| name.to_string()).collect());
+        self
+    }
+
+    pub fn print(mut self) -> io::Result<()> {
+        if self.stderr.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        self.sort_rows();
+        let row_specs = self.row_specs();
+        let TableBuilder { stderr, columns, rows, header, order, .. } = self;
+        let (rows, formats) = layout(&columns, &rows, order.as_deref());
+        let rows: Vec<&[&str]> = rows.iter().map(Vec::as_slice).collect();
+        let header = header.unwrap_or_else(|| stderr.theme.headers.clone());
+        let width = stderr.width.layout();
+        stderr.write_block("table", |log| write_table(&mut log.writer, &rows, header.spec(), &formats, &row_specs, width))
+    }
+
+    /// Renders the table to a `String` instead of the stream
+    pub fn render(mut self, ansi: bool) -> String {
+        self.sort_rows();
+        let row_specs = self.row_specs();
+        let (rows, formats) = layout(&self.columns, &self.rows, self.order.as_deref());
+        let rows: Vec<&[&str]> = rows.iter().map(Vec::as_slice).collect();
+        let header = self.header.as_ref().unwrap_or(&self.stderr.theme.headers).spec();
+        let width = self.stderr.width.layout();
+        render_to_string(ansi && !self.stderr.is_plain(), |buf| write_table(buf, &rows, header, &formats, &row_specs, width))
+    }
+
+    /// Writes the table as CSV (header first, sorting and column order
+    /// applied), for `--format csv` style output
+    ///
+    /// ```
+    /// use stderr::Stderr;
+    ///
+    /// let mut log = Stderr::new();
+    /// let mut out = Vec::new();
+    /// log.table_builder()
+    ///     .columns(["name", "note"])
+    ///     .row(["api", "up, healthy"])
+    ///     .to_csv(&mut out)
+    ///     .unwrap();
+    /// assert_eq!(String::from_utf8(out).unwrap(), "name,note\napi,\"up, healthy\"\n");
+    /// ```
+    pub fn to_csv(self, w: impl io::Write) -> io::Result<()> {
+        self.write_delimited(w, ',')
+    }
+
+    /// Like [`to_csv`](Self::to_csv), separated by tabs
+    pub fn to_tsv(self, w: impl io::Write) -> io::Result<()> {
+        self.write_delimited(w, '\t')
+    }
+
+    fn write_delimited(mut self, mut w: impl io::Write, delimiter: char) -> io::Result<()> {
+        self.sort_rows();
+        let (rows, _) = layout(&self.columns, &self.rows, self.order.as_deref());
+        let rows: Vec<&[&str]> = rows.iter().map(Vec::as_slice).collect();
+        write_delimited(&mut w, &rows, delimiter)
+    }
+
+    fn sort_rows(&mut self) {
+        let Some((column, compare)) = self.sort.take() else { return };
+        fn cell(row: &[String], column: usize) -> &str {
+            row.get(column).map(String::as_str).unwrap_or("")
+        }
+        self.rows.sort_by(|a, b| compare(cell(a, column), cell(b, column)));
+    }
+
+    // Each body row's color: the row style hook first, then striping
+    fn row_specs(&self) -> Vec<Option<ColorSpec>> {
+        let mut stripe = ColorSpec::new();
+        stripe.set_dimmed(true);
+        self.rows
+            .iter()
+            .enumerate()
+            .map(|(i, row)| {
+                let cells: Vec<&str> = row.iter().map(String::as_str).collect();
+                let style = self.row_style.as_ref().and_then(|style| style(&cells));
+                match style {
+                    Some(style) => Some(style.spec().clone()),
+                    None if self.striped && i % 2 == 1 => Some(stripe.clone()),
+                    None => None,
+                }
+            })
+            .collect()
+    }
+}
+
+// Compares digit runs by value and everything else by character
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (mut a, mut b) = (a.chars().peekable(), b.chars().peekable());
+    loop {
+        match (a.peek().copied(), b.peek().copied()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) if x.is_ascii_digit() && y.is_ascii_digit() => {
+                let x = digits(&mut a);
+                let y = digits(&mut b);
+                let x = x.trim_start_matches('0');
+                let y = y.trim_start_matches('0');
+                let order = x.len().cmp(&y.len()).then_with(|| x.cmp(y));
+                if order != Ordering::Equal { return order; }
+            }
+            (Some(x), Some(y)) => {
+                let order = x.cmp(&y);
+                if order != Ordering::Equal { return order; }
+                a.next();
+                b.next();
+            }
+        }
+    }
+}
+
+fn digits(chars: &mut std::iter::Peekable<std::str::Chars>) -> String {
+    let mut run = String::new();
+    while let Some(c) = chars.next_if(char::is_ascii_digit) {
+        run.push(c);
+    }
+    run
+}
+
+// Header and body rows plus column formats, in display order
+fn layout<'a>(columns: &'a [Column], rows: &'a [Vec<String>], order: Option<&[String]>) -> (Vec<Vec<&'a str>>, Vec<ColumnFormat>) {
+    let shown: Vec<usize> = match order {
+        Some(names) => names
+            .iter()
+            .filter_map(|name| columns.iter().position(|column| &column.name == name))
+            .collect(),
+        None => (0..columns.len()).collect(),
+    };
+    if shown.is_empty() { return (Vec::new(), Vec::new()); }
+
+    let mut table = vec![shown.iter().map(|&i| columns[i].name.as_str()).collect::<Vec<_>>()];
+    for row in rows {
+        table.push(shown.iter().map(|&i| row.get(i).map(String::as_str).unwrap_or("")).collect());
+    }
+    let formats = shown.iter().map(|&i| columns[i].format).collect();
+    (table, formats)
+}
+
+impl Stderr {
+    /// Starts a [`TableBuilder`] drawing to this logger
+    pub fn table_builder(&mut self) -> TableBuilder<'_> {
+        TableBuilder::new(self)
+    }
+}
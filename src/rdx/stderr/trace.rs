@@ -3,9 +3,24 @@
 //! This module adds sophisticated tracing capabilities inspired by the bash
 //! FUNCNAME array, with visual hierarchy using box-drawing characters.
 
-use super::stderr::{Stderr, OptionFlag};
+use std::borrow::Cow;
+
+use super::stderr::{Stderr, LogLevel};
 use crate::esc::colors::Color as ESC;
 
+// Plain mode and non-Unicode terminals draw the tree in ASCII: `+---[f]`,
+// `|`, `` `--> ``
+fn tree<'a>(stderr: &Stderr, text: &'a str) -> Cow<'a, str> {
+    if !stderr.ascii_only() { return Cow::Borrowed(text); }
+    Cow::Owned(text.chars().map(|c| match c {
+        '\u{03BB}' => '+', // λ
+        '\u{2504}' => '-', // ┄
+        '\u{2506}' => '|', // ┆
+        '\u{2514}' => '`', // └
+        c => c,
+    }).collect())
+}
+
 #[cfg(feature = "trace")]
 impl Stderr {
     /// Enhanced hierarchical trace with manual function name
@@ -16,7 +31,7 @@ impl Stderr {
     ///     └┄┄> message
     ///     └┄┄>> continuation message
     pub fn trace_fn(&mut self, func_name: &str, msg: &str) {
-        if !self.config.trace { return; }
+        if !self.is_enabled(LogLevel::Trace) { return; }
         self.hierarchical_trace(func_name, msg);
     }
 
@@ -24,7 +39,7 @@ impl Stderr {
     /// Note: This is only useful when called from within a #[named] function
     #[cfg(feature = "auto-fn-names")]
     pub fn trace_auto(&mut self, msg: &str) {
-        if !self.config.trace { return; }
+        if !self.is_enabled(LogLevel::Trace) { return; }
         // This will only work if called from within a #[named] function
         self.hierarchical_trace("auto", msg);
     }
@@ -38,22 +53,17 @@ impl Stderr {
 
     /// Internal hierarchical trace implementation
     fn hierarchical_trace(&mut self, func_name: &str, msg: &str) {
-        if self.check_flag(OptionFlag::Quiet) { return; }
-
-        let same_func = match &self.last_trace_func {
-            Some(last) if last == func_name => true,
-            _ => false,
-        };
+        let same_func = matches!(&self.last_trace_func, Some(last) if last == func_name);
 
         if same_func {
             // Continuation of the same function call
-            let formatted = format!("\t└┄┄>> {}", msg);
+            let formatted = format!("{} {}", tree(self, "\t└┄┄>>"), msg);
             self.trace(&formatted);
         } else {
             // Start of a new function branch
-            let header = format!("λ┄┄┄[{}]", func_name);
+            let header = format!("{}[{}]", tree(self, "λ┄┄┄"), func_name);
             // Print header and message on separate lines with connectors
-            let formatted = format!("{}\n\t┆\n\t└┄┄> {}", header, msg);
+            let formatted = format!("{}{} {}", header, tree(self, "\n\t┆\n\t└┄┄>"), msg);
             self.trace(&formatted);
             self.last_trace_func = Some(func_name.to_string());
         }
@@ -63,7 +73,7 @@ impl Stderr {
     /// 
     /// Returns a guard that will log function exit when dropped
     pub fn trace_scope(&mut self, func_name: &str) -> TraceScope<'_> {
-        if self.config.trace {
+        if self.is_enabled(LogLevel::Trace) {
             self.trace_fn(func_name, "entering");
         }
         TraceScope::new(self, func_name)
@@ -81,24 +91,36 @@ impl Stderr {
 }
 
 /// RAII guard for automatic function exit tracing
+///
+/// With the `otel` feature each scope is also an OpenTelemetry span (child
+/// of the enclosing scope's span), and its steps are span events.
 pub struct TraceScope<'a> {
     stderr: &'a mut Stderr,
     func_name: String,
     should_trace: bool,
+    // Only started when trace output is enabled
+    #[cfg(feature = "otel")]
+    span: Option<super::otel::ScopeSpan>,
 }
 
 impl<'a> TraceScope<'a> {
     fn new(stderr: &'a mut Stderr, func_name: &str) -> Self {
-        let should_trace = stderr.config.trace; // Read before borrowing
+        let should_trace = stderr.is_enabled(LogLevel::Trace); // Read before borrowing
         Self {
             stderr,
             func_name: func_name.to_string(),
             should_trace,
+            #[cfg(feature = "otel")]
+            span: should_trace.then(|| super::otel::ScopeSpan::start(func_name)),
         }
     }
 
     /// Add a step within this function scope
     pub fn step(&mut self, msg: &str) {
+        #[cfg(feature = "otel")]
+        if let Some(span) = &self.span {
+            span.event(msg, None);
+        }
         if self.should_trace {
             self.stderr.trace_fn(&self.func_name, msg);
         }
@@ -106,6 +128,10 @@ impl<'a> TraceScope<'a> {
 
     /// Add a step with debug information
     pub fn step_debug<T: std::fmt::Debug>(&mut self, msg: &str, value: &T) {
+        #[cfg(feature = "otel")]
+        if let Some(span) = &self.span {
+            span.event(msg, Some(format!("{:?}", value)));
+        }
         if self.should_trace {
             let formatted = format!("{}: {:#?}", msg, value);
             self.stderr.trace_fn(&self.func_name, &formatted);
@@ -126,10 +152,10 @@ impl<'a> Drop for TraceScope<'a> {
 impl Stderr {
     /// Trace with explicit level indication
     pub fn trace_level(&mut self, level: u8, func_name: &str, msg: &str) {
-        if !self.config.trace { return; }
+        if !self.is_enabled(LogLevel::Trace) { return; }
         
         let indent = "  ".repeat(level as usize);
-        let formatted = format!("{}└┄ [{}] {}", indent, func_name, msg);
+        let formatted = format!("{}{} [{}] {}", indent, tree(self, "└┄"), func_name, msg);
         self.trace(&formatted);
     }
 
@@ -172,11 +198,17 @@ impl Stderr {
 
     /// Internal helper for labelled traces
     fn trace_labelled(&mut self, label: &str, color: termcolor::Color, msg: &str) {
-        if !self.config.trace { return; }
-        
-        let _ = self.set_fg(color);
-        let formatted_prefix = format!("\t└┄┄[ {} ]", label);
-        let _ = self.print_with_prefix(color, &formatted_prefix, msg);
-        let _ = self.reset();
+        if !self.is_enabled(LogLevel::Trace) { return; }
+        self.dispatch_sinks(LogLevel::Trace, msg);
+        if !self.terminal_enabled(LogLevel::Trace) { return; }
+        if self.is_json() {
+            let result = self.write_json(LogLevel::Trace, &format!("[{}] {}", label, msg));
+            return self.handle_write_error(result);
+        }
+
+        let branch = tree(self, "\t└┄┄[").into_owned();
+        let result = self.print_with_prefix(color, format_args!("{} {} ]", branch, label), msg)
+            .and_then(|_| self.reset());
+        self.handle_write_error(result);
     }
 }
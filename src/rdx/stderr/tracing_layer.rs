@@ -0,0 +1,142 @@
+//! `tracing` integration: a subscriber layer that prints spans with the
+//! hierarchical trace glyphs and events with the level glyphs, through the
+//! global logger
+//!
+//! Span headers go through the same `trace_fn` path as `qtrace_fn!`, so
+//! `tracing` spans and direct trace calls share one `λ┄┄┄[name]` tree.
+
+use std::fmt::{self, Write as _};
+
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::layer::{Context, Layer};
+use tracing_subscriber::registry::LookupSpan;
+
+use super::static_logger::LOGGER;
+use super::stderr::LogLevel;
+
+/// Renders `tracing` spans and events on the global logger.
+///
+/// Events inside a span at `TRACE` level become steps under the span's
+/// `λ┄┄┄[span]` header; every other event prints as a regular line at the
+/// matching level. What prints follows the logger's configuration.
+///
+/// ```
+/// use tracing_subscriber::prelude::*;
+/// use stderr::StderrLayer;
+///
+/// tracing_subscriber::registry().with(StderrLayer::new()).init();
+///
+/// let span = tracing::trace_span!("load_config", path = "app.toml");
+/// let _entered = span.enter();
+/// tracing::trace!("parsing");
+/// tracing::info!(keys = 12, "config loaded");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct StderrLayer {
+    exits: bool,
+}
+
+impl Default for StderrLayer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl StderrLayer {
+    pub const fn new() -> Self {
+        Self { exits: true }
+    }
+
+    /// Whether closing a span prints an `exiting` step (default on)
+    pub const fn exits(mut self, exits: bool) -> Self {
+        self.exits = exits;
+        self
+    }
+}
+
+impl<S> Layer<S> for StderrLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        if !LOGGER.enabled(LogLevel::Trace) { return; }
+        let Some(span) = ctx.span(id) else { return };
+        let mut fields = Fields::default();
+        attrs.record(&mut fields);
+        let fields = fields.into_text();
+        let msg = if fields.is_empty() { "entering".to_string() } else { format!("entering {}", fields) };
+        LOGGER.raw().trace_fn(span.name(), &msg);
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let level = log_level(*event.metadata().level());
+        if !LOGGER.enabled(level) { return; }
+        let mut fields = Fields::default();
+        event.record(&mut fields);
+        let text = fields.into_text();
+
+        match ctx.event_span(event) {
+            Some(span) if level == LogLevel::Trace => LOGGER.raw().trace_fn(span.name(), &text),
+            _ => LOGGER.log(level, text),
+        }
+    }
+
+    fn on_close(&self, id: Id, ctx: Context<'_, S>) {
+        if !self.exits || !LOGGER.enabled(LogLevel::Trace) { return; }
+        if let Some(span) = ctx.span(&id) {
+            LOGGER.raw().trace_fn(span.name(), "exiting");
+        }
+    }
+}
+
+fn log_level(level: Level) -> LogLevel {
+    match level {
+        Level::ERROR => LogLevel::Error,
+        Level::WARN => LogLevel::Warn,
+        Level::INFO => LogLevel::Info,
+        Level::DEBUG => LogLevel::Debug,
+        Level::TRACE => LogLevel::Trace,
+    }
+}
+
+// The `message` field, then the others as `key=value`
+#[derive(Default)]
+struct Fields {
+    message: String,
+    rest: String,
+}
+
+impl Fields {
+    fn push(&mut self, field: &Field, value: fmt::Arguments<'_>) {
+        if !self.rest.is_empty() { self.rest.push(' '); }
+        let _ = write!(self.rest, "{}={}", field.name(), value);
+    }
+
+    fn into_text(self) -> String {
+        match (self.message.is_empty(), self.rest.is_empty()) {
+            (_, true) => self.message,
+            (true, false) => self.rest,
+            (false, false) => format!("{} {}", self.message, self.rest),
+        }
+    }
+}
+
+impl Visit for Fields {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "message" {
+            self.message.push_str(value);
+        } else {
+            self.push(field, format_args!("{}", value));
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        if field.name() == "message" {
+            let _ = write!(self.message, "{:?}", value);
+        } else {
+            self.push(field, format_args!("{:?}", value));
+        }
+    }
+}
@@ -0,0 +1,140 @@
+//! Tree view for nested data (dependency trees, directory listings), drawn
+//! with the same box-drawing pieces as `boxed`
+
+use std::io;
+use termcolor::WriteColor;
+
+use super::stderr::{Stderr, OptionFlag};
+use crate::esc::boxes::{BorderStyle, BoxChars};
+use crate::esc::style::Style;
+use crate::utils::ansi::{truncate_ansi, visible_width};
+
+/// A node of a tree drawn by [`Stderr::tree`], with its own glyph and color
+///
+/// ```
+/// use stderr::{Color, Stderr, Style, TreeNode};
+///
+/// let root = TreeNode::new("my-app v0.3.0").children([
+///     TreeNode::new("serde v1.0").child(TreeNode::new("serde_derive v1.0")),
+///     TreeNode::new("openssl v0.10").glyph("!").style(Style::new().fg(Color::YELLOW)),
+/// ]);
+///
+/// let text = Stderr::new().render_tree(&root, false);
+/// assert_eq!(text, "\
+/// my-app v0.3.0
+/// ├─ serde v1.0
+/// │  └─ serde_derive v1.0
+/// └─ ! openssl v0.10
+/// ");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TreeNode {
+    pub(crate) label: String,
+    pub(crate) glyph: Option<String>,
+    pub(crate) style: Option<Style>,
+    pub(crate) children: Vec<TreeNode>,
+}
+
+impl TreeNode {
+    pub fn new(label: impl Into<String>) -> Self {
+        Self { label: label.into(), ..Self::default() }
+    }
+
+    /// Shown before the label, e.g. a folder or status glyph
+    pub fn glyph(mut self, glyph: impl Into<String>) -> Self {
+        self.glyph = Some(glyph.into());
+        self
+    }
+
+    /// Colors the glyph and label
+    pub fn style(mut self, style: impl Into<Style>) -> Self {
+        self.style = Some(style.into());
+        self
+    }
+
+    pub fn child(mut self, child: TreeNode) -> Self {
+        self.children.push(child);
+        self
+    }
+
+    pub fn children(mut self, children: impl IntoIterator<Item = TreeNode>) -> Self {
+        self.children.extend(children);
+        self
+    }
+}
+
+// What nodes need while rendering
+struct Ctx<'a> {
+    chars: BoxChars,
+    lines: Style,
+    ansi: bool,
+    width: usize,
+    out: &'a mut Vec<String>,
+}
+
+impl Ctx<'_> {
+    fn paint(&self, style: &Style, piece: &str) -> String {
+        if self.ansi && !piece.is_empty() {
+            format!("{}{}{}", Style::to_ansi(style.spec()), piece, Style::ANSI_RESET)
+        } else {
+            piece.to_string()
+        }
+    }
+
+    // `node` after `lead` (the connectors so far), then its children
+    fn push(&mut self, node: &TreeNode, lead: &str, branch: &str, rest: &str) {
+        let text = match &node.glyph {
+            Some(glyph) => format!("{} {}", glyph, node.label),
+            None => node.label.clone(),
+        };
+        let room = self.width.saturating_sub(visible_width(lead) + visible_width(branch)).max(1);
+        let text = truncate_ansi(&text, room);
+        let text = match &node.style {
+            Some(style) => self.paint(style, &text),
+            None => text,
+        };
+        let connectors = self.paint(&self.lines, &format!("{}{}", lead, branch));
+        self.out.push(format!("{}{}", connectors, text));
+
+        let lead = format!("{}{}", lead, rest);
+        let last = node.children.len().saturating_sub(1);
+        for (i, child) in node.children.iter().enumerate() {
+            let (branch, rest) = if i == last {
+                (format!("{}{} ", self.chars.bottom_left, self.chars.horizontal), "   ".to_string())
+            } else {
+                (format!("{}{} ", self.chars.left_t, self.chars.horizontal), format!("{}  ", self.chars.vertical))
+            };
+            self.push(child, &lead, &branch, &rest);
+        }
+    }
+}
+
+impl Stderr {
+    /// Draws a [`TreeNode`] and its descendants with `├─` / `└─` connectors
+    /// (ASCII ones in plain mode); labels are cut at the layout width
+    pub fn tree(&mut self, root: &TreeNode) -> io::Result<()> {
+        if self.check_flag(OptionFlag::Quiet) { return Ok(()); }
+        let ansi = self.writer.supports_color() && !self.is_plain();
+        let lines = self.tree_lines(root, ansi);
+        self.write_lines("tree", &lines)
+    }
+
+    /// Renders a tree to a `String` instead of the stream
+    pub fn render_tree(&self, root: &TreeNode, ansi: bool) -> String {
+        let ansi = ansi && !self.is_plain();
+        self.tree_lines(root, ansi).iter().map(|line| format!("{}\n", line)).collect()
+    }
+
+    fn tree_lines(&self, root: &TreeNode, ansi: bool) -> Vec<String> {
+        let mut out = Vec::new();
+        let mut ctx = Ctx {
+            chars: BoxChars::from_style(&self.border(BorderStyle::Light)),
+            lines: self.theme.boxes.clone(),
+            ansi,
+            width: self.width.layout(),
+            out: &mut out,
+        };
+        ctx.push(root, "", "", "");
+        out
+    }
+}
@@ -0,0 +1,132 @@
+//! Layout width shared by banners, boxes, tables and flag tables
+
+use std::cell::Cell;
+
+use crate::utils::helpers::term_width;
+
+/// Below this many columns boxes lose their borders, tables are stacked as
+/// `header: value` lines and banner titles are shortened
+pub const NARROW_WIDTH: usize = 40;
+
+/// How wide drawn elements (banners, boxes, tables) may get
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WidthPolicy {
+    /// Use the whole width
+    #[default]
+    Fill,
+    /// Use the width, but never more than this many columns
+    Cap(usize),
+}
+
+/// Whether long messages wrap to the terminal, and how continuation lines
+/// are marked. Continuations are indented under the message column.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SoftWrap {
+    /// Print messages as-is and let the terminal wrap them
+    #[default]
+    Off,
+    /// Wrap, marking continuation lines with a dim `…`
+    Ellipsis,
+    /// Wrap, marking continuation lines with a dim `│` guide
+    Guide,
+    /// Wrap at word boundaries, with continuation lines simply indented
+    Indent,
+}
+
+impl SoftWrap {
+    pub(crate) fn marker(self) -> Option<&'static str> {
+        match self {
+            SoftWrap::Off => None,
+            SoftWrap::Ellipsis => Some("\u{2026}"),
+            SoftWrap::Guide => Some("\u{2502}"),
+            // Stands in the marker column, keeping the text aligned
+            SoftWrap::Indent => Some(" "),
+        }
+    }
+
+    // ASCII stand-ins for plain mode
+    pub(crate) fn ascii_marker(self) -> Option<&'static str> {
+        match self {
+            SoftWrap::Off => None,
+            SoftWrap::Ellipsis => Some(">"),
+            SoftWrap::Guide => Some("|"),
+            SoftWrap::Indent => Some(" "),
+        }
+    }
+}
+
+/// The width `Stderr` lays out against: an explicit override, or the
+/// terminal width looked up on first use and cached until refreshed.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Width {
+    fixed: Option<usize>,
+    cached: Cell<Option<usize>>,
+    pub(crate) policy: WidthPolicy,
+    // Columns taken by the current indentation; drawn elements fit in the rest
+    pub(crate) indent: usize,
+    // Extra cap on box width, on top of the policy
+    #[cfg(feature = "formatting")]
+    pub(crate) box_max: Option<usize>,
+}
+
+impl Width {
+    pub(crate) fn new(fixed: Option<usize>, policy: WidthPolicy) -> Self {
+        Self {
+            fixed,
+            cached: Cell::new(None),
+            policy,
+            indent: 0,
+            #[cfg(feature = "formatting")]
+            box_max: None,
+        }
+    }
+
+    /// Full available width (the override, or the terminal's)
+    pub(crate) fn available(&self) -> usize {
+        if let Some(fixed) = self.fixed {
+            return fixed;
+        }
+        match self.cached.get() {
+            Some(width) => width,
+            None => {
+                let width = term_width();
+                self.cached.set(Some(width));
+                width
+            }
+        }
+    }
+
+    /// Width for drawn elements, after the policy and the indentation
+    pub(crate) fn layout(&self) -> usize {
+        let width = match self.policy {
+            WidthPolicy::Fill => self.available(),
+            WidthPolicy::Cap(max) => self.available().min(max),
+        };
+        width.saturating_sub(self.indent)
+    }
+
+    #[cfg(feature = "formatting")]
+    pub(crate) fn with_box_max(mut self, box_max: Option<usize>) -> Self {
+        self.box_max = box_max;
+        self
+    }
+
+    /// Width boxes wrap their content to: the layout width, capped by the
+    /// box maximum if one is set
+    #[cfg(feature = "formatting")]
+    pub(crate) fn boxes(&self) -> usize {
+        match self.box_max {
+            Some(max) => self.layout().min(max),
+            None => self.layout(),
+        }
+    }
+
+    pub(crate) fn set_fixed(&mut self, fixed: Option<usize>) {
+        self.fixed = fixed;
+    }
+
+    /// Forgets the cached terminal width (e.g. after a resize)
+    pub(crate) fn refresh(&self) {
+        self.cached.set(None);
+    }
+}
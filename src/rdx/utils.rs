@@ -15,6 +15,12 @@
   #[path = "utils/grid.rs"]
   pub mod grid;
 
+  #[path = "utils/ansi.rs"]
+  pub mod ansi;
+
+  #[path = "utils/text.rs"]
+  pub mod text;
+
 
 
 
@@ -0,0 +1,267 @@
+//! src/lib/utils/ansi.rs
+
+//! # Pre-colored Text
+//!
+//! Splits text that already contains ANSI colors (output captured from a
+//! child process, `render_*` strings, ...) into styled spans, so it can be
+//! measured, wrapped and truncated by what is visible rather than by bytes.
+
+  use termcolor::{Color, ColorSpec};
+  use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+  use crate::esc::colors::spec_to_ansi;
+  use super::helpers::runs;
+  use super::text::{display_width, skip_osc};
+
+  /// A run of text drawn with one style
+  #[derive(Debug, Clone, PartialEq, Eq)]
+  pub struct StyledSpan {
+    pub spec: ColorSpec,
+    pub text: String,
+  }
+
+  impl StyledSpan {
+    /// Visible width in terminal columns
+    pub fn width(&self) -> usize {
+      self.text.width()
+    }
+  }
+
+  /// Tokenizes ANSI-colored text into styled spans.
+  ///
+  /// SGR sequences (colors and text attributes) become the spans' `ColorSpec`;
+  /// any other escape sequence (cursor movement, clearing) is dropped.
+  ///
+  /// ```
+  /// use stderr::parse_ansi;
+  ///
+  /// let spans = parse_ansi("\x1b[1;31merror:\x1b[0m not found");
+  /// assert_eq!(spans.len(), 2);
+  /// assert!(spans[0].spec.bold());
+  /// assert_eq!(spans[1].text, " not found");
+  /// ```
+  pub fn parse_ansi(text: &str) -> Vec<StyledSpan> {
+    let mut spans = Vec::new();
+    let mut spec = ColorSpec::new();
+    let mut current = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+      if c != '\x1b' {
+        current.push(c);
+        continue;
+      }
+      match chars.next() {
+        Some('[') => {}
+        Some(']') => { skip_osc(&mut chars); continue; }
+        _ => continue,
+      }
+      let mut params = String::new();
+      let mut last = None;
+      for c in chars.by_ref() {
+        if ('@'..='~').contains(&c) { last = Some(c); break; }
+        params.push(c);
+      }
+      if last != Some('m') { continue; }
+
+      let mut next = spec.clone();
+      apply_sgr(&mut next, &params);
+      if next != spec {
+        push_span(&mut spans, &spec, &mut current);
+        spec = next;
+      }
+    }
+    push_span(&mut spans, &spec, &mut current);
+    spans
+  }
+
+  /// Renders spans back to a string, resetting after every styled span.
+  pub fn render_spans(spans: &[StyledSpan]) -> String {
+    let mut out = String::new();
+    for span in spans {
+      push_styled(&mut out, &span.spec, &span.text);
+    }
+    out
+  }
+
+  /// Width of `text` on screen, ignoring escape sequences; the same as
+  /// [`display_width`](crate::utils::text::display_width).
+  ///
+  /// ```
+  /// use stderr::visible_width;
+  ///
+  /// assert_eq!(visible_width("\x1b[1mok\x1b[0m"), 2);
+  /// assert_eq!(visible_width("日本"), 4);
+  /// ```
+  pub fn visible_width(text: &str) -> usize {
+    display_width(text)
+  }
+
+  /// Word-wraps colored text to `width` visible columns, keeping each
+  /// word's style. Runs of whitespace collapse to one space; words longer
+  /// than `width` are split.
+  ///
+  /// ```
+  /// use stderr::{visible_width, wrap_ansi};
+  ///
+  /// let lines = wrap_ansi("\x1b[32mall tests\x1b[0m passed in 3s", 10);
+  /// assert_eq!(lines.len(), 3);
+  /// assert!(lines.iter().all(|line| visible_width(line) <= 10));
+  /// ```
+  pub fn wrap_ansi(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let spans = parse_ansi(text);
+    let cells = cells(&spans);
+    let mut lines = Vec::new();
+
+    for logical in cells.split(|&(_, c)| c == '\n') {
+      let mut line: Vec<(usize, char)> = Vec::new();
+      let mut used = 0;
+      for word in logical.split(|&(_, c)| c.is_whitespace()).filter(|w| !w.is_empty()) {
+        if let Some(&(span, _)) = line.last() {
+          if used + 1 + columns(word) <= width {
+            line.push((span, ' '));
+            used += 1;
+          } else {
+            lines.push(render_cells(&spans, &line));
+            line.clear();
+            used = 0;
+          }
+        }
+        for &cell in word {
+          let cell_width = char_width(cell.1);
+          if used + cell_width > width && !line.is_empty() {
+            lines.push(render_cells(&spans, &line));
+            line.clear();
+            used = 0;
+          }
+          line.push(cell);
+          used += cell_width;
+        }
+      }
+      lines.push(render_cells(&spans, &line));
+    }
+    lines
+  }
+
+  /// Cuts colored text down to `width` visible columns, ending with `…` when
+  /// anything was removed. Styles are kept and closed properly.
+  pub fn truncate_ansi(text: &str, width: usize) -> String {
+    elide_ansi(text, width, |room| room)
+  }
+
+  /// Cuts colored text down to `width` visible columns by replacing the
+  /// middle with `…`; `head` says how many of the columns left after the
+  /// `…` go to the start of the text, the rest go to its end.
+  pub(crate) fn elide_ansi(text: &str, width: usize, head: impl Fn(usize) -> usize) -> String {
+    let spans = parse_ansi(text);
+    let cells = cells(&spans);
+    if columns(&cells) <= width {
+      return render_cells(&spans, &cells);
+    }
+    // Keep what fits in `width - 1` columns, leaving room for the `…`
+    let room = width.saturating_sub(1);
+    let head_room = head(room).min(room);
+    let tail_room = room - head_room;
+    let mut used = 0;
+    let keep_head = cells.iter().take_while(|&&(_, c)| {
+      used += char_width(c);
+      used <= head_room
+    }).count();
+    used = 0;
+    let keep_tail = cells.iter().rev().take_while(|&&(_, c)| {
+      used += char_width(c);
+      used <= tail_room
+    }).count();
+    let mut out = render_cells(&spans, &cells[..keep_head]);
+    if width > 0 { out.push('\u{2026}'); }
+    out.push_str(&render_cells(&spans, &cells[cells.len() - keep_tail..]));
+    out
+  }
+
+  fn char_width(c: char) -> usize {
+    c.width().unwrap_or(0)
+  }
+
+  // Columns taken by a run of cells
+  fn columns(cells: &[(usize, char)]) -> usize {
+    cells.iter().map(|&(_, c)| char_width(c)).sum()
+  }
+
+  // Each visible character paired with the index of its span
+  fn cells(spans: &[StyledSpan]) -> Vec<(usize, char)> {
+    spans.iter()
+      .enumerate()
+      .flat_map(|(i, span)| span.text.chars().map(move |c| (i, c)))
+      .collect()
+  }
+
+  fn render_cells(spans: &[StyledSpan], cells: &[(usize, char)]) -> String {
+    let mut out = String::new();
+    for run in runs(cells, |a, b| a.0 == b.0) {
+      let text: String = run.iter().map(|&(_, c)| c).collect();
+      push_styled(&mut out, &spans[run[0].0].spec, &text);
+    }
+    out
+  }
+
+  fn push_styled(out: &mut String, spec: &ColorSpec, text: &str) {
+    if spec.is_none() {
+      out.push_str(text);
+    } else {
+      out.push_str(&spec_to_ansi(spec));
+      out.push_str(text);
+      out.push_str("\x1b[0m");
+    }
+  }
+
+  fn push_span(spans: &mut Vec<StyledSpan>, spec: &ColorSpec, text: &mut String) {
+    if text.is_empty() { return; }
+    match spans.last_mut() {
+      Some(last) if last.spec == *spec => last.text.push_str(text),
+      _ => spans.push(StyledSpan { spec: spec.clone(), text: text.clone() }),
+    }
+    text.clear();
+  }
+
+  const BASIC: [Color; 8] = [
+    Color::Black, Color::Red, Color::Green, Color::Yellow,
+    Color::Blue, Color::Magenta, Color::Cyan, Color::White,
+  ];
+
+  fn apply_sgr(spec: &mut ColorSpec, params: &str) {
+    let codes: Vec<u8> = params.split(';').map(|p| p.parse().unwrap_or(0)).collect();
+    let mut i = 0;
+    while i < codes.len() {
+      match codes[i] {
+        0 => *spec = ColorSpec::new(),
+        1 => { spec.set_bold(true); }
+        2 => { spec.set_dimmed(true); }
+        3 => { spec.set_italic(true); }
+        4 => { spec.set_underline(true); }
+        9 => { spec.set_strikethrough(true); }
+        22 => { spec.set_bold(false).set_dimmed(false); }
+        23 => { spec.set_italic(false); }
+        24 => { spec.set_underline(false); }
+        29 => { spec.set_strikethrough(false); }
+        n @ 30..=37 => { spec.set_fg(Some(BASIC[(n - 30) as usize])).set_intense(false); }
+        n @ 90..=97 => { spec.set_fg(Some(BASIC[(n - 90) as usize])).set_intense(true); }
+        39 => { spec.set_fg(None); }
+        n @ 40..=47 => { spec.set_bg(Some(BASIC[(n - 40) as usize])); }
+        n @ 100..=107 => { spec.set_bg(Some(Color::Ansi256(n - 100 + 8))); }
+        49 => { spec.set_bg(None); }
+        n @ (38 | 48) => {
+          let color = match codes.get(i + 1) {
+            Some(5) => codes.get(i + 2).map(|&c| { i += 2; Color::Ansi256(c) }),
+            Some(2) if i + 4 < codes.len() => {
+              i += 4;
+              Some(Color::Rgb(codes[i - 2], codes[i - 1], codes[i]))
+            }
+            _ => None,
+          };
+          if n == 38 { spec.set_fg(color); } else { spec.set_bg(color); }
+        }
+        _ => {}
+      }
+      i += 1;
+    }
+  }
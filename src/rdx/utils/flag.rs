@@ -11,8 +11,7 @@ use crate::esc::boxes::{BorderStyle, BoxChars};
 ///
 /// ```
 /// // In the calling code:
-/// use my_crate::utils::flag::flag_table;
-/// use my_crate::term_width; // Or however you access it
+/// use stderr::{flag_table, term_width, BorderStyle};
 ///
 /// let bitmask = 0b10101010;
 /// let labels = &["a", "b", "c", "d", "e", "f", "g", "h"];
@@ -35,10 +34,11 @@ where
 
     let required_width_for_one_row = 3 + (total_labels * 5) + 1;
 
+    // As many 5-column cells per row as fit, at least one
     let labels_per_chunk = if required_width_for_one_row <= term_width {
         total_labels
     } else {
-        (total_labels + 1) / 2
+        (term_width.saturating_sub(4) / 5).clamp(1, (total_labels + 1) / 2)
     };
 
     if labels_per_chunk == 0 { return String::new(); }
@@ -50,9 +50,9 @@ where
         let start_bit_index = chunk_index * labels_per_chunk;
 
         let h_four = chars.horizontal.repeat(4);
-        let top_border = format!(" {}{}{}", chars.top_left, h_four, std::iter::repeat(format!("{}{}", chars.top_t, h_four)).take(num_cols - 1).collect::<String>());
-        let mid_border = format!(" {}{}{}", chars.left_t, h_four, std::iter::repeat(format!("{}{}", chars.cross, h_four)).take(num_cols - 1).collect::<String>());
-        let bot_border = format!(" {}{}{}", chars.bottom_left, h_four, std::iter::repeat(format!("{}{}", chars.bottom_t, h_four)).take(num_cols - 1).collect::<String>());
+        let top_border = format!(" {}{}{}", chars.top_left, h_four, format!("{}{}", chars.top_t, h_four).repeat(num_cols - 1));
+        let mid_border = format!(" {}{}{}", chars.left_t, h_four, format!("{}{}", chars.cross, h_four).repeat(num_cols - 1));
+        let bot_border = format!(" {}{}{}", chars.bottom_left, h_four, format!("{}{}", chars.bottom_t, h_four).repeat(num_cols - 1));
 
         let mut index_row = format!(" {}", chars.vertical);
         let mut value_row = format!(" {}", chars.vertical);
@@ -1,10 +1,22 @@
 //! src/lib/utils/grid.rs
 
 use std::io;
-use termcolor::ColorSpec; // Ensure WriteColor is in scope for the methods
-use crate::stderr::Stderr; // Adjust the path to your Stderr struct
+use termcolor::{ColorSpec, WriteColor};
+use crate::stderr::{Stderr, OptionFlag}; // Adjust the path to your Stderr struct
+use crate::utils::helpers::render_to_string;
 
 pub fn print_color_grid(logger: &mut Stderr, cols: usize) -> io::Result<()> {
+    if logger.check_flag(OptionFlag::Quiet) { return Ok(()); }
+    logger.write_block("colors", |log| write_color_grid(&mut log.writer, cols))
+}
+
+/// Renders the 256-color grid to a `String` instead of writing it to a logger.
+/// With `ansi = false` only the cell numbers remain.
+pub fn render_color_grid(cols: usize, ansi: bool) -> String {
+    render_to_string(ansi, |buf| write_color_grid(buf, cols))
+}
+
+fn write_color_grid<W: WriteColor>(w: &mut W, cols: usize) -> io::Result<()> {
     for i in 0..256 {
 
         // Determine the best foreground color (black or white) for contrast
@@ -39,19 +51,17 @@ pub fn print_color_grid(logger: &mut Stderr, cols: usize) -> io::Result<()> {
         spec.set_bg(Some(termcolor::Color::Ansi256(i as u8)));
         spec.set_fg(Some(fg_color));
 
-        logger.set_color(&spec)?;
-        logger.write(&format!(" {:<3} .", i))?;
-        logger.reset()?;
+        w.set_color(&spec)?;
+        write!(w, " {:<3} .", i)?;
+        w.reset()?;
 
         if (i + 1) % cols == 0 {
-            logger.newline()?;
-        } else {
-            logger.write("")?;
+            writeln!(w)?;
         }
     }
 
     // Ensure we always end with a final newline for clean terminal output
-    logger.newline()?;
+    writeln!(w)?;
 
     Ok(())
 }
@@ -1,6 +1,7 @@
 //! src/lib/helpers.rs
 
   use std::io::{self};
+  use termcolor::Buffer;
   use terminal_size::terminal_size;
 
 
@@ -9,6 +10,34 @@
     std::iter::repeat(ch).take(n).collect()
   }
 
+  /// Runs a renderer against an in-memory buffer and returns the output as a `String`.
+  /// When `ansi` is false the color calls are dropped and the text is plain.
+  pub(crate) fn render_to_string<F>(ansi: bool, render: F) -> String
+  where
+    F: FnOnce(&mut Buffer) -> io::Result<()>,
+  {
+    let mut buf = if ansi { Buffer::ansi() } else { Buffer::no_color() };
+    let _ = render(&mut buf); // writes into a Vec cannot fail
+    String::from_utf8_lossy(buf.as_slice()).into_owned()
+  }
+
+  /// Splits `items` into runs of neighbours for which `same` holds, like
+  /// `slice::chunk_by` (which needs Rust 1.77).
+  pub(crate) fn runs<T>(items: &[T], same: impl Fn(&T, &T) -> bool) -> Vec<&[T]> {
+    let mut runs = Vec::new();
+    let mut start = 0;
+    for i in 1..=items.len() {
+      if i == items.len() || !same(&items[i - 1], &items[i]) {
+        runs.push(&items[start..i]);
+        start = i;
+      }
+    }
+    runs
+  }
+
+  // Moved to `utils::text`; kept here for existing imports
+  pub use super::text::strip_ansi;
+
   /// Gets the terminal width from the environment or a default.
   pub fn term_width() -> usize {
       terminal_size()
@@ -21,10 +50,118 @@
     std::env::var(key)
   }
 
-  #[allow(dead_code)]
+  /// Reads one line from stdin.
+  ///
+  /// With the `interactive` feature this goes through the same reader
+  /// thread as timed prompts, so a line still owed to a prompt that timed
+  /// out answers this call instead of racing it for stdin.
   pub fn readline() -> io::Result<String> {
+    #[cfg(feature = "interactive")]
+    return timed::readline_shared();
+    #[cfg(not(feature = "interactive"))]
+    read_stdin_line()
+  }
+
+  fn read_stdin_line() -> io::Result<String> {
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+    Ok(input)
+  }
+
+  #[cfg(feature = "interactive")]
+  pub(crate) use timed::{line_read_pending, readline_timeout};
+
+  #[cfg(feature = "interactive")]
+  mod timed {
+    use std::io;
+    use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+    use std::sync::{Mutex, PoisonError};
+    use std::thread;
+    use std::time::Duration;
+    use super::read_stdin_line;
+
+    /// `readline` that gives up after `timeout`, returning `None`.
+    ///
+    /// A single background thread does all line reads. A read abandoned by
+    /// a timeout stays in flight, and the line it eventually gets answers
+    /// the next call, so no input is lost.
+    pub(crate) fn readline_timeout(timeout: Duration) -> io::Result<Option<String>> {
+      recv_line(Some(timeout))
+    }
+
+    /// `readline` through the reader thread, waiting as long as it takes
+    pub(crate) fn readline_shared() -> io::Result<String> {
+      recv_line(None).map(Option::unwrap_or_default)
+    }
+
+    /// Whether a timed-out read still owns stdin; raw key reads would
+    /// have their bytes taken by it
+    pub(crate) fn line_read_pending() -> bool {
+      let reader = TIMED_READER.lock().unwrap_or_else(PoisonError::into_inner);
+      reader.as_ref().is_some_and(|reader| reader.in_flight)
+    }
+
+    fn recv_line(timeout: Option<Duration>) -> io::Result<Option<String>> {
+      let mut reader = TIMED_READER.lock().unwrap_or_else(PoisonError::into_inner);
+      let reader = reader.get_or_insert_with(TimedReader::spawn);
+
+      if !reader.in_flight {
+        reader.requests.send(()).map_err(|_| reader_gone())?;
+        reader.in_flight = true;
+      }
+      let line = match timeout {
+        Some(timeout) => reader.lines.recv_timeout(timeout),
+        None => reader.lines.recv().map_err(|_| RecvTimeoutError::Disconnected),
+      };
+      match line {
+        Ok(line) => {
+          reader.in_flight = false;
+          line.map(Some)
+        }
+        Err(RecvTimeoutError::Timeout) => Ok(None),
+        Err(RecvTimeoutError::Disconnected) => Err(reader_gone()),
+      }
+    }
+
+    static TIMED_READER: Mutex<Option<TimedReader>> = Mutex::new(None);
+
+    struct TimedReader {
+      requests: Sender<()>,
+      lines: Receiver<io::Result<String>>,
+      in_flight: bool,
+    }
+
+    impl TimedReader {
+      fn spawn() -> Self {
+        let (requests, pending) = mpsc::channel::<()>();
+        let (send, lines) = mpsc::channel();
+        thread::spawn(move || {
+          for () in pending {
+            if send.send(read_stdin_line()).is_err() { break; }
+          }
+        });
+        Self { requests, lines, in_flight: false }
+      }
+    }
+
+    fn reader_gone() -> io::Error {
+      io::Error::new(io::ErrorKind::BrokenPipe, "stdin reader thread stopped")
+    }
+  }
+
+  // One buffered reader for the whole process: a fresh `BufReader` per call
+  // would drop whatever it had read past the first newline
+  #[cfg(feature = "async")]
+  static ASYNC_STDIN: once_cell::sync::Lazy<tokio::sync::Mutex<tokio::io::BufReader<tokio::io::Stdin>>> =
+    once_cell::sync::Lazy::new(|| tokio::sync::Mutex::new(tokio::io::BufReader::new(tokio::io::stdin())));
+
+  /// Async `readline` using tokio's stdin, so prompts don't block the runtime.
+  #[cfg(feature = "async")]
+  pub async fn readline_async() -> io::Result<String> {
+    use tokio::io::AsyncBufReadExt;
+
     let mut input = String::new();
-    io::stdin().read_line(&mut input)?; // Now this works!
-    Ok(input) // If successful, wrap the result in `Ok`
+    ASYNC_STDIN.lock().await.read_line(&mut input).await?;
+    Ok(input)
   }
 
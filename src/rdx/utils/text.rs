@@ -0,0 +1,105 @@
+//! src/lib/utils/text.rs
+
+//! # Measuring Text
+//!
+//! Escape-code-aware measuring for strings that may already be colored:
+//! what the tables, boxes and panels use to line cells up, exported so
+//! callers building their own layouts can do the same, plus shortening
+//! with an ellipsis so long paths and keys fit a column.
+
+  use std::iter::Peekable;
+  use std::str::Chars;
+  use unicode_width::UnicodeWidthStr;
+
+  use super::ansi::elide_ansi;
+
+  /// Where [`truncate`] puts the `…` when it shortens text
+  #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+  pub enum Ellipsis {
+    /// Keep the end: `…/config/secrets.env`, for paths
+    Start,
+    /// Keep both ends: `@myapp…config`, for keys and identifiers
+    Middle,
+    /// Keep the start: `Deploying to prod…`
+    #[default]
+    End,
+  }
+
+  /// Removes ANSI escape sequences from `text`: CSI (colors, cursor
+  /// movement) and OSC (titles, `ESC ]8` hyperlinks, whose target is
+  /// never shown).
+  ///
+  /// ```
+  /// use stderr::utils::text::strip_ansi;
+  ///
+  /// assert_eq!(strip_ansi("\x1b[1;31merror\x1b[0m: \x1b[2Kdone"), "error: done");
+  /// assert_eq!(strip_ansi("\x1b]8;;https://example.com\x1b\\docs\x1b]8;;\x1b\\"), "docs");
+  /// ```
+  pub fn strip_ansi(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+      if c != '\x1b' {
+        out.push(c);
+        continue;
+      }
+      match chars.next() {
+        // CSI: parameters until a final byte in '@'..='~'
+        Some('[') => {
+          for c in chars.by_ref() {
+            if ('@'..='~').contains(&c) { break; }
+          }
+        }
+        Some(']') => skip_osc(&mut chars),
+        _ => {}
+      }
+    }
+    out
+  }
+
+  /// Consumes the rest of an OSC sequence (after `ESC ]`): everything up to
+  /// BEL or the string terminator `ESC \`
+  pub(crate) fn skip_osc(chars: &mut Peekable<Chars>) {
+    while let Some(c) = chars.next() {
+      if c == '\x07' { break; }
+      if c == '\x1b' && chars.peek() == Some(&'\\') {
+        chars.next();
+        break;
+      }
+    }
+  }
+
+  /// Columns `text` takes on screen: escape sequences count for nothing,
+  /// wide characters (CJK, most emoji) for two and combining marks for none.
+  ///
+  /// ```
+  /// use stderr::utils::text::display_width;
+  ///
+  /// assert_eq!(display_width("\x1b[32mok\x1b[0m"), 2);
+  /// assert_eq!(display_width("日本"), 4);
+  /// assert_eq!(display_width("e\u{301}"), 1);
+  /// ```
+  pub fn display_width(text: &str) -> usize {
+    strip_ansi(text).width()
+  }
+
+  /// Shortens `text` to at most `max` columns, replacing what was cut with
+  /// `…` at the [`Ellipsis`] position. Text that fits is returned as is;
+  /// colors are kept and closed properly.
+  ///
+  /// ```
+  /// use stderr::utils::text::{display_width, truncate, Ellipsis};
+  ///
+  /// let path = "/home/deploy/app/config/secrets.env";
+  /// assert_eq!(truncate(path, 20, Ellipsis::Start), "…/config/secrets.env");
+  /// assert_eq!(truncate(path, 20, Ellipsis::Middle), "/home/dep…ecrets.env");
+  /// assert_eq!(truncate(path, 20, Ellipsis::End), "/home/deploy/app/co…");
+  /// assert_eq!(display_width(&truncate("\x1b[1mbold text\x1b[0m", 5, Ellipsis::Middle)), 5);
+  /// ```
+  pub fn truncate(text: &str, max: usize, ellipsis: Ellipsis) -> String {
+    match ellipsis {
+      Ellipsis::Start => elide_ansi(text, max, |_| 0),
+      Ellipsis::Middle => elide_ansi(text, max, |room| room / 2),
+      Ellipsis::End => elide_ansi(text, max, |room| room),
+    }
+  }